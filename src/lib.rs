@@ -1,6 +0,0 @@
-mod board;
-mod errors;
-mod solve;
-pub use board::Board;
-pub use errors::UpdateError;
-pub use solve::BoardState;
@@ -0,0 +1,146 @@
+//! dropping repeated puzzles from a collection
+//!
+//! a collection has one puzzle per line as 81 characters, with a digit for each given and `.`
+//! or `0` for each blank, or packed with letters for runs of blanks (see `io::read_gordon`).
+//! blank lines and lines starting with `#` are skipped
+
+use anyhow::{Context, Result};
+use std::{
+    collections::HashSet,
+    io::{BufRead, Write},
+};
+use sudoku_core::{io, Board};
+
+/// how many puzzles `dedup` read and dropped
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Counts {
+    pub read: usize,
+    /// the same givens in the same places as an earlier puzzle
+    pub duplicates: usize,
+    /// an earlier puzzle relabeled, shuffled, or transposed
+    pub isomorphs: usize,
+}
+
+impl Counts {
+    pub fn kept(&self) -> usize {
+        self.read - self.duplicates - self.isomorphs
+    }
+}
+
+/// copies the first puzzle of each canonical form from `input` to `output`, unchanged
+///
+/// exact repeats are caught before canonicalizing, which is the slow part. only packed grids
+/// are kept, so memory grows by about 80 bytes per distinct puzzle
+pub fn dedup(input: impl BufRead, mut output: impl Write) -> Result<Counts> {
+    let mut counts = Counts::default();
+    let mut seen_lines = HashSet::new();
+    let mut seen_forms = HashSet::new();
+    // `lines` already ends lines at `\n` and `\r\n`, which leaves any lone `\r` to split on
+    let lines = input.lines().flat_map(|line| match line {
+        Ok(line) => io::lines(&line)
+            .map(|line| Ok(line.to_string()))
+            .collect::<Vec<_>>(),
+        Err(err) => vec![Err(err)],
+    });
+    for (number, line) in lines.enumerate() {
+        let line = line?;
+        let puzzle = line.trim();
+        if puzzle.is_empty() || puzzle.starts_with('#') {
+            continue;
+        }
+        let board = io::read_gordon(puzzle).with_context(|| format!("line {}", number + 1))?;
+        counts.read += 1;
+        if !seen_lines.insert(raw(&board)) {
+            counts.duplicates += 1;
+            continue;
+        }
+        if !seen_forms.insert(board.canonical_form().packed()) {
+            counts.isomorphs += 1;
+            continue;
+        }
+        writeln!(output, "{puzzle}")?;
+    }
+    output.flush()?;
+    Ok(counts)
+}
+
+/// the givens packed two cells to a byte, like `CanonicalForm::packed` but without moving them
+fn raw(board: &Board) -> [u8; 41] {
+    let givens: [[Option<usize>; 9]; 9] = board.clone().into();
+    let mut packed = [0; 41];
+    for (i, cell) in givens.iter().flatten().enumerate() {
+        packed[i / 2] |= (cell.unwrap_or(0) as u8) << (4 * (i % 2));
+    }
+    packed
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PUZZLE: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+
+    #[test]
+    fn dedup_keeps_the_first_of_each_form() {
+        // the second line swaps the first two rows, the third relabels 1 and 2
+        let swapped = format!("{}{}{}", &PUZZLE[9..18], &PUZZLE[..9], &PUZZLE[18..]);
+        let relabeled: String = PUZZLE
+            .chars()
+            .map(|c| match c {
+                '1' => '2',
+                '2' => '1',
+                c => c,
+            })
+            .collect();
+        let input = [
+            PUZZLE,
+            &swapped[..],
+            "# a comment",
+            PUZZLE,
+            &relabeled[..],
+            "",
+        ]
+        .join("\n");
+        let mut output = vec![];
+
+        let counts = dedup(input.as_bytes(), &mut output).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), format!("{PUZZLE}\n"));
+        assert_eq!(
+            counts,
+            Counts {
+                read: 4,
+                duplicates: 1,
+                isomorphs: 2
+            }
+        );
+        assert_eq!(counts.kept(), 1);
+    }
+    #[test]
+    fn dedup_reads_any_line_ending() {
+        let input = format!("{PUZZLE}\r\n{PUZZLE}\r{PUZZLE}\n# a comment\r\n{PUZZLE}");
+
+        let counts = dedup(input.as_bytes(), vec![]).unwrap();
+
+        assert_eq!(counts.read, 4);
+        assert_eq!(counts.duplicates, 3);
+    }
+    #[test]
+    fn dedup_points_at_bad_lines() {
+        let error = dedup("12!".as_bytes(), vec![]).unwrap_err();
+
+        assert_eq!(error.to_string(), "line 1");
+        assert!(format!("{error:#}").contains("'!'"));
+    }
+    #[test]
+    fn dedup_reads_packed_puzzles() {
+        let packed = "53b7d6b195d98d6a8c6c34b8a3b17c2c6a6d28d419b5d8b79";
+        let input = format!("{PUZZLE}\n{packed}");
+
+        let counts = dedup(input.as_bytes(), vec![]).unwrap();
+
+        assert_eq!(counts.read, 2);
+        assert_eq!(counts.duplicates, 1);
+    }
+}
@@ -0,0 +1,1465 @@
+mod dedup;
+mod ocr;
+mod report;
+mod resources;
+mod selftest;
+mod submission;
+mod techniques;
+
+use anyhow::{bail, Context, Result};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use ocr::{ImageImporter, OcrCommand};
+use rand::{rngs::StdRng, SeedableRng};
+use report::{Summary, Verbosity};
+use std::{
+    fs,
+    io::{BufReader, BufWriter, Read},
+    path::{Path, PathBuf},
+    process, thread,
+    time::{Duration, Instant},
+};
+use sudoku_core::{
+    capabilities::{Capabilities, Format},
+    events::{Discard, Event, EventSink, JsonLines},
+    io,
+    metrics::Metrics,
+    schema,
+    store::SolutionStore,
+    strategy_names, Arrow, Board, Cage, CellPos, DigStrategy, GeneratorOptions, Inequality, Parity,
+    Reanalysis, RuleSet, Sandwich, Setter, Shape, Size, SolveError, SolveStats, SolvedBoard,
+    Solver, SolverBackend, Tier, Unit, ValueOrder, Variant,
+};
+
+#[derive(Parser)]
+#[command(
+    name = "sudoku",
+    about = "solves sudoku puzzles stored as csv files, writing the solution to out.csv",
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    solve: SolveArgs,
+    /// only print errors
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+    /// also print the grade and the grid once done
+    #[arg(short, long, global = true)]
+    verbose: bool,
+    /// also send a line of JSON for each event of the run to `stdout` or append it to a file
+    #[arg(long, global = true, value_name = "stdout|FILE")]
+    events: Option<String>,
+    /// write counters and histograms of the run to a file in the Prometheus text format, for a
+    /// node exporter's textfile collector
+    #[arg(long, global = true, value_name = "FILE")]
+    metrics: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// run the built in checks to make sure this build works
+    Selftest,
+    /// list the formats, engines, strategies, and variants this build supports
+    Capabilities {
+        /// print it as a line of JSON for other programs to read
+        #[arg(long)]
+        json: bool,
+    },
+    /// relabel and shuffle a puzzle into an equivalent one, writing it to out.csv
+    Disguise {
+        /// the puzzle to disguise
+        file: PathBuf,
+    },
+    /// report how a puzzle's givens are laid out: how many, their symmetry, and how many are in
+    /// each house
+    Analyze {
+        /// the puzzle to analyze
+        file: PathBuf,
+    },
+    /// rate how hard a puzzle is from the techniques it takes to solve
+    Rate {
+        /// the puzzle to rate
+        file: PathBuf,
+    },
+    /// check that a puzzle can be solved without guessing, listing the deductions that prove it
+    Certify {
+        /// the puzzle to check
+        file: PathBuf,
+        /// the hardest tier of techniques the deductions may use
+        #[arg(long, value_enum, default_value_t = Level::Expert)]
+        level: Level,
+    },
+    /// watch a puzzle while it is being set, printing whether it has one solution and its grade
+    /// each time the file is saved
+    Watch {
+        /// the puzzle being set
+        file: PathBuf,
+    },
+    /// show the next deduction the solver would make, without solving the rest
+    Hint {
+        /// the puzzle, as far as it has been solved
+        file: PathBuf,
+    },
+    /// write out every deduction of a solve as a sentence, such as for teaching material
+    Explain {
+        /// the puzzle to explain
+        file: PathBuf,
+    },
+    /// write what each deduction of a solve changed to trace.txt, for attaching to bug reports
+    Trace {
+        /// the puzzle to trace
+        file: PathBuf,
+    },
+    /// step through a trace written by `trace`, printing each technique and the final board
+    Replay {
+        /// the trace to replay
+        file: PathBuf,
+    },
+    /// fit the difficulty model to a collection of rated puzzles, writing it to difficulty.model
+    #[cfg(feature = "difficulty-model")]
+    FitDifficulty {
+        /// one puzzle per line as 81 characters, then a space and how hard people found it
+        file: PathBuf,
+    },
+    /// check a puzzle's JSON against the schema, listing every problem with it
+    ValidateSchema {
+        /// the JSON document to check
+        file: PathBuf,
+    },
+    /// solve a puzzle of several boards that share cells, such as a samurai, laid out in the
+    /// puzzle JSON
+    Composite {
+        /// the JSON document with the puzzle's boards
+        file: PathBuf,
+    },
+    /// copy a collection of puzzles, one per line, to deduped.txt without repeats or isomorphs
+    Dedup {
+        /// the collection to read
+        file: PathBuf,
+    },
+    /// grade every puzzle of a collection, one per line, keeping the grades in a store so puzzles
+    /// graded before, or isomorphs of them, aren't graded again
+    Grade {
+        /// the collection to read
+        file: PathBuf,
+        /// the store to read grades from and save them to
+        #[arg(long, default_value = "grades.store")]
+        cache: PathBuf,
+        /// grade every puzzle, without reading or saving the store
+        #[arg(long)]
+        no_cache: bool,
+    },
+    /// check a submitted puzzle for contradictions, a single solution, its rating, and repeats,
+    /// printing one verdict
+    CheckSubmission {
+        /// the puzzle as 81 characters, like a line of a collection
+        file: PathBuf,
+        /// the collection of accepted puzzles to look for it in
+        #[arg(long, value_name = "FILE")]
+        against: Option<PathBuf>,
+    },
+    /// print the solution an answer code from `--answer-code` stands for
+    Reveal {
+        /// the code, dashes and case don't matter
+        code: String,
+    },
+    /// dig a new puzzle with a single solution out of a random grid, writing it to out.csv
+    Generate {
+        /// the order to try digging cells out in
+        #[arg(long, value_enum, default_value_t = Dig::Random)]
+        dig: Dig,
+        /// stop digging once the puzzle is down to this many givens
+        #[arg(long, default_value_t = 0)]
+        givens: usize,
+        /// the seed for the grid and the digging, so a puzzle can be made again
+        #[arg(long)]
+        seed: Option<u64>,
+        /// how many cells across, 4 or 6 for a mini sudoku, which is dug in a random order
+        #[arg(long, default_value_t = 9)]
+        size: usize,
+        /// dig the puzzle out of this solved grid instead of a random one, so it is the answer
+        #[arg(long, value_name = "FILE", conflicts_with = "size")]
+        solution: Option<PathBuf>,
+    },
+    /// solve a Latin square, which needs every value once in each row and column but has no
+    /// houses, writing it to out.csv
+    Latin {
+        /// the square to solve, with as many rows as cells in each
+        #[arg(required_unless_present = "size")]
+        file: Option<PathBuf>,
+        /// make a random square this many cells across instead, from 4 to 9
+        #[arg(long, conflicts_with = "file")]
+        size: Option<usize>,
+    },
+    /// solve a 4x4, 6x6, 9x9, or 16x16 sudoku, from a csv with values 1-16 or a grid of one
+    /// symbol a cell, such as `0`-`F` for 1-16 and `.` for blanks, writing it to out.csv
+    Grid {
+        /// the puzzle to solve, its size taken from how many rows or cells it has
+        file: PathBuf,
+        /// the characters the values are written with when the puzzle isn't a csv
+        #[arg(long, value_enum, default_value_t = Symbols::Hex)]
+        symbols: Symbols,
+    },
+    /// print a puzzle as a grid and draw it to board.svg
+    Render {
+        /// the puzzle to draw
+        file: PathBuf,
+        /// draw the solution instead, with the values the solver filled in set apart from the
+        /// givens: in brackets when printed and in blue rather than bold black when drawn
+        #[arg(long)]
+        diff: bool,
+    },
+    /// sample solutions of an open puzzle and draw how often each cell takes each value to
+    /// heatmap.svg
+    Heatmap {
+        /// the puzzle to sample
+        file: PathBuf,
+        /// how many solutions to sample
+        #[arg(long, default_value_t = 100)]
+        samples: usize,
+    },
+}
+
+#[derive(Args)]
+struct SolveArgs {
+    /// the puzzle to solve
+    #[arg(required_unless_present = "from_image")]
+    file: Option<PathBuf>,
+    /// read the puzzle from a photo with `--ocr-cmd` instead of from a csv file
+    #[arg(long, conflicts_with = "file", requires = "ocr_cmd")]
+    from_image: Option<PathBuf>,
+    /// the command that reads the digits out of the image, see `OcrCommand`
+    #[arg(long, requires = "from_image")]
+    ocr_cmd: Option<String>,
+    /// digits read with less confidence than this are left blank
+    #[arg(long, default_value_t = 0.5, requires = "from_image")]
+    min_confidence: f32,
+    /// read the file as a sukaku, the candidates of every cell, instead of as a csv of givens
+    #[arg(long, conflicts_with = "from_image")]
+    sukaku: bool,
+    /// after the solution, write a second grid with 1 for given cells and 0 for solved ones
+    #[arg(long)]
+    mark_givens: bool,
+    /// also print a short code for the solution that `sudoku reveal` turns back into it, so an
+    /// answer key can be shared without giving the answer away
+    #[arg(long)]
+    answer_code: bool,
+    /// exit with an error instead of solving if the puzzle doesn't have exactly one solution
+    #[arg(long)]
+    require_unique: bool,
+    /// what to write for cells the solver couldn't fill in
+    #[arg(long, value_enum, default_value_t = Partial::Fail)]
+    partial: Partial,
+    /// only use these solving techniques, such as `xwing,swordfish`
+    #[arg(long, value_delimiter = ',')]
+    enable: Vec<String>,
+    /// don't use these solving techniques, even if they are in `--enable`
+    #[arg(long, value_delimiter = ',')]
+    disable: Vec<String>,
+    /// how to search once the solving techniques run out
+    #[arg(long, value_enum, default_value_t = Backend::Backtracking)]
+    backend: Backend,
+    /// extra rules the solution has to follow, which only the backtracking backend supports
+    #[arg(long, value_enum, value_delimiter = ',')]
+    variant: Vec<Variation>,
+    /// a preset of variants for a common kind of puzzle, one of classic, x, windoku,
+    /// antiknight-classic, or killer (which takes its cages from `--cages`), added to any
+    /// `--variant`s
+    #[arg(long, value_name = "PRESET")]
+    rules: Option<String>,
+    /// the cages for `--variant killer`, one per line as the sum and then the cells, such as
+    /// `15 r1c1 r1c2`
+    #[arg(long, value_name = "FILE")]
+    cages: Option<PathBuf>,
+    /// the arrows for `--variant arrow`, one per line as the circle and then the cells along the
+    /// arrow, such as `r1c1 r2c2 r3c3`
+    #[arg(long, value_name = "FILE")]
+    arrows: Option<PathBuf>,
+    /// the clues for `--variant sandwich`, one per line as a row or column and then the sum
+    /// between its 1 and 9, such as `r1 12` or `c4 0`
+    #[arg(long, value_name = "FILE")]
+    sandwiches: Option<PathBuf>,
+    /// the signs for `--variant greater-than`, one per line as two cells next to each other with
+    /// `>` or `<` between them, such as `r1c1 > r1c2`
+    #[arg(long, value_name = "FILE")]
+    inequalities: Option<PathBuf>,
+    /// the marks for `--variant even-odd`, either nine rows of nine characters with `e` for even,
+    /// `o` for odd, and `.` for unmarked cells, or one mark per line such as `r1c1 even`
+    #[arg(long, value_name = "FILE")]
+    parities: Option<PathBuf>,
+    /// the order the backtracking search tries a cell's values in when it guesses
+    #[arg(long, value_enum, default_value_t = Order::Ascending)]
+    value_order: Order,
+    /// the seed for `--value-order random`, `--shuffle-ties`, and `--backend anneal`, so a run
+    /// can be repeated
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    /// break ties in which cell to guess and which value to try first at random, by `--seed`,
+    /// instead of always taking the first
+    #[arg(long)]
+    shuffle_ties: bool,
+    /// give up if the search has to stack more than this many guesses
+    #[arg(long)]
+    max_depth: Option<usize>,
+    /// give up after this many seconds, writing how far the search got if `--partial` allows it
+    #[arg(long)]
+    timeout: Option<f64>,
+    /// print the search stats with the peak memory, cpu time, and allocations of the run
+    #[arg(long)]
+    report_resources: bool,
+    /// time the checks of each kind of unit and each solving technique, and print a table of
+    /// where the solve's time went
+    #[arg(long)]
+    profile: bool,
+    /// append a row of the search stats to a csv file, with how many candidates each technique
+    /// ruled out
+    #[arg(long, value_name = "FILE")]
+    stats_csv: Option<PathBuf>,
+}
+
+/// see `SolverBackend`
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Backend {
+    /// guess cell by cell, using the solving techniques along the way
+    Backtracking,
+    /// dancing links, which is faster on hard puzzles but skips the techniques
+    Dlx,
+    /// simulated annealing seeded by `--seed`, a baseline that can give up on hard puzzles
+    Anneal,
+}
+
+/// see `Variant`
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Variation {
+    /// four extra 3x3 windows that each need every value once
+    Windoku,
+    /// the two long diagonals also need every value once
+    Diagonal,
+    /// cages from `--cages` whose values add up to their sum without repeating
+    Killer,
+    /// cells a chess knight's move apart can't hold the same value
+    AntiKnight,
+    /// cells next to each other in a row or column can't hold consecutive values
+    NonConsecutive,
+    /// arrows from `--arrows` whose circle is the sum of the cells along them
+    Arrow,
+    /// clues from `--sandwiches` for the sum between the 1 and the 9 of a row or column
+    Sandwich,
+    /// signs from `--inequalities` between cells next to each other
+    GreaterThan,
+    /// marks from `--parities` for cells that hold an even or an odd value
+    EvenOdd,
+}
+
+/// see `ValueOrder`
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Order {
+    /// smallest value first
+    Ascending,
+    /// the value the fewest neighbouring cells could also be first
+    LeastConstraining,
+    /// the value the most neighbouring cells could also be first
+    PeerFrequency,
+    /// a shuffled order picked by `--seed`
+    Random,
+}
+
+/// see `Tier`
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Level {
+    /// singles only
+    Easy,
+    /// intersections and naked and hidden pairs or triples
+    Medium,
+    /// fish, wings, single digit patterns, coloring, and quads
+    Hard,
+    /// almost locked sets, bug+1, and forcing chains
+    Expert,
+}
+
+/// see `io::SymbolMap`
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Symbols {
+    /// `0`-`F`
+    Hex,
+    /// `1`-`9` and then `A`-`G`
+    DigitsThenLetters,
+    /// `A`-`Z`
+    Letters,
+}
+
+/// see `DigStrategy`
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Dig {
+    /// every cell in a random order
+    Random,
+    /// a cell and its mirror through the center together, for half turn symmetry
+    SymmetricPairs,
+    /// the outer ring first, leaving the givens in the middle
+    BorderFirst,
+    /// the center first, leaving the givens around the edges
+    CenterOut,
+}
+
+/// how to write a board that isn't completely solved
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Partial {
+    /// write nothing and exit with an error
+    Fail,
+    /// leave unsolved cells empty
+    Blank,
+    /// write the candidates left in unsolved cells, such as `248`
+    Candidates,
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let verbosity = Verbosity::new(cli.quiet, cli.verbose);
+    let events = match event_sink(cli.events.as_deref()) {
+        Ok(events) => events,
+        Err(why) => {
+            println!("error: {why:?}");
+            process::exit(1)
+        }
+    };
+    let mut metrics = Metrics::default();
+    let mut sinks = (events, &mut metrics);
+    let events: &mut dyn EventSink = &mut sinks;
+    let result = match cli.command {
+        Some(Command::Selftest) => process::exit(if selftest::run() { 0 } else { 1 }),
+        Some(Command::Capabilities { json }) => {
+            capabilities(json);
+            return;
+        }
+        Some(Command::Disguise { file }) => disguise(&file),
+        Some(Command::Analyze { file }) => analyze(&file),
+        Some(Command::Rate { file }) => rate(&file),
+        Some(Command::Certify { file, level }) => certify(&file, level),
+        Some(Command::Watch { file }) => watch(&file),
+        Some(Command::Hint { file }) => hint(&file),
+        Some(Command::Explain { file }) => explain(&file),
+        Some(Command::Trace { file }) => trace(&file),
+        Some(Command::Replay { file }) => replay(&file),
+        #[cfg(feature = "difficulty-model")]
+        Some(Command::FitDifficulty { file }) => fit_difficulty(&file),
+        Some(Command::ValidateSchema { file }) => validate_schema(&file),
+        Some(Command::Composite { file }) => solve_composite(&file),
+        Some(Command::Dedup { file }) => dedup(&file, verbosity, events),
+        Some(Command::Grade {
+            file,
+            cache,
+            no_cache,
+        }) => grade(&file, (!no_cache).then_some(&cache)),
+        Some(Command::CheckSubmission { file, against }) => {
+            check_submission(&file, against.as_deref(), events)
+        }
+        Some(Command::Reveal { code }) => reveal(&code),
+        Some(Command::Generate {
+            dig,
+            givens,
+            seed,
+            size,
+            solution,
+        }) => generate(dig, givens, seed, size, solution.as_ref()),
+        Some(Command::Latin { file, size }) => latin(file.as_ref(), size),
+        Some(Command::Grid { file, symbols }) => solve_grid(&file, symbols),
+        Some(Command::Render { file, diff }) => render(&file, diff),
+        Some(Command::Heatmap { file, samples }) => heat_map(&file, samples),
+        None => run(cli.solve, verbosity, events),
+    };
+    if let Err(why) = &result {
+        // the run already failed, so a failure to report it too can't make things worse
+        let _ = events.emit(&Event::Failed {
+            error: format!("{why:#}"),
+        });
+    }
+    drop(sinks);
+    let written = match cli.metrics {
+        Some(path) => fs::write(&path, metrics.to_prometheus())
+            .with_context(|| format!("writing metrics to {}", path.display())),
+        None => Ok(()),
+    };
+    if let Err(why) = result.and(written) {
+        println!("error: {why:?}");
+        process::exit(1)
+    }
+}
+/// where `--events` sends the run's events
+fn event_sink(target: Option<&str>) -> Result<Box<dyn EventSink>> {
+    Ok(match target {
+        None => Box::new(Discard),
+        Some("stdout") => Box::new(JsonLines::stdout()),
+        Some(path) => {
+            let file = fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .with_context(|| format!("opening {path} for events"))?;
+            Box::new(JsonLines(file))
+        }
+    })
+}
+fn run(args: SolveArgs, verbosity: Verbosity, events: &mut dyn EventSink) -> Result<()> {
+    let (input, board) = match (args.file, args.from_image, args.ocr_cmd) {
+        (Some(file_name), _, _) if args.sukaku => {
+            let board = io::read_sukaku(&fs::read_to_string(&file_name)?)?;
+            (file_name, board)
+        }
+        (Some(file_name), _, _) => {
+            let board = Board::build_wide(read_file(&file_name)?)?;
+            (file_name, board)
+        }
+        (None, Some(image), Some(command)) => {
+            let board = read_image(&image, &OcrCommand(command), args.min_confidence)?;
+            (image, board)
+        }
+        // clap requires a file or an image with a command unless there is a subcommand
+        _ => unreachable!("clap checks the puzzle arguments"),
+    };
+    events.emit(&Event::Started {
+        input: input.display().to_string(),
+    })?;
+    let givens = board.givens();
+    let mut variants = match &args.rules {
+        Some(name) => {
+            let rules = RuleSet::preset(name)?;
+            let rules = match &args.cages {
+                Some(path) if rules.needs_cages() => rules.with_cages(read_cages(path)?),
+                None if rules.needs_cages() => {
+                    bail!("--rules {name} needs the cages from --cages")
+                }
+                _ => rules,
+            };
+            rules.variants().to_vec()
+        }
+        None => vec![],
+    };
+    let extra = args
+        .variant
+        .iter()
+        .map(|variant| {
+            Ok(match variant {
+                Variation::Windoku => Variant::Windoku,
+                Variation::Diagonal => Variant::Diagonal,
+                Variation::AntiKnight => Variant::AntiKnight,
+                Variation::NonConsecutive => Variant::NonConsecutive,
+                Variation::Killer => match &args.cages {
+                    Some(path) => Variant::Killer(read_cages(path)?),
+                    None => bail!("--variant killer needs the cages from --cages"),
+                },
+                Variation::Arrow => match &args.arrows {
+                    Some(path) => Variant::Arrows(read_arrows(path)?),
+                    None => bail!("--variant arrow needs the arrows from --arrows"),
+                },
+                Variation::Sandwich => match &args.sandwiches {
+                    Some(path) => Variant::Sandwiches(read_sandwiches(path)?),
+                    None => bail!("--variant sandwich needs the clues from --sandwiches"),
+                },
+                Variation::GreaterThan => match &args.inequalities {
+                    Some(path) => Variant::GreaterThan(read_inequalities(path)?),
+                    None => bail!("--variant greater-than needs the signs from --inequalities"),
+                },
+                Variation::EvenOdd => match &args.parities {
+                    Some(path) => Variant::EvenOdd(read_parities(path)?),
+                    None => bail!("--variant even-odd needs the marks from --parities"),
+                },
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    variants.extend(extra);
+    // solutions are only counted under the classic rules
+    if !variants.is_empty() {
+        if args.require_unique {
+            bail!("--require-unique only checks classic puzzles");
+        }
+    } else {
+        match board.has_unique_solution() {
+            Ok(false) if args.require_unique => bail!("the puzzle has more than one solution"),
+            Ok(false) => {
+                eprintln!("warning: the puzzle has more than one solution, only one is written")
+            }
+            Err(why) if args.require_unique => {
+                return Err(why).context("the puzzle has no solution");
+            }
+            // solving reports it
+            Ok(true) | Err(_) => {}
+        }
+    }
+    let mut builder = Solver::builder().backend(match args.backend {
+        Backend::Backtracking => SolverBackend::Backtracking,
+        Backend::Dlx => SolverBackend::Dlx,
+        Backend::Anneal => SolverBackend::Anneal,
+    });
+    builder = builder.value_order(match args.value_order {
+        Order::Ascending => ValueOrder::Ascending,
+        Order::LeastConstraining => ValueOrder::LeastConstraining,
+        Order::PeerFrequency => ValueOrder::PeerFrequency,
+        Order::Random => ValueOrder::Random { seed: args.seed },
+    });
+    if args.shuffle_ties || args.backend == Backend::Anneal {
+        builder = builder.tie_break_seed(args.seed);
+    }
+    if let Some(max_depth) = args.max_depth {
+        builder = builder.max_depth(max_depth);
+    }
+    if let Some(seconds) = args.timeout {
+        let timeout = Duration::try_from_secs_f64(seconds).context("invalid --timeout")?;
+        builder = builder.timeout(timeout);
+    }
+    for variant in variants {
+        builder = builder.variant(variant);
+    }
+    builder = builder.profile(args.profile);
+    for name in techniques::disabled(&args.enable, &args.disable)? {
+        builder = builder.disable_strategy(name);
+    }
+    // grading solves the puzzle again, so it is only done when it will be printed
+    let grade = (verbosity == Verbosity::Verbose).then(|| report::grade(&board));
+    let start = Instant::now();
+    let result = builder.build().solve(board);
+    let mut summary = Summary::new("solved", &input, start.elapsed());
+    summary.grade = grade;
+    let (solution, stats) = match result {
+        Err(SolveError::Stopped { why, best }) => {
+            events.emit(&Event::Stopped {
+                input: summary.input.clone(),
+                why,
+                best: best.to_line_string(),
+            })?;
+            if args.partial == Partial::Fail {
+                return Err(SolveError::Stopped { why, best }.into());
+            }
+            eprintln!("warning: {why}, writing how far it got");
+            write_file(&best, args.partial, args.mark_givens.then_some(givens))?;
+            summary.action = "stopped early on";
+            summary.output = Some("out.csv".to_string());
+            summary.grid = Some(best);
+            summary.print(verbosity);
+            return Ok(());
+        }
+        result => result?,
+    };
+    write_file(&solution, args.partial, args.mark_givens.then_some(givens))?;
+    events.emit(&Event::Solved {
+        input: summary.input.clone(),
+        stats: stats.clone(),
+        solution: solution.to_line_string(),
+    })?;
+    if let Some(path) = &args.stats_csv {
+        append_stats(path, &summary.input, &stats)?;
+    }
+    summary.output = Some("out.csv".to_string());
+    summary.grid = Some(solution.clone());
+    summary.print(verbosity);
+    if args.answer_code {
+        let solution = SolvedBoard::try_from(solution)
+            .context("only a complete solution has an answer code")?;
+        let code = io::write_answer_code(&solution).context("the solution breaks a rule")?;
+        println!("answer code: {code}");
+    }
+    if args.profile {
+        print!("{}", report::profile(&stats.profile));
+    }
+    if args.report_resources {
+        print!("{}", report::stats(&stats));
+        println!("{}", resources::Usage::now());
+    }
+    Ok(())
+}
+/// appends a row of `stats` to the csv at `path`, starting it with a header if it is new
+fn append_stats(path: &Path, input: &str, stats: &SolveStats) -> Result<()> {
+    let techniques: Vec<_> = std::iter::once("singles").chain(strategy_names()).collect();
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("opening {} for stats", path.display()))?;
+    let is_new = file.metadata()?.len() == 0;
+    let mut writer = csv::Writer::from_writer(file);
+    if is_new {
+        let header = ["input", "guesses", "max_depth", "elapsed_ms"];
+        writer.write_record(header.into_iter().chain(techniques.iter().copied()))?;
+    }
+    let row = [
+        input.to_string(),
+        stats.guesses.to_string(),
+        stats.max_depth.to_string(),
+        stats.elapsed.as_millis().to_string(),
+    ];
+    let eliminations = techniques
+        .iter()
+        .map(|name| stats.eliminations.get(name).unwrap_or(&0).to_string());
+    writer.write_record(row.into_iter().chain(eliminations))?;
+    writer.flush()?;
+    Ok(())
+}
+fn capabilities(json: bool) {
+    let format = |name, read, write| Format { name, read, write };
+    let mut capabilities = Capabilities::current()
+        .with_format(format("csv", true, true))
+        .with_format(format("image", true, false));
+    if cfg!(feature = "count-allocs") {
+        capabilities = capabilities.with_feature("count-allocs");
+    }
+    if cfg!(feature = "difficulty-model") {
+        capabilities = capabilities.with_feature("difficulty-model");
+    }
+    if json {
+        println!("{}", capabilities.to_json());
+        return;
+    }
+    let formats: Vec<_> = capabilities
+        .formats
+        .iter()
+        .map(|supported| match (supported.read, supported.write) {
+            (true, true) => supported.name.to_string(),
+            (true, false) => format!("{} (read only)", supported.name),
+            _ => format!("{} (write only)", supported.name),
+        })
+        .collect();
+    println!("version: {}", capabilities.version);
+    println!("features: {}", capabilities.features.join(", "));
+    println!("formats: {}", formats.join(", "));
+    println!("engines: {}", capabilities.engines.join(", "));
+    println!("strategies: {}", capabilities.strategies.join(", "));
+    println!("variants: {}", capabilities.variants.join(", "));
+}
+fn read_image(image: &Path, importer: &impl ImageImporter, min_confidence: f32) -> Result<Board> {
+    let (board, dropped) = Board::build_weighted(importer.import(image)?, min_confidence)?;
+    for (row, column) in dropped {
+        eprintln!("warning: dropped the digit read at row {row}, column {column} since it clashed");
+    }
+    Ok(board)
+}
+fn disguise(file_name: &PathBuf) -> Result<()> {
+    let board = Board::build_wide(read_file(file_name)?)?;
+    // a disguised puzzle is meant to have blanks
+    write_file(
+        &board.disguise(&mut rand::thread_rng()),
+        Partial::Blank,
+        None,
+    )
+}
+fn analyze(file_name: &PathBuf) -> Result<()> {
+    let board = Board::build_wide(read_file(file_name)?)?;
+    let givens = board
+        .givens()
+        .iter()
+        .flatten()
+        .filter(|&&given| given)
+        .count();
+    let symmetries: Vec<_> = board
+        .given_symmetries()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let density = board.given_density();
+    println!("givens: {givens}");
+    if symmetries.is_empty() {
+        println!("symmetry: none");
+    } else {
+        println!("symmetry: {}", symmetries.join(", "));
+    }
+    println!("givens per house, with the band totals on the right and stacks below:");
+    for (band, houses) in density.houses.iter().enumerate() {
+        println!(
+            "  {:>2} {:>2} {:>2} | {:>2}",
+            houses[0], houses[1], houses[2], density.bands[band]
+        );
+    }
+    let [first, second, third] = density.stacks;
+    println!("  {first:>2} {second:>2} {third:>2}");
+    Ok(())
+}
+fn rate(file_name: &PathBuf) -> Result<()> {
+    let board = Board::build_wide(read_file(file_name)?)?;
+    let rating = board.rate().context("the puzzle has no solution")?;
+    println!("tier: {}", rating.tier);
+    println!("score: {}", rating.score);
+    println!("hardest technique: {}", rating.hardest);
+    println!("steps: {}", rating.steps);
+    #[cfg(feature = "difficulty-model")]
+    println!("model score: {:.1}", board.difficulty()?.model_score());
+    Ok(())
+}
+fn certify(file_name: &PathBuf, level: Level) -> Result<()> {
+    let level = match level {
+        Level::Easy => Tier::Easy,
+        Level::Medium => Tier::Medium,
+        Level::Hard => Tier::Hard,
+        Level::Expert => Tier::Expert,
+    };
+    let board = Board::build_wide(read_file(file_name)?)?;
+    if !board
+        .has_unique_solution()
+        .context("the puzzle has no solution")?
+    {
+        bail!("the puzzle has more than one solution, which no deductions can settle");
+    }
+    let Some(certificate) = board
+        .is_logic_solvable(level)
+        .context("the puzzle has no solution")?
+    else {
+        bail!("the puzzle can't be solved without guessing or a technique harder than {level}");
+    };
+    println!("no guessing needed with {level} techniques");
+    for (i, step) in certificate.iter().enumerate() {
+        let placed: Vec<_> = step
+            .placements
+            .iter()
+            .map(|&(row, column, val)| format!("{val} at r{}c{}", row + 1, column + 1))
+            .collect();
+        println!(
+            "{}. {}: {} placed, {} ruled out{}",
+            i + 1,
+            step.technique,
+            step.placements.len(),
+            step.eliminations.len(),
+            if placed.is_empty() {
+                String::new()
+            } else {
+                format!(" ({})", placed.join(", "))
+            }
+        );
+    }
+    Ok(())
+}
+fn watch(file_name: &PathBuf) -> Result<()> {
+    let mut setter: Option<Setter> = None;
+    let mut last_saved = None;
+    loop {
+        let saved = fs::metadata(file_name)?.modified()?;
+        if last_saved == Some(saved) {
+            thread::sleep(Duration::from_millis(250));
+            continue;
+        }
+        last_saved = Some(saved);
+        // a half saved or mistyped file is reported and the watch goes on
+        let puzzle = match read_file(file_name).and_then(|lines| Ok(Board::build_wide(lines)?)) {
+            Ok(puzzle) => puzzle,
+            Err(why) => {
+                println!("error: {why:#}");
+                continue;
+            }
+        };
+        let how = match &mut setter {
+            Some(setter) => setter.update(puzzle),
+            None => {
+                setter = Some(Setter::new(puzzle));
+                Reanalysis::Full
+            }
+        };
+        let setter = setter.as_ref().expect("the setter was just made");
+        let analysis = setter.analysis();
+        let solutions = match analysis.solutions {
+            0 => "no solution",
+            1 => "one solution",
+            _ => "more than one solution",
+        };
+        let grade = analysis
+            .rating
+            .map_or("ungraded".to_string(), |rating| rating.to_string());
+        let givens = setter.puzzle().givens();
+        let givens = givens.iter().flatten().filter(|&&given| given).count();
+        println!(
+            "{givens} givens, {solutions}, {grade} ({} analysis)",
+            how.name()
+        );
+    }
+}
+fn hint(file_name: &PathBuf) -> Result<()> {
+    let board = Board::build_wide(read_file(file_name)?)?;
+    let Some(step) = board.hint().context("the puzzle has no solution")? else {
+        println!("no hint, the puzzle is either solved or needs a guess");
+        return Ok(());
+    };
+    // rows and columns are counted from 1 for people
+    let cell = |(row, column): (usize, usize)| format!("r{}c{}", row + 1, column + 1);
+    let cells: Vec<_> = step
+        .cells
+        .iter()
+        .map(|pos| cell((pos.row(), pos.column())))
+        .collect();
+    println!("technique: {}", step.technique);
+    println!("from: {}", cells.join(" "));
+    for (row, column, val) in step.placements {
+        println!("place {val} at {}", cell((row, column)));
+    }
+    for (row, column, val) in step.eliminations {
+        println!("remove {val} from {}", cell((row, column)));
+    }
+    Ok(())
+}
+fn explain(file_name: &PathBuf) -> Result<()> {
+    let board = Board::build_wide(read_file(file_name)?)?;
+    for (i, sentence) in board.explain().iter().enumerate() {
+        println!("{}. {sentence}", i + 1);
+    }
+    Ok(())
+}
+fn trace(file_name: &PathBuf) -> Result<()> {
+    let board = Board::build_wide(read_file(file_name)?)?;
+    let steps: Vec<_> = board.clone().solve_steps().collect();
+    let text = io::write_trace(&board, &steps);
+    fs::write("trace.txt", &text)?;
+    println!(
+        "wrote {} steps to trace.txt ({} bytes)",
+        steps.len(),
+        text.len()
+    );
+    Ok(())
+}
+fn replay(file_name: &PathBuf) -> Result<()> {
+    let (_, steps) = io::replay_trace(&fs::read_to_string(file_name)?)
+        .with_context(|| format!("reading the trace in {}", file_name.display()))?;
+    for (i, (technique, _)) in steps.iter().enumerate() {
+        println!("{}. {technique}", i + 1);
+    }
+    if let Some((_, board)) = steps.last() {
+        println!("{}", board.to_line_string());
+    }
+    Ok(())
+}
+#[cfg(feature = "difficulty-model")]
+fn fit_difficulty(file_name: &PathBuf) -> Result<()> {
+    let text = fs::read_to_string(file_name)?;
+    let mut rated = vec![];
+    for (number, line) in io::lines(&text).enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let context = || format!("line {} of {}", number + 1, file_name.display());
+        let (puzzle, rating) = line
+            .split_once(char::is_whitespace)
+            .with_context(|| format!("{} has no rating", context()))?;
+        let rating: f64 = rating.trim().parse().with_context(context)?;
+        let board = io::read_gordon(puzzle).with_context(context)?;
+        rated.push((board.difficulty().with_context(context)?, rating));
+    }
+    let model = sudoku_core::Model::fit(&rated);
+    fs::write("difficulty.model", model.to_string())?;
+    println!("fitted to {} puzzles, wrote difficulty.model", rated.len());
+    Ok(())
+}
+fn validate_schema(file_name: &PathBuf) -> Result<()> {
+    let problems = schema::validate(&fs::read_to_string(file_name)?);
+    if problems.is_empty() {
+        println!(
+            "{} is a valid version {} puzzle",
+            file_name.display(),
+            schema::CURRENT
+        );
+        return Ok(());
+    }
+    for problem in &problems {
+        println!("{}: {problem}", file_name.display());
+    }
+    bail!("{} doesn't match the schema", file_name.display())
+}
+/// prints each board of the solution on one line, after where it is on the sheet
+fn solve_composite(file_name: &PathBuf) -> Result<()> {
+    let puzzle = schema::read(&fs::read_to_string(file_name)?)
+        .with_context(|| format!("reading {}", file_name.display()))?;
+    let solution = puzzle.composite()?.solve()?;
+    for ((row, column), board) in solution.boards() {
+        println!(
+            "row {}, column {}: {}",
+            row + 1,
+            column + 1,
+            board.to_line_string()
+        );
+    }
+    Ok(())
+}
+fn check_submission(
+    file_name: &PathBuf,
+    against: Option<&Path>,
+    events: &mut dyn EventSink,
+) -> Result<()> {
+    let against = against
+        .map(|path| {
+            fs::File::open(path)
+                .map(BufReader::new)
+                .with_context(|| format!("opening {}", path.display()))
+        })
+        .transpose()?;
+    let verdict = submission::check(&fs::read_to_string(file_name)?, against)?;
+    let fields = verdict.fields();
+    for (name, value) in &fields {
+        println!("{name}: {value}");
+    }
+    events.emit(&Event::Report {
+        name: "submission",
+        fields,
+    })?;
+    Ok(())
+}
+fn dedup(file_name: &PathBuf, verbosity: Verbosity, events: &mut dyn EventSink) -> Result<()> {
+    events.emit(&Event::Started {
+        input: file_name.display().to_string(),
+    })?;
+    let input = BufReader::new(fs::File::open(file_name)?);
+    let output = BufWriter::new(fs::File::create("deduped.txt")?);
+    let start = Instant::now();
+    let counts = dedup::dedup(input, output)?;
+    events.emit(&Event::Report {
+        name: "dedup",
+        fields: vec![
+            ("read", counts.read.to_string()),
+            ("kept", counts.kept().to_string()),
+            ("duplicates", counts.duplicates.to_string()),
+            ("isomorphs", counts.isomorphs.to_string()),
+        ],
+    })?;
+    let mut summary = Summary::new("deduped", file_name, start.elapsed());
+    summary.output = Some("deduped.txt".to_string());
+    summary.notes.push(format!(
+        "kept {} of {} puzzles, dropping {} duplicates and {} isomorphs",
+        counts.kept(),
+        counts.read,
+        counts.duplicates,
+        counts.isomorphs
+    ));
+    summary.print(verbosity);
+    Ok(())
+}
+/// prints each puzzle's grade, reusing and adding to the grades saved in `cache`
+fn grade(file_name: &PathBuf, cache: Option<&PathBuf>) -> Result<()> {
+    let mut store = match cache {
+        Some(cache) if cache.exists() => {
+            SolutionStore::load(BufReader::new(fs::File::open(cache)?))
+                .with_context(|| format!("reading {}", cache.display()))?
+        }
+        _ => SolutionStore::new(),
+    };
+    let text = fs::read_to_string(file_name)?;
+    let (mut graded, mut reused) = (0, 0);
+    for (number, line) in io::lines(&text).enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let context = || format!("line {} of {}", number + 1, file_name.display());
+        let form = io::read_gordon(line)
+            .with_context(context)?
+            .canonical_form();
+        if store.get_grade(&form).is_some() {
+            reused += 1;
+        }
+        let rating = store.grade(&form).with_context(context)?;
+        println!("{}: {rating}", number + 1);
+        graded += 1;
+    }
+    if let Some(cache) = cache {
+        store.save(BufWriter::new(fs::File::create(cache)?))?;
+    }
+    println!("graded {graded} puzzles, reusing {reused} grades");
+    Ok(())
+}
+fn reveal(code: &str) -> Result<()> {
+    let solution = io::read_answer_code(code).with_context(|| format!("reading {code:?}"))?;
+    for row in <[[usize; 9]; 9]>::from(solution) {
+        let row: Vec<_> = row.iter().map(usize::to_string).collect();
+        println!("{}", row.join(","));
+    }
+    Ok(())
+}
+/// calls `$f::<S>($args)` with the shape of a sudoku `$side` cells across, or fails if there is
+/// no sudoku that size
+macro_rules! sudoku_sized {
+    ($side:expr, $f:ident($($arg:expr),*)) => {
+        match $side {
+            4 => $f::<Size<2, 2>>($($arg),*),
+            // houses two rows tall and three columns wide, the usual shape in print
+            6 => $f::<Size<2, 3>>($($arg),*),
+            9 => $f::<Size<3, 3>>($($arg),*),
+            16 => $f::<Size<4, 4>>($($arg),*),
+            side => bail!("there are no sudoku rules for {side}x{side} grids"),
+        }
+    };
+}
+/// calls `$f::<S>($args)` with the shape of a Latin square `$side` cells across, whose houses
+/// are its rows
+///
+/// only the sizes futoshiki and the like are played on are here, since each one is another copy
+/// of the solver in the binary
+macro_rules! latin_sized {
+    ($side:expr, $f:ident($($arg:expr),*)) => {
+        match $side {
+            4 => $f::<Size<1, 4>>($($arg),*),
+            5 => $f::<Size<1, 5>>($($arg),*),
+            6 => $f::<Size<1, 6>>($($arg),*),
+            7 => $f::<Size<1, 7>>($($arg),*),
+            8 => $f::<Size<1, 8>>($($arg),*),
+            9 => $f::<Size<1, 9>>($($arg),*),
+            side => bail!("a Latin square has to be 4-9 cells across, not {side}"),
+        }
+    };
+}
+fn generate(
+    dig: Dig,
+    givens: usize,
+    seed: Option<u64>,
+    size: usize,
+    solution: Option<&PathBuf>,
+) -> Result<()> {
+    let seed = seed.unwrap_or_else(rand::random);
+    if size != 9 {
+        if dig != Dig::Random {
+            bail!("only 9x9 puzzles can be dug in another order");
+        }
+        return sudoku_sized!(size, generate_sized(givens, seed));
+    }
+    let dig = match dig {
+        Dig::Random => DigStrategy::Random,
+        Dig::SymmetricPairs => DigStrategy::SymmetricPairs,
+        Dig::BorderFirst => DigStrategy::BorderFirst,
+        Dig::CenterOut => DigStrategy::CenterOut,
+    };
+    let options = GeneratorOptions {
+        dig,
+        target_givens: givens,
+    };
+    let mut rng = StdRng::seed_from_u64(seed);
+    let generated = match solution {
+        Some(file_name) => {
+            let board = Board::build_wide(read_file(file_name)?)?;
+            board
+                .has_unique_solution()
+                .context("the solution breaks a rule")?;
+            let solution =
+                SolvedBoard::try_from(board).context("the solution has to be a complete grid")?;
+            Board::from_solution(solution, &options, &mut rng)
+        }
+        None => Board::generate(&options, &mut rng),
+    }?;
+    write_file(&generated.puzzle, Partial::Blank, None)?;
+    println!("dig: {}", generated.dig.name());
+    println!("seed: {seed}");
+    println!("givens: {}", generated.givens);
+    println!("grade: {}", report::grade(&generated.puzzle));
+    Ok(())
+}
+fn generate_sized<S: Shape>(givens: usize, seed: u64) -> Result<()> {
+    let options = GeneratorOptions {
+        target_givens: givens,
+        ..Default::default()
+    };
+    let generated = Board::<S>::generate_sized(&options, &mut StdRng::seed_from_u64(seed))?;
+    write_grid(&generated.puzzle)?;
+    println!("seed: {seed}");
+    println!("givens: {}", generated.givens);
+    Ok(())
+}
+fn latin(file_name: Option<&PathBuf>, size: Option<usize>) -> Result<()> {
+    match (file_name, size) {
+        (Some(file_name), _) => {
+            let lines = read_file(file_name)?;
+            latin_sized!(lines.len(), solve_sized(lines))
+        }
+        (None, Some(size)) => latin_sized!(size, random_square()),
+        (None, None) => bail!("give a square to solve or a --size to make one"),
+    }
+}
+fn random_square<S: Shape>() -> Result<()> {
+    let square = Board::<S>::default()
+        .sample_solution(&mut rand::thread_rng())
+        .context("every size has a latin square")?;
+    write_grid(&square)
+}
+fn solve_grid(file_name: &PathBuf, symbols: Symbols) -> Result<()> {
+    let text = fs::read_to_string(file_name)?;
+    // a grid of symbols has no commas, and a csv of a 16x16 grid has 15 on each line
+    if !text.contains(',') {
+        let symbols = match symbols {
+            Symbols::Hex => io::SymbolMap::hex(),
+            Symbols::DigitsThenLetters => io::SymbolMap::digits_then_letters(),
+            Symbols::Letters => io::SymbolMap::letters(),
+        };
+        let cells = text.chars().filter(|c| !c.is_whitespace()).count();
+        let side = (1..=16).find(|side| side * side >= cells).unwrap_or(cells);
+        return sudoku_sized!(side, solve_symbols(&symbols, &text));
+    }
+    let lines = read_csv(text.as_bytes())?;
+    sudoku_sized!(lines.len(), solve_sized(lines))
+}
+fn solve_symbols<S: Shape>(symbols: &io::SymbolMap, text: &str) -> Result<()> {
+    let solution = symbols
+        .read::<S>(text)?
+        .solve()
+        .context("the puzzle has no solution")?;
+    write_grid(&solution)
+}
+fn solve_sized<S: Shape>(lines: Vec<Vec<Option<i64>>>) -> Result<()> {
+    let solution = Board::<S>::from_givens(lines)?
+        .solve()
+        .context("the puzzle has no solution")?;
+    write_grid(&solution)
+}
+/// a cell such as `r1c1`, counting rows and columns from 1
+fn read_cell_name(word: &str) -> Result<CellPos> {
+    let cell = || {
+        let (row, column) = word.strip_prefix('r')?.split_once('c')?;
+        let (row, column): (usize, usize) = (row.parse().ok()?, column.parse().ok()?);
+        CellPos::at(row.checked_sub(1)?, column.checked_sub(1)?)
+    };
+    cell().with_context(|| format!("{word:?} isn't a cell like r1c1"))
+}
+/// the cages of a killer puzzle, one per line as the sum and then cells such as `r1c1`
+fn read_cages(path: &Path) -> Result<Vec<Cage>> {
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("reading cages from {}", path.display()))?;
+    io::lines(&text)
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(i, line)| {
+            let mut words = line.split_whitespace();
+            let sum = words
+                .next()
+                .and_then(|sum| sum.parse().ok())
+                .with_context(|| format!("cage {} doesn't start with its sum", i + 1))?;
+            let cells = words.map(read_cell_name).collect::<Result<_>>()?;
+            Cage::new(cells, sum).with_context(|| format!("cage {}", i + 1))
+        })
+        .collect()
+}
+/// the arrows of an arrow puzzle, one per line as the circle and then the cells along the arrow
+fn read_arrows(path: &Path) -> Result<Vec<Arrow>> {
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("reading arrows from {}", path.display()))?;
+    io::lines(&text)
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(i, line)| {
+            let mut cells = line
+                .split_whitespace()
+                .map(read_cell_name)
+                .collect::<Result<Vec<_>>>()?;
+            if cells.is_empty() {
+                bail!("arrow {} has no circle", i + 1)
+            }
+            let circle = cells.remove(0);
+            Arrow::new(circle, cells).with_context(|| format!("arrow {}", i + 1))
+        })
+        .collect()
+}
+/// the clues of a sandwich puzzle, one per line as a row or column such as `r1` or `c4` and then
+/// the sum
+fn read_sandwiches(path: &Path) -> Result<Vec<Sandwich>> {
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("reading sandwiches from {}", path.display()))?;
+    io::lines(&text)
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(i, line)| {
+            let context = || format!("sandwich {} isn't a row or column and a sum", i + 1);
+            let (unit, sum) = line
+                .trim()
+                .split_once(char::is_whitespace)
+                .with_context(context)?;
+            let (kind, number) = unit.split_at_checked(1).with_context(context)?;
+            let number: usize = number.parse().with_context(context)?;
+            let index = number.checked_sub(1).with_context(context)?;
+            let unit = match kind {
+                "r" => Unit::Row(index),
+                "c" => Unit::Column(index),
+                _ => bail!(context()),
+            };
+            let sum = sum.trim().parse().with_context(context)?;
+            Sandwich::new(unit, sum).with_context(|| format!("sandwich {}", i + 1))
+        })
+        .collect()
+}
+/// the signs of a greater-than puzzle, one per line such as `r1c1 > r1c2` or `r2c5 < r3c5`
+fn read_inequalities(path: &Path) -> Result<Vec<Inequality>> {
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("reading signs from {}", path.display()))?;
+    io::lines(&text)
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(i, line)| {
+            let words: Vec<_> = line.split_whitespace().collect();
+            let inequality = match words[..] {
+                [left, ">", right] => {
+                    Inequality::new(read_cell_name(left)?, read_cell_name(right)?)
+                }
+                [left, "<", right] => {
+                    Inequality::new(read_cell_name(right)?, read_cell_name(left)?)
+                }
+                _ => bail!("sign {} isn't two cells with > or < between them", i + 1),
+            };
+            inequality.with_context(|| format!("sign {}", i + 1))
+        })
+        .collect()
+}
+/// the marks of an even-odd puzzle, as a mask of nine rows such as `e.o......` or as one mark per
+/// line such as `r1c1 even`, which can't be mixed
+fn read_parities(path: &Path) -> Result<Vec<(CellPos, Parity)>> {
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("reading marks from {}", path.display()))?;
+    let lines: Vec<_> = io::lines(&text)
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    let parity = |word: &str| match word.to_ascii_lowercase().as_str() {
+        "e" | "even" => Ok(Some(Parity::Even)),
+        "o" | "odd" => Ok(Some(Parity::Odd)),
+        "." => Ok(None),
+        _ => bail!("{word:?} isn't even or odd"),
+    };
+    if lines.iter().any(|line| line.contains(char::is_whitespace)) {
+        return lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let words: Vec<_> = line.split_whitespace().collect();
+                match words[..] {
+                    [cell, kind] => match parity(kind)? {
+                        Some(parity) => Ok((read_cell_name(cell)?, parity)),
+                        None => bail!("mark {} doesn't say even or odd", i + 1),
+                    },
+                    _ => bail!("mark {} isn't a cell and even or odd", i + 1),
+                }
+            })
+            .collect();
+    }
+    if lines.len() != 9 || lines.iter().any(|line| line.chars().count() != 9) {
+        bail!("a mask of marks needs nine rows of nine characters");
+    }
+    let mut marks = vec![];
+    for (pos, mark) in CellPos::all().zip(lines.iter().flat_map(|line| line.chars())) {
+        if let Some(parity) = parity(&mark.to_string())
+            .with_context(|| format!("row {}, column {}", pos.row() + 1, pos.column() + 1))?
+        {
+            marks.push((pos, parity));
+        }
+    }
+    Ok(marks)
+}
+/// writes a board of any size to out.csv
+fn write_grid<S: Shape>(board: &Board<S>) -> Result<()> {
+    let mut writer = csv::Writer::from_path("out.csv")?;
+    for row in board.values() {
+        writer.write_record(
+            row.iter()
+                .map(|val| val.map_or(String::new(), |val| val.to_string())),
+        )?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+/// prints the puzzle, or with `diff` its solution, and draws the same to board.svg
+///
+/// which values were given comes from the puzzle before it is solved, since the solved board
+/// can't tell them apart
+fn render(file_name: &PathBuf, diff: bool) -> Result<()> {
+    let board = Board::build_wide(read_file(file_name)?)?;
+    let givens = board.givens();
+    let board = match diff {
+        true => board
+            .solve()
+            .context("the puzzle has no solution to show")?,
+        false => board,
+    };
+    print!("{}", report::diff(&board, &givens));
+    fs::write("board.svg", io::board_svg(&board, &givens))?;
+    Ok(())
+}
+fn heat_map(file_name: &PathBuf, samples: usize) -> Result<()> {
+    let board = Board::build_wide(read_file(file_name)?)?;
+    let map = board.heat_map(samples, &mut rand::thread_rng())?;
+    fs::write("heatmap.svg", io::heat_map_svg(&map))?;
+    let (row, column) = map.most_uncertain();
+    println!(
+        "the most open cell is row {}, column {} ({:.2} bits)",
+        row + 1,
+        column + 1,
+        map.entropy(row, column)
+    );
+    Ok(())
+}
+fn write_file(board: &Board, partial: Partial, givens: Option<Vec<Vec<bool>>>) -> Result<()> {
+    let rows: Vec<Vec<String>> = match SolvedBoard::try_from(board.clone()) {
+        Ok(solution) => <[[usize; 9]; 9]>::from(solution)
+            .iter()
+            .map(|row| row.iter().map(usize::to_string).collect())
+            .collect(),
+        Err(why) if partial == Partial::Fail => {
+            return Err(why).context("the solution is incomplete, use --partial to write it anyway")
+        }
+        Err(_) => board
+            .candidates()
+            .iter()
+            .zip(board.givens())
+            .map(|(row, solved)| {
+                row.iter()
+                    .zip(solved)
+                    .map(|(vals, solved)| match partial {
+                        _ if solved => vals[0].to_string(),
+                        Partial::Candidates => vals.iter().map(usize::to_string).collect(),
+                        _ => String::new(),
+                    })
+                    .collect()
+            })
+            .collect(),
+    };
+    let file = fs::OpenOptions::new()
+        .write(true)
+        .truncate(true)
+        .create(true)
+        .open("out.csv")?;
+
+    let mut writer = csv::Writer::from_writer(file);
+    for row in rows {
+        writer.write_record(row)?;
+    }
+    for line in givens.into_iter().flatten() {
+        writer.serialize(line.into_iter().map(u8::from).collect::<Vec<_>>())?;
+    }
+    writer.flush()?;
+
+    Ok(())
+}
+fn read_file(file_name: &PathBuf) -> Result<Vec<Vec<Option<i64>>>> {
+    read_csv(fs::OpenOptions::new().read(true).open(file_name)?)
+}
+/// the rows of a puzzle csv, which may end its lines with `\n`, `\r\n`, or `\r`
+///
+/// cells are read as `i64`s so a value out of range gets an error naming its cell instead of
+/// failing to parse
+fn read_csv(file: impl Read) -> Result<Vec<Vec<Option<i64>>>> {
+    Ok(csv::ReaderBuilder::new()
+        .has_headers(false)
+        .trim(csv::Trim::All)
+        .from_reader(file)
+        .deserialize()
+        .collect::<Result<Vec<_>, _>>()?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn read_csv_ignores_line_endings() {
+        let rows = read_csv("1,,3\r\n,5,\r7,8,9".as_bytes()).unwrap();
+
+        assert_eq!(
+            rows,
+            vec![
+                vec![Some(1), None, Some(3)],
+                vec![None, Some(5), None],
+                vec![Some(7), Some(8), Some(9)]
+            ]
+        );
+    }
+    #[test]
+    fn read_csv_reads_values_too_big_for_a_cell() {
+        let rows = read_csv("4294967296,-1,300".as_bytes()).unwrap();
+
+        assert_eq!(rows, vec![vec![Some(4294967296), Some(-1), Some(300)]]);
+    }
+}
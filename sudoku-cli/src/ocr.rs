@@ -0,0 +1,108 @@
+//! reading puzzles out of images with an outside OCR tool
+//!
+//! the OCR itself isn't done here. an `ImageImporter` turns an image into digits with
+//! confidences, and `Board::build_weighted` deals with whatever it got wrong
+
+use anyhow::{bail, Context, Result};
+use std::{path::Path, process::Command};
+use sudoku_core::{io, WeightedCell};
+
+/// turns a picture of a puzzle into rows of digits with how sure it is of each
+pub trait ImageImporter {
+    fn import(&self, image: &Path) -> Result<Vec<Vec<WeightedCell>>>;
+}
+
+/// runs a shell command that prints the digits it read
+///
+/// `{}` in the command is replaced with the image path, or the path is added to the end if
+/// there is no `{}`. the command should print 9 lines of 9 comma separated cells, each blank (or
+/// `0` or `.`) for an empty cell, a digit, or `digit:confidence` with a confidence from 0 to 1
+pub struct OcrCommand(pub String);
+
+impl ImageImporter for OcrCommand {
+    fn import(&self, image: &Path) -> Result<Vec<Vec<WeightedCell>>> {
+        let image = image.display().to_string();
+        let command = if self.0.contains("{}") {
+            self.0.replace("{}", &image)
+        } else {
+            format!("{} {image}", self.0)
+        };
+        let output = Command::new("sh")
+            .arg("-c")
+            .arg(&command)
+            .output()
+            .with_context(|| format!("couldn't run the OCR command {command:?}"))?;
+        if !output.status.success() {
+            bail!(
+                "the OCR command failed ({}): {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )
+        }
+        parse_digits(&String::from_utf8_lossy(&output.stdout))
+    }
+}
+
+/// reads the digits matrix printed by an OCR command
+pub fn parse_digits(text: &str) -> Result<Vec<Vec<WeightedCell>>> {
+    io::lines(text)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            line.split(',')
+                .map(|cell| parse_cell(cell.trim()))
+                .collect()
+        })
+        .collect()
+}
+
+fn parse_cell(cell: &str) -> Result<WeightedCell> {
+    if matches!(cell, "" | "0" | ".") {
+        return Ok(None);
+    }
+    let (digit, confidence) = cell.split_once(':').unwrap_or((cell, "1"));
+    let digit = digit
+        .parse()
+        .with_context(|| format!("{cell:?} isn't a digit"))?;
+    let confidence = confidence
+        .parse()
+        .with_context(|| format!("{cell:?} doesn't have a valid confidence"))?;
+    Ok(Some((digit, confidence)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_digits_reads_confidences() {
+        let rows = parse_digits("5:0.9,,3\n.,0,7\n").unwrap();
+
+        assert_eq!(
+            rows,
+            vec![
+                vec![Some((5, 0.9)), None, Some((3, 1.0))],
+                vec![None, None, Some((7, 1.0))]
+            ]
+        );
+    }
+    #[test]
+    fn parse_digits_ignores_line_endings() {
+        let rows = parse_digits("1,2\r\n3,4\r5,6\n7,8").unwrap();
+
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0], vec![Some((1, 1.0)), Some((2, 1.0))]);
+    }
+    #[test]
+    fn parse_digits_rejects_junk() {
+        assert!(parse_digits("5:high").is_err());
+        assert!(parse_digits("x").is_err());
+    }
+    #[test]
+    fn ocr_command_passes_the_image_path() {
+        let rows = OcrCommand("echo 1:0.5,{}".into())
+            .import(Path::new("2"))
+            .unwrap();
+
+        assert_eq!(rows, vec![vec![Some((1, 0.5)), Some((2, 1.0))]]);
+    }
+}
@@ -0,0 +1,315 @@
+//! what the cli prints once a run is done, at the level `--quiet` and `--verbose` pick
+//!
+//! solving a puzzle and deduping a collection both end with a `Summary`, so they read the same
+
+use std::{collections::BTreeMap, fmt::Write, path::Path, time::Duration};
+use sudoku_core::{Board, SolveStats};
+
+/// how much to print about a run that worked, errors are always printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// nothing
+    Quiet,
+    /// what was read, how long it took, and what was written
+    Normal,
+    /// also the grade and the grid
+    Verbose,
+}
+
+impl Verbosity {
+    pub fn new(quiet: bool, verbose: bool) -> Self {
+        match (quiet, verbose) {
+            (true, _) => Verbosity::Quiet,
+            (_, true) => Verbosity::Verbose,
+            _ => Verbosity::Normal,
+        }
+    }
+}
+
+/// the end of a run
+#[derive(Debug, Clone)]
+pub struct Summary {
+    /// what was done to the input, such as `solved`
+    pub action: &'static str,
+    pub input: String,
+    pub time: Duration,
+    pub output: Option<String>,
+    /// how hard the puzzle was, see `grade`
+    pub grade: Option<String>,
+    /// the board that was written, previewed when verbose
+    pub grid: Option<Board>,
+    /// anything else worth saying, a line each
+    pub notes: Vec<String>,
+}
+
+impl Summary {
+    pub fn new(action: &'static str, input: &Path, time: Duration) -> Self {
+        Summary {
+            action,
+            input: input.display().to_string(),
+            time,
+            output: None,
+            grade: None,
+            grid: None,
+            notes: vec![],
+        }
+    }
+    /// the lines to print at `verbosity`, empty when quiet
+    pub fn render(&self, verbosity: Verbosity) -> String {
+        let mut text = String::new();
+        if verbosity == Verbosity::Quiet {
+            return text;
+        }
+        let seconds = self.time.as_secs_f64();
+        let _ = writeln!(text, "{} {} in {seconds:.3} s", self.action, self.input);
+        if verbosity == Verbosity::Verbose {
+            if let Some(grade) = &self.grade {
+                let _ = writeln!(text, "grade: {grade}");
+            }
+        }
+        for note in &self.notes {
+            let _ = writeln!(text, "{note}");
+        }
+        if let Some(output) = &self.output {
+            let _ = writeln!(text, "wrote {output}");
+        }
+        if let (Verbosity::Verbose, Some(grid)) = (verbosity, &self.grid) {
+            text.push_str(&preview(grid));
+        }
+        text
+    }
+    pub fn print(&self, verbosity: Verbosity) {
+        print!("{}", self.render(verbosity));
+    }
+}
+
+/// the puzzle's tier, score, and hardest technique, see `Board::rate`
+pub fn grade(board: &Board) -> String {
+    match board.rate() {
+        Ok(rating) => rating.to_string(),
+        Err(why) => format!("unsolvable ({why})"),
+    }
+}
+
+/// a table of where the time of a profiled solve went, slowest first, see `SolveStats::profile`
+pub fn profile(times: &BTreeMap<&'static str, Duration>) -> String {
+    let total: Duration = times.values().sum();
+    let mut times: Vec<_> = times.iter().collect();
+    times.sort_by(|a, b| b.1.cmp(a.1));
+    let width = times.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    let mut text = String::new();
+    for (name, time) in times {
+        let share = match total.is_zero() {
+            true => 0.0,
+            false => time.as_secs_f64() / total.as_secs_f64() * 100.0,
+        };
+        let _ = writeln!(
+            text,
+            "{name:width$}  {:>9.3} ms  {share:>5.1}%",
+            time.as_secs_f64() * 1000.0
+        );
+    }
+    text
+}
+
+/// what the solve did, one line each: guesses, depth, time, seed, and each technique's
+/// eliminations from most to fewest
+pub fn stats(stats: &SolveStats) -> String {
+    let mut lines = vec![
+        ("guesses", stats.guesses.to_string()),
+        ("max depth", stats.max_depth.to_string()),
+        (
+            "elapsed",
+            format!("{:.3} ms", stats.elapsed.as_secs_f64() * 1000.0),
+        ),
+    ];
+    if let Some(seed) = stats.seed {
+        lines.push(("seed", seed.to_string()));
+    }
+    let mut eliminations: Vec<_> = stats.eliminations.iter().collect();
+    eliminations.sort_by(|a, b| b.1.cmp(a.1));
+    for (&name, count) in eliminations {
+        lines.push((name, format!("{count} eliminated")));
+    }
+    let width = lines.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    let mut text = String::new();
+    for (name, val) in lines {
+        let _ = writeln!(text, "{name:width$}  {val}");
+    }
+    text
+}
+
+/// the grid in rows of three, with `.` for cells that aren't filled in
+fn preview(board: &Board) -> String {
+    let values: [[Option<usize>; 9]; 9] = board.clone().into();
+    let mut text = String::new();
+    for (i, row) in values.iter().enumerate() {
+        if i > 0 && i % 3 == 0 {
+            text.push_str("------+-------+------\n");
+        }
+        let cells: Vec<_> = row
+            .iter()
+            .map(|val| val.map_or(".".to_string(), |val| val.to_string()))
+            .collect();
+        let _ = writeln!(
+            text,
+            "{} | {} | {}",
+            cells[..3].join(" "),
+            cells[3..6].join(" "),
+            cells[6..].join(" ")
+        );
+    }
+    text
+}
+
+/// the grid with the values `givens` doesn't mark, the ones solving filled in, in brackets
+///
+/// each cell takes three characters, ` 5 ` for a given, `[5]` for a solved value, and ` . ` for
+/// a cell that isn't filled in, so the brackets don't push the columns out of line
+pub fn diff(board: &Board, givens: &[Vec<bool>]) -> String {
+    let values: [[Option<usize>; 9]; 9] = board.clone().into();
+    let mut text = String::new();
+    for (i, (row, givens)) in values.iter().zip(givens).enumerate() {
+        if i > 0 && i % 3 == 0 {
+            text.push_str("---------+---------+---------\n");
+        }
+        let cells: Vec<_> = row
+            .iter()
+            .zip(givens)
+            .map(|(val, given)| match (val, given) {
+                (None, _) => " . ".to_string(),
+                (Some(val), true) => format!(" {val} "),
+                (Some(val), false) => format!("[{val}]"),
+            })
+            .collect();
+        let _ = writeln!(
+            text,
+            "{}|{}|{}",
+            cells[..3].concat(),
+            cells[3..6].concat(),
+            cells[6..].concat()
+        );
+    }
+    text
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn board(rows: &[&str]) -> Board {
+        let mut lines: Vec<Vec<Option<u8>>> = rows
+            .iter()
+            .map(|row| {
+                let mut line: Vec<_> = row
+                    .chars()
+                    .map(|c| c.to_digit(10).map(|d| d as u8))
+                    .collect();
+                line.resize(9, None);
+                line
+            })
+            .collect();
+        lines.resize(9, vec![None; 9]);
+        Board::build(lines).unwrap()
+    }
+
+    #[test]
+    fn quiet_prints_nothing() {
+        let summary = Summary::new("solved", Path::new("a.csv"), Duration::from_millis(5));
+
+        assert_eq!(summary.render(Verbosity::Quiet), "");
+        assert_eq!(
+            summary.render(Verbosity::Normal),
+            "solved a.csv in 0.005 s\n"
+        );
+    }
+    #[test]
+    fn verbose_adds_the_grade_and_grid() {
+        let mut summary = Summary::new("solved", Path::new("a.csv"), Duration::ZERO);
+        summary.output = Some("out.csv".to_string());
+        summary.grade = Some("singles".to_string());
+        summary.grid = Some(board(&[
+            "12.......",
+            "",
+            "",
+            "",
+            "",
+            "",
+            "",
+            "",
+            "........9",
+        ]));
+        let text = summary.render(Verbosity::Verbose);
+        let lines: Vec<_> = text.lines().collect();
+
+        assert_eq!(
+            lines[..3],
+            ["solved a.csv in 0.000 s", "grade: singles", "wrote out.csv"]
+        );
+        assert_eq!(lines[3], "1 2 . | . . . | . . .");
+        assert_eq!(lines[6], "------+-------+------");
+        assert_eq!(lines[13], ". . . | . . . | . . 9");
+        let grid = summary.grid.as_ref().unwrap().to_line_string();
+        assert_eq!((&grid[..3], &grid[78..]), ("12.", "..9"));
+    }
+    #[test]
+    fn stats_line_up_with_the_busiest_technique_first() {
+        let stats = SolveStats {
+            guesses: 3,
+            max_depth: 2,
+            elapsed: Duration::from_micros(1500),
+            eliminations: [("singles", 40), ("x-wing", 2), ("naked pair", 7)].into(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            super::stats(&stats),
+            "guesses     3\n\
+             max depth   2\n\
+             elapsed     1.500 ms\n\
+             singles     40 eliminated\n\
+             naked pair  7 eliminated\n\
+             x-wing      2 eliminated\n"
+        );
+    }
+    #[test]
+    fn diff_brackets_solved_values() {
+        let puzzle = board(&["1.3", "", "", "", "", "", "", "", ""]);
+        let solution = board(&["123", "", "", "", "", "", "", "", "........9"]);
+        let text = diff(&solution, &puzzle.givens());
+        let lines: Vec<_> = text.lines().collect();
+
+        assert_eq!(lines[0], " 1 [2] 3 | .  .  . | .  .  . ");
+        assert_eq!(lines[3], "---------+---------+---------");
+        assert_eq!(lines[10], " .  .  . | .  .  . | .  . [9]");
+    }
+    #[test]
+    fn profile_lists_the_slowest_first() {
+        let times = BTreeMap::from([
+            ("rows", Duration::from_millis(1)),
+            ("x-wing", Duration::from_millis(3)),
+        ]);
+
+        assert_eq!(
+            profile(&times),
+            "x-wing      3.000 ms   75.0%\nrows        1.000 ms   25.0%\n"
+        );
+    }
+    #[test]
+    fn grade_shows_the_rating() {
+        let solution = [
+            "53467891.",
+            "672195348",
+            "198342567",
+            "859761423",
+            "426853791",
+            "713924856",
+            "961537284",
+            "287419635",
+            "345286179",
+        ];
+
+        assert_eq!(grade(&board(&solution)), "easy (score 1, needs singles)");
+        assert_eq!(grade(&board(&[])), "expert (score 50, needs guessing)");
+    }
+}
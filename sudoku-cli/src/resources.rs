@@ -0,0 +1,115 @@
+//! what a run cost: peak memory, cpu time, and (with the `count-allocs` feature) allocations
+//!
+//! memory and cpu time come from `/proc/self`, so they are only reported on linux
+
+use std::{fmt, fs};
+
+/// the resources used by the process up to the point it was taken
+#[derive(Debug, Clone, Default)]
+pub struct Usage {
+    /// the most resident memory the process has used, in kilobytes
+    pub peak_rss_kb: Option<u64>,
+    /// user plus system time, in seconds
+    pub cpu_seconds: Option<f64>,
+    /// how many allocations were made, if the counting allocator is installed
+    pub allocations: Option<usize>,
+}
+
+impl Usage {
+    pub fn now() -> Self {
+        Self {
+            peak_rss_kb: peak_rss_kb(),
+            cpu_seconds: cpu_seconds(),
+            allocations: counting::allocations(),
+        }
+    }
+}
+
+impl fmt::Display for Usage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn or_unknown<T: fmt::Display>(val: Option<T>, unit: &str) -> String {
+            val.map_or("unknown".to_string(), |val| format!("{val}{unit}"))
+        }
+        writeln!(f, "peak rss: {}", or_unknown(self.peak_rss_kb, " kB"))?;
+        writeln!(
+            f,
+            "cpu time: {}",
+            or_unknown(self.cpu_seconds.map(|s| format!("{s:.3}")), " s")
+        )?;
+        write!(
+            f,
+            "allocations: {}",
+            self.allocations.map_or(
+                "not counted (build with --features count-allocs)".into(),
+                |n| n.to_string()
+            )
+        )
+    }
+}
+
+/// the `VmHWM` line of `/proc/self/status`
+fn peak_rss_kb() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    line.split_whitespace().nth(1)?.parse().ok()
+}
+
+/// `utime` and `stime` from `/proc/self/stat`, which count clock ticks
+fn cpu_seconds() -> Option<f64> {
+    let stat = fs::read_to_string("/proc/self/stat").ok()?;
+    // the command name can contain spaces, so count fields from the closing paren
+    let mut fields = stat.get(stat.rfind(')')? + 2..)?.split_whitespace();
+    let utime: u64 = fields.nth(11)?.parse().ok()?;
+    let stime: u64 = fields.next()?.parse().ok()?;
+    Some((utime + stime) as f64 / ticks_per_second()?)
+}
+
+/// how many clock ticks the kernel counts a second, which `/proc` times are in
+#[cfg(target_os = "linux")]
+fn ticks_per_second() -> Option<f64> {
+    // SAFETY: sysconf only reads a configuration value
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    (ticks > 0).then_some(ticks as f64)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn ticks_per_second() -> Option<f64> {
+    None
+}
+
+#[cfg(feature = "count-allocs")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+    /// the system allocator, counting every allocation it hands out
+    struct Counting;
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            System.alloc(layout)
+        }
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: Counting = Counting;
+
+    pub fn allocations() -> Option<usize> {
+        Some(ALLOCATIONS.load(Ordering::Relaxed))
+    }
+}
+
+#[cfg(not(feature = "count-allocs"))]
+mod counting {
+    pub fn allocations() -> Option<usize> {
+        None
+    }
+}
@@ -0,0 +1,154 @@
+use sudoku_core::Board;
+
+/// a puzzle, one string per row, with `.` for blanks
+type Grid = [&'static str; 9];
+
+const EASY: Grid = [
+    "53..7....",
+    "6..195...",
+    ".98....6.",
+    "8...6...3",
+    "4..8.3..1",
+    "7...2...6",
+    ".6....28.",
+    "...419..5",
+    "....8..79",
+];
+const EASY_SOLUTION: Grid = [
+    "534678912",
+    "672195348",
+    "198342567",
+    "859761423",
+    "426853791",
+    "713924856",
+    "961537284",
+    "287419635",
+    "345286179",
+];
+const HARD: Grid = [
+    ".......12",
+    "....35...",
+    "...6...7.",
+    "7.....3..",
+    "...4..8..",
+    "1........",
+    "...12....",
+    ".8.....4.",
+    ".5....6..",
+];
+const HARD_SOLUTION: Grid = [
+    "673894512",
+    "912735486",
+    "845612973",
+    "798261354",
+    "526473891",
+    "134589267",
+    "469128735",
+    "287356149",
+    "351947628",
+];
+/// two 5s in the first row
+const DUPLICATE_GIVENS: Grid = [
+    "53..7...5",
+    "6..195...",
+    ".98....6.",
+    "8...6...3",
+    "4..8.3..1",
+    "7...2...6",
+    ".6....28.",
+    "...419..5",
+    "....8..79",
+];
+
+struct Check {
+    subsystem: &'static str,
+    name: &'static str,
+    run: fn() -> bool,
+}
+
+const CHECKS: &[Check] = &[
+    Check {
+        subsystem: "build",
+        name: "accepts a well formed puzzle",
+        run: || Board::build(lines(EASY)).is_ok(),
+    },
+    Check {
+        subsystem: "build",
+        name: "rejects the wrong number of rows",
+        run: || Board::build(lines(EASY)[..8].to_vec()).is_err(),
+    },
+    Check {
+        subsystem: "build",
+        name: "rejects values outside 1-9",
+        run: || {
+            let mut lines = lines(EASY);
+            lines[0][2] = Some(10);
+            Board::build(lines).is_err()
+        },
+    },
+    Check {
+        subsystem: "solve",
+        name: "solves an easy puzzle",
+        run: || solves_to(EASY, EASY_SOLUTION),
+    },
+    Check {
+        subsystem: "solve",
+        name: "solves a hard puzzle",
+        run: || solves_to(HARD, HARD_SOLUTION),
+    },
+    Check {
+        subsystem: "solve",
+        name: "leaves a finished puzzle alone",
+        run: || solves_to(EASY_SOLUTION, EASY_SOLUTION),
+    },
+    Check {
+        subsystem: "solve",
+        name: "rejects duplicate givens",
+        run: || {
+            matches!(
+                Board::build(lines(DUPLICATE_GIVENS)).map(Board::solve),
+                Ok(Err(_))
+            )
+        },
+    },
+];
+
+fn lines(grid: Grid) -> Vec<Vec<Option<u8>>> {
+    grid.iter()
+        .map(|row| {
+            row.chars()
+                .map(|c| c.to_digit(10).map(|d| d as u8))
+                .collect()
+        })
+        .collect()
+}
+fn solves_to(puzzle: Grid, solution: Grid) -> bool {
+    let expected: Vec<Vec<Option<usize>>> = lines(solution)
+        .into_iter()
+        .map(|row| row.into_iter().map(|cell| cell.map(usize::from)).collect())
+        .collect();
+    match Board::build(lines(puzzle)).map(Board::solve) {
+        Ok(Ok(board)) => {
+            let solved: [[Option<usize>; 9]; 9] = board.into();
+            solved.iter().map(|row| row.to_vec()).eq(expected)
+        }
+        _ => false,
+    }
+}
+
+/// runs every check, printing a line per check, and returns true if they all passed
+pub fn run() -> bool {
+    let mut passed = 0;
+    for check in CHECKS {
+        let ok = (check.run)();
+        passed += ok as usize;
+        println!(
+            "{:<8} {:<36} {}",
+            check.subsystem,
+            check.name,
+            if ok { "pass" } else { "FAIL" }
+        );
+    }
+    println!("{passed}/{} checks passed", CHECKS.len());
+    passed == CHECKS.len()
+}
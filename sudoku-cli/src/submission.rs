@@ -0,0 +1,138 @@
+//! checking a puzzle a publisher submits, with everything the other commands look at in one
+//! verdict
+//!
+//! the puzzle is read like a line of a collection (see `dedup`), checked for contradictions,
+//! counted, rated, and looked for in a collection of puzzles already accepted, in that order.
+//! the checks after the first failing one are skipped, since they can't say anything useful
+
+use anyhow::{Context, Result};
+use std::io::BufRead;
+use sudoku_core::{io, Board, Rating};
+
+/// what `check` found out about a submitted puzzle
+#[derive(Debug, PartialEq, Eq)]
+pub struct Verdict {
+    /// the puzzle as 81 characters with `.` for blanks, however it was submitted
+    pub puzzle: String,
+    /// the puzzle relabeled and shuffled into the form every isomorph of it shares
+    pub canonical: String,
+    /// why the givens can't be solved, if filling in what they force runs into a contradiction
+    pub contradiction: Option<String>,
+    /// how many solutions, counting no further than 2
+    pub solutions: usize,
+    /// `None` unless the puzzle has a single solution
+    pub rating: Option<Rating>,
+    /// the line of the collection with the same puzzle or an isomorph of it
+    pub duplicate_of: Option<usize>,
+}
+
+impl Verdict {
+    /// whether a publisher can take the puzzle: it has one solution and isn't in the collection
+    pub fn accepted(&self) -> bool {
+        self.contradiction.is_none() && self.solutions == 1 && self.duplicate_of.is_none()
+    }
+    /// every check as a `(name, value)` pair, for the lines printed and the `report` event
+    pub fn fields(&self) -> Vec<(&'static str, String)> {
+        let or_none = |val: Option<String>| val.unwrap_or_else(|| "none".to_string());
+        vec![
+            ("accepted", self.accepted().to_string()),
+            ("puzzle", self.puzzle.clone()),
+            ("canonical", self.canonical.clone()),
+            ("contradiction", or_none(self.contradiction.clone())),
+            ("solutions", self.solutions.to_string()),
+            (
+                "rating",
+                or_none(self.rating.map(|rating| rating.to_string())),
+            ),
+            (
+                "duplicate of line",
+                or_none(self.duplicate_of.map(|line| line.to_string())),
+            ),
+        ]
+    }
+}
+
+/// checks the puzzle in `text` against the collection of accepted puzzles in `against`
+pub fn check(text: &str, against: Option<impl BufRead>) -> Result<Verdict> {
+    let board = io::read_gordon(text).context("reading the submitted puzzle")?;
+    let mut verdict = Verdict {
+        puzzle: board.to_line_string(),
+        canonical: board.canonical_form().to_string(),
+        contradiction: board
+            .clone()
+            .propagate()
+            .contradiction
+            .map(|why| format!("{why:?}")),
+        solutions: 0,
+        rating: None,
+        duplicate_of: None,
+    };
+    if verdict.contradiction.is_some() {
+        return Ok(verdict);
+    }
+    verdict.solutions = board.count_solutions(2)?;
+    if verdict.solutions != 1 {
+        return Ok(verdict);
+    }
+    verdict.rating = board.rate().ok();
+    if let Some(collection) = against {
+        verdict.duplicate_of = find(&board, collection)?;
+    }
+    Ok(verdict)
+}
+
+/// the line of `collection` with `board` or an isomorph of it
+fn find(board: &Board, collection: impl BufRead) -> Result<Option<usize>> {
+    let form = board.canonical_form().packed();
+    for (number, line) in collection.lines().enumerate() {
+        let line = line?;
+        let puzzle = line.trim();
+        if puzzle.is_empty() || puzzle.starts_with('#') {
+            continue;
+        }
+        let other = io::read_gordon(puzzle)
+            .with_context(|| format!("line {} of the collection", number + 1))?;
+        if other.canonical_form().packed() == form {
+            return Ok(Some(number + 1));
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PUZZLE: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+
+    #[test]
+    fn new_puzzles_with_one_solution_are_accepted() {
+        let verdict = check(PUZZLE, Some("# accepted so far\n".as_bytes())).unwrap();
+
+        assert!(verdict.accepted());
+        assert_eq!(verdict.puzzle, PUZZLE);
+        assert_eq!(verdict.solutions, 1);
+        assert!(verdict.rating.is_some());
+        assert_eq!(verdict.fields()[0], ("accepted", "true".to_string()));
+    }
+    #[test]
+    fn isomorphs_of_the_collection_are_turned_down() {
+        // the rows of the first band swapped around
+        let swapped = format!("{}{}{}", &PUZZLE[9..18], &PUZZLE[..9], &PUZZLE[18..]);
+        let collection = format!("{}\n{PUZZLE}\n", ".".repeat(81));
+        let verdict = check(&swapped, Some(collection.as_bytes())).unwrap();
+
+        assert!(!verdict.accepted());
+        assert_eq!(verdict.duplicate_of, Some(2));
+    }
+    #[test]
+    fn broken_puzzles_stop_at_the_contradiction() {
+        let broken = format!("11{}", &PUZZLE[2..]);
+        let verdict = check(&broken, None::<&[u8]>).unwrap();
+
+        assert!(verdict.contradiction.is_some());
+        assert_eq!(verdict.solutions, 0);
+        assert!(!verdict.accepted());
+    }
+}
@@ -0,0 +1,112 @@
+//! picking which solving techniques to use from the command line
+//!
+//! the names are the solver's strategy names with dashes for spaces (`naked-pair`), but case,
+//! spaces, and dashes are ignored when matching, so `xwing` and `X-Wing` both mean `x-wing`
+
+use anyhow::{bail, Result};
+use sudoku_core::strategy_names;
+
+/// the strategies to turn off so that only `enable` run (or all of them if it's empty), without
+/// any in `disable`
+pub fn disabled(enable: &[String], disable: &[String]) -> Result<Vec<&'static str>> {
+    let enable = enable
+        .iter()
+        .map(|name| resolve(name))
+        .collect::<Result<Vec<_>>>()?;
+    let disable = disable
+        .iter()
+        .map(|name| resolve(name))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(strategy_names()
+        .into_iter()
+        .filter(|name| (!enable.is_empty() && !enable.contains(name)) || disable.contains(name))
+        .collect())
+}
+
+/// the strategy a technique name refers to, suggesting close names if there isn't one
+pub fn resolve(name: &str) -> Result<&'static str> {
+    let wanted = normalize(name);
+    let names = strategy_names();
+    if let Some(&found) = names.iter().find(|known| normalize(known) == wanted) {
+        return Ok(found);
+    }
+    let suggestions: Vec<_> = names
+        .iter()
+        .filter(|known| {
+            let known = normalize(known);
+            // `chains` should still find `forcing chain`
+            let stem = wanted.trim_end_matches('s');
+            known.contains(stem) || distance(&known, &wanted) <= 2
+        })
+        .map(|known| cli_name(known))
+        .collect();
+    if suggestions.is_empty() {
+        let all: Vec<_> = names.iter().map(|known| cli_name(known)).collect();
+        bail!(
+            "unknown technique {name:?}, expected one of: {}",
+            all.join(", ")
+        )
+    }
+    bail!(
+        "unknown technique {name:?}, did you mean {}?",
+        suggestions.join(" or ")
+    )
+}
+
+fn cli_name(name: &str) -> String {
+    name.replace(' ', "-")
+}
+
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '_'))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// the fewest single character insertions, deletions, or swaps to turn `a` into `b`
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, &b) in b.iter().enumerate() {
+            let swap = previous[j] + usize::from(a != b);
+            current.push(swap.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn resolve_ignores_case_and_dashes() {
+        assert_eq!(resolve("xwing").unwrap(), "x-wing");
+        assert_eq!(resolve("Naked-Pair").unwrap(), "naked pair");
+    }
+    #[test]
+    fn resolve_suggests_close_names() {
+        let error = resolve("chains").unwrap_err().to_string();
+        assert!(error.contains("did you mean forcing-chain?"), "{error}");
+
+        let error = resolve("swordfsh").unwrap_err().to_string();
+        assert!(error.contains("swordfish"), "{error}");
+    }
+    #[test]
+    fn disabled_keeps_only_enabled_techniques() {
+        let disabled = disabled(&["xwing".into(), "swordfish".into()], &[]).unwrap();
+
+        assert_eq!(disabled.len(), strategy_names().len() - 2);
+        assert!(!disabled.contains(&"x-wing"));
+    }
+    #[test]
+    fn disabled_wins_over_enabled() {
+        let disabled = disabled(&["xwing".into()], &["x-wing".into()]).unwrap();
+
+        assert_eq!(disabled, strategy_names());
+    }
+}
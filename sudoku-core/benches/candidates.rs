@@ -0,0 +1,104 @@
+//! compares the candidate set representations on the same puzzles
+//!
+//! every representation runs the same kernel: place each given, remove it from the cell's
+//! peers, and keep placing any cell that drops to one candidate until nothing changes
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::{
+    collections::{BTreeSet, HashSet},
+    hint::black_box,
+};
+use sudoku_core::{BitSet, CandidateSet, CellVal};
+
+const PUZZLES: [(&str, &str); 4] = [
+    (
+        "easy",
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79",
+    ),
+    (
+        "medium",
+        "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..",
+    ),
+    (
+        "hard",
+        ".......12....35......6...7.7.....3.....4..8..1...........12.....8.....4..5....6..",
+    ),
+    (
+        "empty",
+        ".................................................................................",
+    ),
+];
+
+fn parse(puzzle: &str) -> Vec<Option<CellVal>> {
+    puzzle
+        .chars()
+        .map(|c| CellVal::new(c.to_digit(10)? as usize).ok())
+        .collect()
+}
+
+/// every other cell in the same row, column, or house
+fn peers() -> Vec<Vec<usize>> {
+    (0..81)
+        .map(|i| {
+            let (row, column) = (i / 9, i % 9);
+            (0..81)
+                .filter(|&j| {
+                    let (r, c) = (j / 9, j % 9);
+                    j != i && (r == row || c == column || (r / 3 == row / 3 && c / 3 == column / 3))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// returns how many cells were placed, or `None` if a cell ran out of candidates
+fn propagate<S: CandidateSet>(givens: &[Option<CellVal>], peers: &[Vec<usize>]) -> Option<usize> {
+    let mut cells = vec![S::full(); 81];
+    let mut placed = [false; 81];
+    let mut queue: Vec<(usize, CellVal)> = givens
+        .iter()
+        .enumerate()
+        .filter_map(|(i, given)| Some((i, (*given)?)))
+        .collect();
+    while let Some((i, val)) = queue.pop() {
+        if placed[i] {
+            continue;
+        }
+        placed[i] = true;
+        for &peer in &peers[i] {
+            if cells[peer].remove(val) {
+                if cells[peer].is_empty() {
+                    return None;
+                }
+                if let Some(single) = cells[peer].single() {
+                    queue.push((peer, single));
+                }
+            }
+        }
+    }
+    Some(placed.iter().filter(|&&placed| placed).count())
+}
+
+fn bench_representations(c: &mut Criterion) {
+    let peers = peers();
+    let mut group = c.benchmark_group("propagate");
+    for (name, puzzle) in PUZZLES {
+        let givens = parse(puzzle);
+        group.bench_with_input(BenchmarkId::new("im::HashSet", name), &givens, |b, g| {
+            b.iter(|| propagate::<im::HashSet<CellVal>>(black_box(g), &peers))
+        });
+        group.bench_with_input(BenchmarkId::new("HashSet", name), &givens, |b, g| {
+            b.iter(|| propagate::<HashSet<CellVal>>(black_box(g), &peers))
+        });
+        group.bench_with_input(BenchmarkId::new("BTreeSet", name), &givens, |b, g| {
+            b.iter(|| propagate::<BTreeSet<CellVal>>(black_box(g), &peers))
+        });
+        group.bench_with_input(BenchmarkId::new("BitSet", name), &givens, |b, g| {
+            b.iter(|| propagate::<BitSet>(black_box(g), &peers))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_representations);
+criterion_main!(benches);
@@ -0,0 +1,109 @@
+//! times the propagation kernels on their own, see `sudoku_core::kernels`
+//!
+//! each group runs one kernel on the same positions, so a change to how cells or units are
+//! stored shows up in the kernel it affects. run with
+//! `cargo bench -p sudoku-core --features kernels --bench propagation`
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use std::hint::black_box;
+use sudoku_core::{
+    io::read_gordon,
+    kernels::{check_unit, guess_and_propagate, remove_possibilities},
+    Board, CellPos, CellVal, Unit,
+};
+
+const POSITIONS: [(&str, &str); 3] = [
+    (
+        "easy",
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79",
+    ),
+    (
+        "hard",
+        ".......12....35......6...7.7.....3.....4..8..1...........12.....8.....4..5....6..",
+    ),
+    (
+        "empty",
+        ".................................................................................",
+    ),
+];
+
+fn positions() -> impl Iterator<Item = (&'static str, Board)> {
+    POSITIONS.into_iter().map(|(name, puzzle)| {
+        (
+            name,
+            read_gordon(puzzle).expect("the positions are 81 cells"),
+        )
+    })
+}
+
+fn bench_remove_possibilities(c: &mut Criterion) {
+    let mut group = c.benchmark_group("remove_possibilities");
+    // four values out of the first blank, which still has all nine before propagating
+    let vals: Vec<_> = CellVal::cell_vals().take(4).collect();
+    for (name, board) in positions() {
+        let givens = board.givens();
+        let blank = CellPos::all()
+            .find(|pos| !givens[pos.row()][pos.column()])
+            .expect("every position has a blank");
+        group.bench_with_input(BenchmarkId::from_parameter(name), &board, |b, board| {
+            b.iter_batched_ref(
+                || board.clone(),
+                |board| remove_possibilities(board, blank, black_box(&vals)),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn bench_check_unit(c: &mut Criterion) {
+    let mut group = c.benchmark_group("check_unit");
+    for (name, board) in positions() {
+        for unit in [Unit::Row(0), Unit::Column(4), Unit::House(8)] {
+            let id = BenchmarkId::new(format!("{unit:?}"), name);
+            group.bench_with_input(id, &board, |b, board| {
+                b.iter_batched_ref(
+                    || board.clone(),
+                    |board| check_unit(board, black_box(unit)),
+                    BatchSize::SmallInput,
+                )
+            });
+        }
+    }
+    group.finish();
+}
+
+fn bench_propagate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("propagate_board");
+    for (name, board) in positions() {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &board, |b, board| {
+            b.iter_batched(
+                || board.clone(),
+                |board| black_box(board).propagate(),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn bench_guess_and_propagate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("guess_and_propagate");
+    for (name, board) in positions() {
+        // the search only guesses once propagating has stopped changing the board
+        let board = board.propagate().board;
+        group.bench_with_input(BenchmarkId::from_parameter(name), &board, |b, board| {
+            b.iter(|| guess_and_propagate(black_box(board)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    kernels,
+    bench_remove_possibilities,
+    bench_check_unit,
+    bench_propagate,
+    bench_guess_and_propagate
+);
+criterion_main!(kernels);
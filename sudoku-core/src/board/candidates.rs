@@ -0,0 +1,339 @@
+use super::{CellVal, Classic, Shape};
+use std::{
+    collections::{BTreeSet, HashSet},
+    fmt,
+    marker::PhantomData,
+};
+
+/// the operations the solver needs from the set of values a cell could still be
+///
+/// every implementation can be compared on the same work (see `benches/candidates.rs`), and the
+/// ones that are also a `SetBackend` can be what `Cell` keeps its candidates in
+pub trait CandidateSet<S: Shape = Classic>: Clone + PartialEq + fmt::Debug {
+    /// every value of the board, 1 to 9 on the classic one
+    fn full() -> Self;
+    fn contains(&self, val: CellVal<S>) -> bool;
+    /// removes the value, returning whether it was there
+    fn remove(&mut self, val: CellVal<S>) -> bool;
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// the value, if it is the only one left
+    fn single(&self) -> Option<CellVal<S>>;
+}
+
+/// a `CandidateSet` that `Candidates` can be built on
+///
+/// which one is used is picked by the crate features: `bitset` uses `BitSet`, `im` (on by
+/// default) uses `im::HashSet`, and with neither a `std::collections::HashSet` is used, so the
+/// crate still builds without `im`
+pub trait SetBackend<S: Shape = Classic>: CandidateSet<S> + FromIterator<CellVal<S>> {
+    fn empty() -> Self {
+        std::iter::empty().collect()
+    }
+    fn insert(&mut self, val: CellVal<S>);
+    /// the values in the set, in no particular order
+    fn values(&self) -> impl Iterator<Item = CellVal<S>> + '_;
+}
+
+#[cfg(feature = "bitset")]
+type Backend<S> = BitSet<S>;
+#[cfg(all(feature = "im", not(feature = "bitset")))]
+type Backend<S> = im::HashSet<CellVal<S>>;
+#[cfg(not(any(feature = "im", feature = "bitset")))]
+type Backend<S> = HashSet<CellVal<S>>;
+
+/// the values a cell could still be, kept in whichever `SetBackend` the features picked
+///
+/// the backend's own methods are only called through the traits, since `im::HashSet` and
+/// `HashSet` have methods with the same names that work differently
+#[derive(Clone, PartialEq, Eq)]
+pub struct Candidates<S: Shape = Classic>(Backend<S>);
+
+impl<S: Shape> Candidates<S> {
+    /// every value of the board
+    pub(crate) fn full() -> Self {
+        Candidates(CandidateSet::full())
+    }
+    pub(crate) fn empty() -> Self {
+        Candidates(SetBackend::empty())
+    }
+    pub(crate) fn contains(&self, val: CellVal<S>) -> bool {
+        CandidateSet::contains(&self.0, val)
+    }
+    pub(crate) fn len(&self) -> usize {
+        CandidateSet::len(&self.0)
+    }
+    pub(crate) fn is_empty(&self) -> bool {
+        CandidateSet::is_empty(&self.0)
+    }
+    /// the value, if it is the only one left
+    pub(crate) fn single(&self) -> Option<CellVal<S>> {
+        CandidateSet::single(&self.0)
+    }
+    pub(crate) fn insert(&mut self, val: CellVal<S>) {
+        SetBackend::insert(&mut self.0, val)
+    }
+    /// a copy without `val`
+    pub(crate) fn without(&self, val: CellVal<S>) -> Self {
+        let mut out = self.clone();
+        CandidateSet::remove(&mut out.0, val);
+        out
+    }
+    /// the values in the set, in no particular order
+    pub(crate) fn iter(&self) -> impl Iterator<Item = CellVal<S>> + '_ {
+        SetBackend::values(&self.0)
+    }
+    /// the values in both sets
+    pub(crate) fn intersection(&self, other: &Self) -> Self {
+        self.iter().filter(|&val| other.contains(val)).collect()
+    }
+    /// the values in this set but not `other`
+    pub(crate) fn difference(&self, other: &Self) -> Self {
+        self.iter().filter(|&val| !other.contains(val)).collect()
+    }
+    /// the values in any of the sets
+    pub(crate) fn unions<'a>(sets: impl IntoIterator<Item = &'a Self>) -> Self {
+        sets.into_iter().flat_map(Candidates::iter).collect()
+    }
+}
+impl<S: Shape> FromIterator<CellVal<S>> for Candidates<S> {
+    fn from_iter<T: IntoIterator<Item = CellVal<S>>>(iter: T) -> Self {
+        Candidates(iter.into_iter().collect())
+    }
+}
+impl<S: Shape> IntoIterator for Candidates<S> {
+    type Item = CellVal<S>;
+    type IntoIter = std::vec::IntoIter<CellVal<S>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter().collect::<Vec<_>>().into_iter()
+    }
+}
+impl<S: Shape> fmt::Debug for Candidates<S> {
+    /// in order, so the output doesn't depend on the backend
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut vals: Vec<_> = self.iter().map(CellVal::into_inner).collect();
+        vals.sort();
+        f.debug_set().entries(vals).finish()
+    }
+}
+
+#[cfg(feature = "im")]
+impl<S: Shape> CandidateSet<S> for im::HashSet<CellVal<S>> {
+    fn full() -> Self {
+        CellVal::cell_vals().collect()
+    }
+    fn contains(&self, val: CellVal<S>) -> bool {
+        im::HashSet::contains(self, &val)
+    }
+    fn remove(&mut self, val: CellVal<S>) -> bool {
+        im::HashSet::remove(self, &val).is_some()
+    }
+    fn len(&self) -> usize {
+        im::HashSet::len(self)
+    }
+    fn single(&self) -> Option<CellVal<S>> {
+        match self.len() {
+            1 => self.iter().next().copied(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "im")]
+impl<S: Shape> SetBackend<S> for im::HashSet<CellVal<S>> {
+    fn insert(&mut self, val: CellVal<S>) {
+        im::HashSet::insert(self, val);
+    }
+    fn values(&self) -> impl Iterator<Item = CellVal<S>> + '_ {
+        self.iter().copied()
+    }
+}
+
+impl<S: Shape> CandidateSet<S> for HashSet<CellVal<S>> {
+    fn full() -> Self {
+        CellVal::cell_vals().collect()
+    }
+    fn contains(&self, val: CellVal<S>) -> bool {
+        HashSet::contains(self, &val)
+    }
+    fn remove(&mut self, val: CellVal<S>) -> bool {
+        HashSet::remove(self, &val)
+    }
+    fn len(&self) -> usize {
+        HashSet::len(self)
+    }
+    fn single(&self) -> Option<CellVal<S>> {
+        match self.len() {
+            1 => self.iter().next().copied(),
+            _ => None,
+        }
+    }
+}
+impl<S: Shape> SetBackend<S> for HashSet<CellVal<S>> {
+    fn insert(&mut self, val: CellVal<S>) {
+        HashSet::insert(self, val);
+    }
+    fn values(&self) -> impl Iterator<Item = CellVal<S>> + '_ {
+        self.iter().copied()
+    }
+}
+
+impl<S: Shape> CandidateSet<S> for BTreeSet<CellVal<S>> {
+    fn full() -> Self {
+        CellVal::cell_vals().collect()
+    }
+    fn contains(&self, val: CellVal<S>) -> bool {
+        BTreeSet::contains(self, &val)
+    }
+    fn remove(&mut self, val: CellVal<S>) -> bool {
+        BTreeSet::remove(self, &val)
+    }
+    fn len(&self) -> usize {
+        BTreeSet::len(self)
+    }
+    fn single(&self) -> Option<CellVal<S>> {
+        match self.len() {
+            1 => self.first().copied(),
+            _ => None,
+        }
+    }
+}
+
+/// a set of cell values packed into the bits of a `u64` (bit `n` is the value `n`), so it holds
+/// the values of a board up to 63 across
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct BitSet<S: Shape = Classic>(u64, PhantomData<S>);
+
+impl<S: Shape> BitSet<S> {
+    fn bit(val: CellVal<S>) -> u64 {
+        1 << val.into_inner()
+    }
+}
+impl<S: Shape> fmt::Debug for BitSet<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set()
+            .entries(
+                CellVal::cell_vals()
+                    .filter(|&val| self.contains(val))
+                    .map(CellVal::into_inner),
+            )
+            .finish()
+    }
+}
+impl<S: Shape> FromIterator<CellVal<S>> for BitSet<S> {
+    fn from_iter<T: IntoIterator<Item = CellVal<S>>>(iter: T) -> Self {
+        let bits = iter.into_iter().fold(0, |bits, val| bits | Self::bit(val));
+        BitSet(bits, PhantomData)
+    }
+}
+impl<S: Shape> CandidateSet<S> for BitSet<S> {
+    fn full() -> Self {
+        CellVal::cell_vals().collect()
+    }
+    fn contains(&self, val: CellVal<S>) -> bool {
+        self.0 & Self::bit(val) != 0
+    }
+    fn remove(&mut self, val: CellVal<S>) -> bool {
+        let had = self.contains(val);
+        self.0 &= !Self::bit(val);
+        had
+    }
+    fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+    fn single(&self) -> Option<CellVal<S>> {
+        match self.len() {
+            1 => CellVal::new(self.0.trailing_zeros() as usize).ok(),
+            _ => None,
+        }
+    }
+}
+impl<S: Shape> SetBackend<S> for BitSet<S> {
+    fn insert(&mut self, val: CellVal<S>) {
+        self.0 |= Self::bit(val);
+    }
+    fn values(&self) -> impl Iterator<Item = CellVal<S>> + '_ {
+        CellVal::cell_vals().filter(move |&val| self.contains(val))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    /// the same checks for every representation
+    fn behaves_like_a_set<S: CandidateSet>() {
+        let mut set = S::full();
+        assert_eq!(set.len(), 9);
+        assert!(set.contains(cell_val!(4)));
+
+        assert!(set.remove(cell_val!(4)));
+        assert!(!set.remove(cell_val!(4)));
+        assert!(!set.contains(cell_val!(4)));
+        assert_eq!(set.single(), None);
+
+        for val in CellVal::cell_vals().filter(|&val| val != cell_val!(7)) {
+            set.remove(val);
+        }
+        assert_eq!(set.single(), Some(cell_val!(7)));
+        set.remove(cell_val!(7));
+        assert!(set.is_empty());
+    }
+
+    /// the extra checks for the sets `Candidates` can use
+    fn behaves_like_a_backend<S: SetBackend>() {
+        behaves_like_a_set::<S>();
+
+        let mut set = S::empty();
+        assert!(set.is_empty());
+        set.insert(cell_val!(3));
+        set.insert(cell_val!(5));
+        let mut vals: Vec<_> = set.values().collect();
+        vals.sort();
+        assert_eq!(vals, [cell_val!(3), cell_val!(5)]);
+    }
+
+    #[cfg(feature = "im")]
+    #[test]
+    fn im_hash_set_is_a_backend() {
+        behaves_like_a_backend::<im::HashSet<CellVal>>();
+    }
+    #[test]
+    fn hash_set_is_a_backend() {
+        behaves_like_a_backend::<HashSet<CellVal>>();
+    }
+    #[test]
+    fn btree_set_is_a_candidate_set() {
+        behaves_like_a_set::<BTreeSet<CellVal>>();
+    }
+    #[test]
+    fn bit_set_is_a_backend() {
+        behaves_like_a_backend::<BitSet>();
+    }
+    #[test]
+    fn candidates_combine_like_sets() {
+        let a: Candidates = [cell_val!(1), cell_val!(2), cell_val!(3)]
+            .into_iter()
+            .collect();
+        let b: Candidates = [cell_val!(2), cell_val!(4)].into_iter().collect();
+
+        assert_eq!(format!("{:?}", a.intersection(&b)), "{2}");
+        assert_eq!(format!("{:?}", a.difference(&b)), "{1, 3}");
+        assert_eq!(
+            format!("{:?}", Candidates::unions([&a, &b])),
+            "{1, 2, 3, 4}"
+        );
+        assert_eq!(
+            a.without(cell_val!(1)),
+            a.difference(&[cell_val!(1)].into_iter().collect())
+        );
+    }
+    #[test]
+    fn bit_set_debug_lists_values() {
+        let set: BitSet = [cell_val!(2), cell_val!(9)].into_iter().collect();
+        assert_eq!(format!("{set:?}"), "{2, 9}");
+    }
+}
@@ -0,0 +1,210 @@
+use super::{Board, Candidates, Cell, CellPos, CellVal};
+use std::fmt;
+
+/// the orders of three lines
+const PERMUTATIONS: [[usize; 3]; 6] = [
+    [0, 1, 2],
+    [0, 2, 1],
+    [1, 0, 2],
+    [1, 2, 0],
+    [2, 0, 1],
+    [2, 1, 0],
+];
+
+/// a puzzle's givens moved and relabeled into the smallest equivalent grid
+///
+/// two puzzles have the same form exactly when `Board::disguise` could turn one into the other,
+/// so it tells isomorphs apart from different puzzles. cells are 0 for blanks and 1-9 for digits,
+/// and the smallest grid compares them row by row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CanonicalForm([u8; 81]);
+
+impl CanonicalForm {
+    /// two cells to a byte, which is all a seen-set of forms needs to keep
+    pub fn packed(&self) -> [u8; 41] {
+        let mut packed = [0; 41];
+        for (i, cell) in self.0.iter().enumerate() {
+            packed[i / 2] |= cell << (4 * (i % 2));
+        }
+        packed
+    }
+    /// the puzzle the form stands for, with its givens where the form put them
+    pub fn board(&self) -> Board {
+        CellPos::all()
+            .zip(self.0)
+            .map(|(pos, cell)| match CellVal::new(cell as usize) {
+                Ok(val) => (pos, Cell::Concrete(val)),
+                Err(_) => (pos, Cell::Possibilities(Candidates::full())),
+            })
+            .collect()
+    }
+}
+impl fmt::Display for CanonicalForm {
+    /// one line of 81 characters, with `.` for blanks
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &cell in &self.0 {
+            match cell {
+                0 => write!(f, ".")?,
+                digit => write!(f, "{digit}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Board {
+    /// the canonical form of the board's givens, see `CanonicalForm`
+    ///
+    /// every arrangement of the columns and transposition is tried, and for each one the rows
+    /// are picked one at a time, dropping a branch as soon as a row comes out larger than the
+    /// same row of the smallest grid found so far
+    pub fn canonical_form(&self) -> CanonicalForm {
+        let mut grid = [0; 81];
+        let givens: [[Option<usize>; 9]; 9] = self.clone().into();
+        for (i, cell) in givens.iter().flatten().enumerate() {
+            grid[i] = cell.map_or(0, |val| val as u8);
+        }
+        let mut transposed = [0; 81];
+        for (i, cell) in transposed.iter_mut().enumerate() {
+            *cell = grid[i % 9 * 9 + i / 9];
+        }
+        let mut search = Search {
+            grid: &grid,
+            columns: [0; 9],
+            rows: [0; 9],
+            form: [0; 81],
+            best: [u8::MAX; 81],
+        };
+        for grid in [&grid, &transposed] {
+            search.grid = grid;
+            for columns in line_orders() {
+                search.columns = columns;
+                search.pick_row(0, [0; 10], 1, false);
+            }
+        }
+        CanonicalForm(search.best)
+    }
+}
+
+/// every order of the 9 lines that keeps each band of 3 together
+fn line_orders() -> impl Iterator<Item = [usize; 9]> {
+    PERMUTATIONS.iter().flat_map(|bands| {
+        PERMUTATIONS.iter().flat_map(move |first| {
+            PERMUTATIONS.iter().flat_map(move |second| {
+                PERMUTATIONS.iter().map(move |third| {
+                    let mut order = [0; 9];
+                    for (band, lines) in [first, second, third].into_iter().enumerate() {
+                        for (line, &offset) in lines.iter().enumerate() {
+                            order[band * 3 + line] = bands[band] * 3 + offset;
+                        }
+                    }
+                    order
+                })
+            })
+        })
+    })
+}
+
+/// the smallest grid over the row arrangements of one column arrangement
+struct Search<'g> {
+    grid: &'g [u8; 81],
+    /// the column of `grid` that each column of the form comes from
+    columns: [usize; 9],
+    /// the row of `grid` that each row of the form comes from, as far as it's picked
+    rows: [usize; 9],
+    /// the form for the rows picked so far
+    form: [u8; 81],
+    best: [u8; 81],
+}
+
+impl Search<'_> {
+    /// tries each row that can go in `slot`, comparing it against the same row of `best`
+    ///
+    /// `labels` maps each digit to the label it got so far (0 for none yet), and `less` is true
+    /// once the rows picked were smaller than `best`'s, so they don't need comparing any more
+    fn pick_row(&mut self, slot: usize, labels: [u8; 10], next: u8, less: bool) {
+        if slot == 9 {
+            // `less` may have been decided against an older, larger `best`
+            if self.form < self.best {
+                self.best = self.form;
+            }
+            return;
+        }
+        for row in 0..9 {
+            let allowed = if slot.is_multiple_of(3) {
+                // the first row of a band can come from any band that isn't used yet
+                self.rows[..slot].iter().all(|&used| used / 3 != row / 3)
+            } else {
+                row / 3 == self.rows[slot - 1] / 3 && !self.rows[..slot].contains(&row)
+            };
+            if !allowed {
+                continue;
+            }
+            let (mut labels, mut next, mut less) = (labels, next, less);
+            let mut larger = false;
+            for (column, &from) in self.columns.iter().enumerate() {
+                let i = slot * 9 + column;
+                let label = relabel(self.grid[row * 9 + from], &mut labels, &mut next);
+                self.form[i] = label;
+                if !less && label != self.best[i] {
+                    larger = label > self.best[i];
+                    less = !larger;
+                    if larger {
+                        break;
+                    }
+                }
+            }
+            if larger {
+                continue;
+            }
+            self.rows[slot] = row;
+            self.pick_row(slot + 1, labels, next, less);
+        }
+    }
+}
+
+/// the label of `value`, giving it the next one if it hasn't been seen (blanks stay 0)
+fn relabel(value: u8, labels: &mut [u8; 10], next: &mut u8) -> u8 {
+    if value == 0 {
+        return 0;
+    }
+    let label = &mut labels[value as usize];
+    if *label == 0 {
+        *label = *next;
+        *next += 1;
+    }
+    *label
+}
+
+#[cfg(test)]
+mod test {
+    use crate::board::macros::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn one_given_goes_last_as_a_one() {
+        let form = board!([[?, ?, 7]]).canonical_form();
+
+        assert_eq!(form.to_string(), format!("{}1", ".".repeat(80)));
+    }
+    #[test]
+    fn disguised_puzzles_share_a_form() {
+        let board = board!([[5, 3, ?, ?, 7] [6, ?, ?, 1, 9, 5] [?, 9, 8, ?, ?, ?, ?, 6]]);
+
+        for seed in 0..4 {
+            let disguised = board.disguise(&mut StdRng::seed_from_u64(seed));
+            assert_eq!(disguised.canonical_form(), board.canonical_form());
+        }
+        let form = board.canonical_form();
+        assert_eq!(form.board().canonical_form(), form);
+    }
+    #[test]
+    fn different_puzzles_have_different_forms() {
+        // the two givens share a row in one and only a house in the other
+        let a = board!([[1, ?, ?, 2]]).canonical_form();
+        let b = board!([[1] [?, ?, 2]]).canonical_form();
+
+        assert_ne!(a, b);
+        assert_ne!(a.packed(), b.packed());
+    }
+}
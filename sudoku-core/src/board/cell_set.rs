@@ -1,18 +1,18 @@
 use super::{
+    candidates::Candidates,
     cell::{Cell, CellVal, ToSet},
-    Board, CellPos, Index,
+    Board, CellPos, Index, Shape,
 };
 use crate::UpdateError;
-use anyhow::Result;
-use im::HashSet;
+use std::collections::HashSet;
 
-type PossibleSet = HashSet<CellPos>;
+type PossibleSet<S> = HashSet<CellPos<S>>;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-struct ConcreteSet(HashSet<CellVal>);
-impl ConcreteSet {
-    fn insert(&mut self, val: CellVal) -> Result<(), UpdateError> {
-        if self.0.contains(&val) {
+struct ConcreteSet<S: Shape>(Candidates<S>);
+impl<S: Shape> ConcreteSet<S> {
+    fn insert(&mut self, val: CellVal<S>) -> Result<(), UpdateError> {
+        if self.0.contains(val) {
             Err(UpdateError::InvalidConcrete)?;
         } else {
             self.0.insert(val);
@@ -22,14 +22,14 @@ impl ConcreteSet {
 }
 
 #[derive(Debug, PartialEq, Eq)]
-struct UpdateSets<'b> {
-    board: &'b mut Board,
-    concrete_set: ConcreteSet,
-    possible_set: PossibleSet,
+struct UpdateSets<'b, S: Shape> {
+    board: &'b mut Board<S>,
+    concrete_set: ConcreteSet<S>,
+    possible_set: PossibleSet<S>,
 }
-impl<'b> UpdateSets<'b> {
+impl<S: Shape> UpdateSets<'_, S> {
     fn update(&mut self) -> Result<(), UpdateError> {
-        let mut new_concretes = im::hashset![];
+        let mut new_concretes = HashSet::new();
         // make possible changes
         for &pos in &self.possible_set {
             *self.board.mut_cell(pos) = self
@@ -43,7 +43,7 @@ impl<'b> UpdateSets<'b> {
                 *self.board.mut_cell(pos) = self.board.cell(pos).make_concrete_cell(val)?;
             }
         }
-        self.possible_set = self.possible_set.clone().relative_complement(new_concretes);
+        self.possible_set.retain(|pos| !new_concretes.contains(pos));
         Ok(())
     }
     fn finished(&self) -> bool {
@@ -52,11 +52,7 @@ impl<'b> UpdateSets<'b> {
             .all(|&pos| match self.board.cell(pos) {
                 Cell::Concrete(_) => true,
                 Cell::Possibilities(set) => {
-                    set.len() != 1
-                        && set
-                            .clone()
-                            .intersection(self.concrete_set.0.clone())
-                            .is_empty()
+                    set.len() != 1 && set.intersection(&self.concrete_set.0).is_empty()
                 }
             })
     }
@@ -64,12 +60,12 @@ impl<'b> UpdateSets<'b> {
 
 #[derive(PartialEq, Eq, Debug)]
 /// An unordered set of cells used for updating
-pub(crate) struct CellSet<'b> {
-    set: HashSet<CellPos>,
-    board: &'b mut Board,
+pub(crate) struct CellSet<'b, S: Shape> {
+    set: HashSet<CellPos<S>>,
+    board: &'b mut Board<S>,
 }
 
-impl<'b> CellSet<'b> {
+impl<S: Shape> CellSet<'_, S> {
     /// checks that there are no duplicates or potential duplicates
     pub(crate) fn check_and_update(mut self) -> Result<(), UpdateError> {
         let mut update_sets = self.get_update_set()?;
@@ -79,8 +75,8 @@ impl<'b> CellSet<'b> {
         Ok(())
     }
     /// gets the initial possible and concrete sets for the cell_set
-    fn get_update_set(&mut self) -> Result<UpdateSets, UpdateError> {
-        let mut concrete_set = ConcreteSet(HashSet::new());
+    fn get_update_set(&mut self) -> Result<UpdateSets<'_, S>, UpdateError> {
+        let mut concrete_set = ConcreteSet(Candidates::empty());
         let mut possible_set = HashSet::new();
         for &pos in &self.set {
             match self.board.cell(pos) {
@@ -97,12 +93,13 @@ impl<'b> CellSet<'b> {
         })
     }
 }
-impl Board {
-    pub(crate) fn get_set<C: ToSet>(&mut self, index: Index) -> CellSet {
-        CellSet {
-            set: C::to_set(index),
-            board: self,
-        }
+impl<S: Shape> Board<S> {
+    pub(crate) fn get_set<C: ToSet>(&mut self, index: Index<S>) -> CellSet<'_, S> {
+        self.get_cells(C::to_set(index))
+    }
+    /// any other group of cells that needs every value once, such as a variant's region
+    pub(crate) fn get_cells(&mut self, set: HashSet<CellPos<S>>) -> CellSet<'_, S> {
+        CellSet { set, board: self }
     }
 }
 
@@ -111,7 +108,7 @@ mod test {
     use super::*;
     use crate::board::cell::macros::*;
     use crate::board::macros::*;
-    use crate::board::{Column, House, Row};
+    use crate::board::{Classic, Column, House, Row, Shape};
 
     macro_rules! cell_set {
         (row($row:expr, $board:ident)) => {
@@ -128,24 +125,17 @@ mod test {
         };
         (house($board:ident)) => {
             CellSet {
-                set: im::hashset![
-                    pos!(0, 0),
-                    pos!(0, 1),
-                    pos!(0, 2),
-                    pos!(1, 0),
-                    pos!(1, 1),
-                    pos!(1, 2),
-                    pos!(2, 0),
-                    pos!(2, 1),
-                    pos!(2, 2)
-                ],
+                set: (0..9)
+                    .map(|i| Classic::house_cell(0, i))
+                    .map(|(row, column)| pos!(row, column))
+                    .collect(),
                 board: &mut $board,
             }
         };
     }
     macro_rules! concrete_set {
         [$( $val:expr ),*] => {
-            ConcreteSet(im::hashset![$( cell_val!($val) ),*])
+            ConcreteSet([$( cell_val!($val) ),*].into_iter().collect())
         };
     }
 
@@ -204,7 +194,7 @@ mod test {
             UpdateSets {
                 board: &mut out_board,
                 possible_set,
-                concrete_set: ConcreteSet(im::hashset![]),
+                concrete_set: ConcreteSet(Candidates::empty()),
             }
         )
     }
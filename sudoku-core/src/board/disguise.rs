@@ -0,0 +1,108 @@
+use super::{Board, Cell, CellVal};
+use rand::{seq::SliceRandom, Rng};
+
+impl Board {
+    /// an equivalent board that looks different
+    ///
+    /// relabels the digits, shuffles the bands and stacks and the lines inside each of them, and
+    /// maybe transposes, none of which change whether (or how) the puzzle can be solved
+    pub fn disguise<R: Rng + ?Sized>(&self, rng: &mut R) -> Board {
+        let mut digits: Vec<CellVal> = CellVal::cell_vals().collect();
+        digits.shuffle(rng);
+        let relabel = |val: CellVal| digits[val.into_inner() - 1];
+        let rows = line_order(rng);
+        let columns = line_order(rng);
+        let transpose = rng.gen_bool(0.5);
+
+        let mut board = Board::default();
+        for (r, &from_r) in rows.iter().enumerate() {
+            for (c, &from_c) in columns.iter().enumerate() {
+                let (from_r, from_c) = if transpose {
+                    (from_c, from_r)
+                } else {
+                    (from_r, from_c)
+                };
+                board.0[r * 9 + c] = match &self.0[from_r * 9 + from_c] {
+                    &Cell::Concrete(val) => Cell::Concrete(relabel(val)),
+                    Cell::Possibilities(vals) => {
+                        Cell::Possibilities(vals.iter().map(relabel).collect())
+                    }
+                };
+            }
+        }
+        board
+    }
+}
+
+/// a shuffled order of the 9 lines that keeps each band of 3 together
+fn line_order<R: Rng + ?Sized>(rng: &mut R) -> Vec<usize> {
+    let mut bands = [0, 1, 2];
+    bands.shuffle(rng);
+    bands
+        .into_iter()
+        .flat_map(|band| {
+            let mut lines = [0, 1, 2].map(|line| band * 3 + line);
+            lines.shuffle(rng);
+            lines
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn easy() -> Board {
+        let rows = [
+            "53..7....",
+            "6..195...",
+            ".98....6.",
+            "8...6...3",
+            "4..8.3..1",
+            "7...2...6",
+            ".6....28.",
+            "...419..5",
+            "....8..79",
+        ];
+        Board::build(
+            rows.iter()
+                .map(|row| {
+                    row.chars()
+                        .map(|c| c.to_digit(10).map(|d| d as u8))
+                        .collect()
+                })
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn line_order_keeps_bands_together() {
+        let order = line_order(&mut StdRng::seed_from_u64(7));
+        let mut sorted = order.clone();
+        sorted.sort();
+
+        assert_eq!(sorted, (0..9).collect::<Vec<_>>());
+        for band in order.chunks(3) {
+            assert!(band.iter().all(|line| line / 3 == band[0] / 3));
+        }
+    }
+    #[test]
+    fn disguise_keeps_the_number_of_givens() {
+        let board = easy();
+        let disguised = board.disguise(&mut StdRng::seed_from_u64(1));
+        let count = |board: &Board| board.givens().iter().flatten().filter(|&&g| g).count();
+
+        assert_eq!(count(&disguised), count(&board));
+    }
+    #[test]
+    fn disguised_puzzle_still_solves() {
+        let solution = easy()
+            .disguise(&mut StdRng::seed_from_u64(2))
+            .solve()
+            .unwrap();
+
+        assert!(solution.is_finished());
+    }
+}
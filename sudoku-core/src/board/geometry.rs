@@ -0,0 +1,114 @@
+//! where the cells of each house, window, and diagonal are on a board of any shape
+//!
+//! every module that needs a house or window takes it from here rather than redoing the
+//! `/ BOX_ROWS * BOX_ROWS` arithmetic, so there is one place for it to be wrong and the tests
+//! below check it exhaustively for each size
+use super::Shape;
+
+/// the `(row, column)` of each house's cells, row-major within the house
+pub(crate) fn house_cells<S: Shape>() -> Vec<Vec<(usize, usize)>> {
+    (0..S::SIDE)
+        .map(|house| (0..S::SIDE).map(|i| S::house_cell(house, i)).collect())
+        .collect()
+}
+
+/// the `(row, column)` of each windoku window's cells, row-major within the window
+///
+/// windows are the shape of a house, with a line of cells between them and around the edge of
+/// the board, so the classic board has four with their top left cells one in from its corners
+pub(crate) fn window_cells<S: Shape>() -> Vec<Vec<(usize, usize)>> {
+    // the first row or column of each window, stepping over a house and the line after it
+    let starts = |size: usize| {
+        (1..S::SIDE)
+            .step_by(size + 1)
+            .take_while(move |&start| start + size < S::SIDE)
+    };
+    starts(S::BOX_ROWS)
+        .flat_map(|top| starts(S::BOX_COLUMNS).map(move |left| (top, left)))
+        .map(|(top, left)| {
+            // the window is the same shape as a house, just shifted
+            (0..S::SIDE)
+                .map(|i| S::house_cell(0, i))
+                .map(|(row, column)| (top + row, left + column))
+                .collect()
+        })
+        .collect()
+}
+
+/// the `(row, column)` of each long diagonal's cells from the top row down, the one from the top
+/// left corner first
+pub(crate) fn diagonal_cells<S: Shape>() -> Vec<Vec<(usize, usize)>> {
+    vec![
+        (0..S::SIDE).map(|row| (row, row)).collect(),
+        (0..S::SIDE).map(|row| (row, S::SIDE - 1 - row)).collect(),
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::{CellPos, Classic, Size, Unit};
+    use std::collections::{BTreeMap, BTreeSet};
+
+    /// every unit and region has one of each cell, and every cell is in one row, column, and
+    /// house
+    fn check<S: Shape>() {
+        let regions = window_cells::<S>().into_iter().chain(diagonal_cells::<S>());
+        let regions = regions.map(|cells| {
+            cells
+                .into_iter()
+                .map(|(row, column)| CellPos::<S>::at(row, column).unwrap())
+                .collect::<Vec<_>>()
+        });
+        for cells in Unit::all_of::<S>()
+            .map(|unit| unit.cells_of::<S>().collect::<Vec<_>>())
+            .chain(regions)
+        {
+            let distinct: BTreeSet<_> = cells.iter().collect();
+            assert_eq!(
+                (cells.len(), distinct.len()),
+                (S::SIDE, S::SIDE),
+                "{cells:?}"
+            );
+        }
+        let mut count = BTreeMap::new();
+        for pos in Unit::all_of::<S>().flat_map(Unit::cells_of::<S>) {
+            *count.entry(pos).or_insert(0) += 1;
+        }
+        assert_eq!(count.len(), S::CELLS);
+        assert!(count.values().all(|&units| units == 3));
+        for (house, cells) in house_cells::<S>().iter().enumerate() {
+            for &(row, column) in cells {
+                assert_eq!(S::house_of(row, column), house);
+            }
+        }
+        // the windows don't overlap each other
+        let windows = window_cells::<S>();
+        let windowed: BTreeSet<_> = windows.iter().flatten().collect();
+        assert_eq!(windowed.len(), windows.len() * S::SIDE);
+    }
+
+    #[test]
+    fn every_size_has_consistent_units() {
+        check::<Classic>();
+        check::<Size<2, 2>>();
+        check::<Size<2, 3>>();
+        check::<Size<4, 4>>();
+        check::<Size<1, 5>>();
+    }
+    #[test]
+    fn the_classic_board_has_four_windows() {
+        assert_eq!(house_cells::<Classic>()[3][5], (4, 2));
+        let windows = window_cells::<Classic>();
+        assert_eq!(windows.len(), 4);
+        assert_eq!(windows[0][0], (1, 1));
+        assert_eq!(windows[3][8], (7, 7));
+        assert_eq!(window_cells::<Size<4, 4>>().len(), 9);
+        // a latin square's houses are its rows, which leave no room for windows
+        assert!(window_cells::<Size<1, 5>>().is_empty());
+        // the diagonals only cross in the center
+        let diagonals = diagonal_cells::<Classic>();
+        assert_eq!(diagonals[1][0], (0, 8));
+        assert_eq!(diagonals[0][4], diagonals[1][4]);
+    }
+}
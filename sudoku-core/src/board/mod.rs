@@ -0,0 +1,634 @@
+mod candidates;
+mod canonical;
+mod cell;
+mod cell_set;
+mod disguise;
+pub(crate) mod geometry;
+mod pattern;
+mod size;
+mod solved;
+mod weighted;
+
+use std::{fmt, marker::PhantomData, str::FromStr};
+
+use crate::{errors::catch_internal, BuildError, ParseError, SolveError};
+use rand::{seq::IteratorRandom, RngCore};
+
+/// how many rows and columns the classic board has
+pub(crate) const SIDE: usize = Classic::SIDE;
+
+/// An Index of a board/row/column, below the side of the board
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub(crate) struct Index<S: Shape = Classic>(usize, PhantomData<S>);
+impl<S: Shape> Index<S> {
+    pub fn new(i: usize) -> Result<Self, OutOfRange> {
+        match i < S::SIDE {
+            true => Ok(Index(i, PhantomData)),
+            false => Err(OutOfRange(i)),
+        }
+    }
+    pub fn into_inner(self) -> usize {
+        self.0
+    }
+    pub fn indexes() -> impl Iterator<Item = Self> {
+        (0..S::SIDE).map(|i| Index(i, PhantomData))
+    }
+}
+impl<S: Shape> fmt::Debug for Index<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Index").field(&self.0).finish()
+    }
+}
+
+/// a number that isn't a row, column, or value of the board
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OutOfRange(pub usize);
+
+pub(crate) use candidates::Candidates;
+pub use candidates::{BitSet, CandidateSet, SetBackend};
+pub use canonical::CanonicalForm;
+pub use cell::CellVal;
+pub use cell::Unit;
+pub(crate) use cell::{intersection, units, Cell, Column, House, Row, ToSet};
+pub use pattern::{Density, Symmetry};
+pub use size::{Classic, Shape, Size};
+pub use solved::SolvedBoard;
+pub use weighted::WeightedCell;
+
+/// Represents the board, 9 by 9 unless it has another `Shape`
+///
+/// the cells are kept row by row
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Board<S: Shape = Classic>(Vec<Cell<S>>);
+
+impl<S: Shape> Default for Board<S> {
+    fn default() -> Self {
+        Board(vec![Cell::default(); S::CELLS])
+    }
+}
+impl From<Board> for [[Option<usize>; SIDE]; SIDE] {
+    fn from(value: Board) -> Self {
+        let mut arr: [[Option<usize>; SIDE]; SIDE] = Default::default();
+        for (r, row) in value.values().into_iter().enumerate() {
+            arr[r].copy_from_slice(&row);
+        }
+        arr
+    }
+}
+
+/// rows of givens as the `i64`s `Board::build_wide` takes
+pub(crate) fn widen(lines: Vec<Vec<Option<u8>>>) -> Vec<Vec<Option<i64>>> {
+    lines
+        .into_iter()
+        .map(|row| row.into_iter().map(|val| val.map(i64::from)).collect())
+        .collect()
+}
+
+impl Board {
+    /// builds a board from rows of givens, with `None` for blank cells
+    pub fn build(lines: Vec<Vec<Option<u8>>>) -> Result<Self, SolveError> {
+        Board::build_wide(widen(lines))
+    }
+    /// builds a board from givens read as wider numbers, such as the `u32` or `i64` cells of a
+    /// spreadsheet export, failing with `BuildError::Value` on the first one outside 1-9
+    ///
+    /// see `Board::from_givens` for the other sizes
+    pub fn build_wide(lines: Vec<Vec<Option<i64>>>) -> Result<Self, SolveError> {
+        Board::from_givens(lines)
+    }
+    /// builds a board from rows of each cell's candidates, such as a sukaku's
+    ///
+    /// a cell with one candidate is filled in with it, and one with none fails with
+    /// `BuildError::NoCandidates`
+    pub fn from_candidates(lines: Vec<Vec<Vec<u8>>>) -> Result<Self, SolveError> {
+        Ok(catch_internal(
+            "building the board from candidates",
+            || Self::from_candidates_unchecked(lines),
+        )??)
+    }
+    /// the board as one line of 81 characters row by row, the format most collections of puzzles
+    /// come in, with a digit for each filled in cell and `.` for the rest
+    ///
+    /// `str::parse` reads it back with the same cells filled in
+    pub fn to_line_string(&self) -> String {
+        self.values()
+            .iter()
+            .flatten()
+            .map(|val| val.map_or('.', |val| char::from(b'0' + val as u8)))
+            .collect()
+    }
+}
+
+impl<S: Shape> Board<S> {
+    /// builds a board of any shape from rows of givens, failing on the first one that isn't a
+    /// value of the board with `BuildError::Value` on the classic board and
+    /// `BuildError::OutOfRange` on the others
+    pub fn from_givens(lines: Vec<Vec<Option<i64>>>) -> Result<Self, SolveError<S>> {
+        Ok(catch_internal("building the board", || {
+            Self::build_unchecked(lines)
+        })??)
+    }
+    fn build_unchecked(lines: Vec<Vec<Option<i64>>>) -> Result<Self, BuildError> {
+        let mut board: Board<S> = Default::default();
+        if lines.len() != S::SIDE {
+            Err(BuildError::RowCount)?
+        }
+        for (r, row) in lines.iter().enumerate() {
+            if row.len() != S::SIDE {
+                Err(BuildError::CellCount(r))?
+            }
+            for (c, &cell) in row.iter().enumerate() {
+                let value = cell.unwrap_or_default();
+                board.0[S::index(r, c)] = Cell::new(cell).ok_or(match S::SIDE {
+                    SIDE => BuildError::Value {
+                        row: r,
+                        column: c,
+                        value,
+                    },
+                    side => BuildError::OutOfRange {
+                        row: r,
+                        column: c,
+                        value,
+                        side,
+                    },
+                })?;
+            }
+        }
+        Ok(board)
+    }
+    fn from_candidates_unchecked(lines: Vec<Vec<Vec<u8>>>) -> Result<Self, BuildError> {
+        let mut board: Board<S> = Default::default();
+        if lines.len() != S::SIDE {
+            Err(BuildError::RowCount)?
+        }
+        for (r, row) in lines.iter().enumerate() {
+            if row.len() != S::SIDE {
+                Err(BuildError::CellCount(r))?
+            }
+            for (c, vals) in row.iter().enumerate() {
+                let mut vals = vals
+                    .iter()
+                    .map(|&val| {
+                        CellVal::new(val as usize).map_err(|_| BuildError::Value {
+                            row: r,
+                            column: c,
+                            value: val.into(),
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                vals.sort();
+                vals.dedup();
+                board.0[S::index(r, c)] = match vals[..] {
+                    [] => Err(BuildError::NoCandidates { row: r, column: c })?,
+                    [val] => Cell::Concrete(val),
+                    _ => Cell::Possibilities(vals.into_iter().collect()),
+                };
+            }
+        }
+        Ok(board)
+    }
+    /// the value of each cell row by row, with `None` for the ones without
+    pub fn values(&self) -> Vec<Vec<Option<usize>>> {
+        self.rows()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| match cell {
+                        Cell::Concrete(cell_val) => Some(cell_val.into_inner()),
+                        Cell::Possibilities(_) => None,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+    /// which cells have a concrete value
+    ///
+    /// called before solving this marks the givens, so it can be used to tell them apart from the
+    /// values the solver filled in
+    pub fn givens(&self) -> Vec<Vec<bool>> {
+        self.rows()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| matches!(cell, Cell::Concrete(_)))
+                    .collect()
+            })
+            .collect()
+    }
+    /// empties the cell at `pos`, giving it the candidates its peers allow
+    ///
+    /// peers that lost the cell's value to it get the value back, unless another of their peers
+    /// has it. the board doesn't know which cells were given, so `givens` is the board's `givens`
+    /// from before solving, and a cell it marks, or doesn't reach, is a given that is left alone
+    /// and makes this return false. a cell without a value is left alone too
+    pub fn clear(&mut self, pos: CellPos<S>, givens: &[Vec<bool>]) -> bool {
+        let given = givens.get(pos.row()).and_then(|row| row.get(pos.column()));
+        if given != Some(&false) {
+            return false;
+        }
+        let &Cell::Concrete(old) = self.cell(pos) else {
+            return true;
+        };
+        *self.mut_cell(pos) = Cell::Possibilities(self.allowed(pos));
+        let peers: Vec<_> = self.peers(pos).collect();
+        for peer in peers {
+            let allowed = self.allowed(peer).contains(old);
+            if let Cell::Possibilities(set) = self.mut_cell(peer) {
+                if allowed {
+                    set.insert(old);
+                }
+            }
+        }
+        true
+    }
+    /// the values none of the peers of `pos` have
+    fn allowed(&self, pos: CellPos<S>) -> Candidates<S> {
+        let taken: Vec<CellVal<S>> = self
+            .peers(pos)
+            .filter_map(|peer| match self.cell(peer) {
+                &Cell::Concrete(val) => Some(val),
+                Cell::Possibilities(_) => None,
+            })
+            .collect();
+        CellVal::cell_vals()
+            .filter(|val| !taken.contains(val))
+            .collect()
+    }
+    /// the cells of each row, top to bottom
+    pub(crate) fn rows(&self) -> impl Iterator<Item = &[Cell<S>]> {
+        self.0.chunks(S::SIDE)
+    }
+    /// get the cell at the indicated position
+    pub(crate) fn cell(&self, pos: CellPos<S>) -> &Cell<S> {
+        // won't fail because each Index is below the side of the board
+        &self.0[pos.index()]
+    }
+    pub(crate) fn mut_cell(&mut self, pos: CellPos<S>) -> &mut Cell<S> {
+        &mut self.0[pos.index()]
+    }
+    /// the unsolved cell with the fewest candidates, and its candidates
+    ///
+    /// every solution has one of those values in that cell, so no other cell has to be guessed
+    /// at this depth. taking the cell with the fewest (minimum remaining values) keeps the number
+    /// of branches down. ties go to the first cell in row-major order, or to a random one if
+    /// there is a `ties` rng
+    ///
+    /// the values aren't in any particular order
+    pub(crate) fn branch(
+        &self,
+        ties: Option<&mut dyn RngCore>,
+    ) -> Option<(CellPos<S>, Vec<CellVal<S>>)> {
+        let open: Vec<_> = CellPos::all()
+            .filter_map(|pos| match self.cell(pos) {
+                Cell::Possibilities(set) => Some((pos, set)),
+                Cell::Concrete(_) => None,
+            })
+            .collect();
+        let fewest = open.iter().map(|(_, set)| set.len()).min()?;
+        let mut tied = open.into_iter().filter(|(_, set)| set.len() == fewest);
+        let (pos, set) = match ties {
+            Some(rng) => tied.choose(rng)?,
+            None => tied.next()?,
+        };
+        Some((pos, set.iter().collect()))
+    }
+    pub(crate) fn is_finished(&self) -> bool {
+        self.0.iter().all(|cell| match cell {
+            Cell::Concrete(_) => true,
+            Cell::Possibilities(_) => false,
+        })
+    }
+    /// how many candidates are left, counting a cell with a value as one
+    pub(crate) fn candidate_count(&self) -> usize {
+        self.0
+            .iter()
+            .map(|cell| match cell {
+                Cell::Concrete(_) => 1,
+                Cell::Possibilities(set) => set.len(),
+            })
+            .sum()
+    }
+    /// how many cells have a value
+    pub(crate) fn solved_cells(&self) -> usize {
+        self.0
+            .iter()
+            .filter(|cell| matches!(cell, Cell::Concrete(_)))
+            .count()
+    }
+    /// true if the two cells are different and share a row, column, or house
+    ///
+    /// two cells that see each other can't hold the same value
+    pub(crate) fn sees(&self, a: CellPos<S>, b: CellPos<S>) -> bool {
+        a != b
+            && (Row::containing(a) == Row::containing(b)
+                || Column::containing(a) == Column::containing(b)
+                || House::containing(a) == House::containing(b))
+    }
+    /// every cell that `pos` sees, in order
+    pub(crate) fn peers(&self, pos: CellPos<S>) -> impl Iterator<Item = CellPos<S>> + '_ {
+        CellPos::all().filter(move |&other| self.sees(pos, other))
+    }
+}
+
+impl fmt::Display for Board {
+    /// the board as one line of 81 characters, see `Board::to_line_string`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_line_string())
+    }
+}
+/// reads the one line of 81 characters that `Board::to_line_string` writes, taking `0` as a blank
+/// as well as `.`
+impl FromStr for Board {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, ParseError> {
+        let line = line.trim();
+        let count = line.chars().count();
+        if count != SIDE * SIDE {
+            Err(ParseError::CellCount(count))?
+        }
+        CellPos::all()
+            .zip(line.chars())
+            .map(|(pos, c)| match c {
+                '.' | '0' => Ok((pos, Cell::default())),
+                _ => c
+                    .to_digit(10)
+                    .and_then(|d| CellVal::new(d as usize).ok())
+                    .map(|val| (pos, Cell::Concrete(val)))
+                    .ok_or(ParseError::Character(c)),
+            })
+            .collect()
+    }
+}
+/// where a cell is on the board
+///
+/// positions order row by row, top to bottom and left to right within each row
+#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CellPos<S: Shape = Classic> {
+    row: Index<S>,
+    column: Index<S>,
+}
+impl<S: Shape> fmt::Debug for CellPos<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CellPos")
+            .field(&self.row.into_inner())
+            .field(&self.column.into_inner())
+            .finish()
+    }
+}
+impl<S: Shape> CellPos<S> {
+    pub(crate) fn new(row: Index<S>, column: Index<S>) -> Self {
+        CellPos { row, column }
+    }
+    /// the position at `row` and `column`, or `None` if either is off the board
+    pub fn at(row: usize, column: usize) -> Option<Self> {
+        Some(CellPos::new(
+            Index::new(row).ok()?,
+            Index::new(column).ok()?,
+        ))
+    }
+    /// every position on the board in row-major order, which won't change between versions
+    ///
+    /// so `CellPos::all().nth(i)` is the `i`th cell of an 81 character puzzle string
+    pub fn all() -> impl Iterator<Item = Self> {
+        Index::indexes().flat_map(|row| Index::indexes().map(move |column| CellPos { row, column }))
+    }
+    pub fn row(self) -> usize {
+        self.row.into_inner()
+    }
+    pub fn column(self) -> usize {
+        self.column.into_inner()
+    }
+    /// where this is in `CellPos::all`, from 0 to 80 on the classic board
+    pub fn index(self) -> usize {
+        S::index(self.row(), self.column())
+    }
+    /// the board with this cell set to `num`
+    pub(crate) fn make_concrete_board(self, board: &Board<S>, num: CellVal<S>) -> Board<S> {
+        CellPos::all()
+            .filter_map(|pos| {
+                let cell = if pos == self {
+                    board.cell(pos).make_concrete_cell(num).ok()?
+                } else if pos.row == self.row || pos.column == self.column {
+                    board.cell(pos).remove_possibility(num)
+                } else {
+                    board.cell(pos).clone()
+                };
+                Some((pos, cell))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod macros {
+    use super::Board;
+
+    macro_rules! board {
+        ($rows:tt) => (crate::board::macros::make_board(board!(init $rows)));
+        (init [ $($row:tt)* ]) => (vec![$( board_row!($row) ),*]);
+    }
+    macro_rules! board_row {
+        ([$($cell:tt),*]) => (vec![$( board_cell!($cell) ),*]);
+    }
+    macro_rules! board_cell {
+        (?)=> (crate::board::macros::cell!(? 1, 2, 3, 4, 5, 6, 7, 8, 9));
+        ({ $($possibility:expr),* }) => (crate::board::macros::cell!(? $( $possibility ),*));
+        ($concrete:expr) => (crate::board::macros::cell!( $concrete ));
+    }
+
+    macro_rules! pos {
+        ($row:expr, $column:expr) => {
+            crate::board::CellPos::<crate::board::Classic>::new(
+                crate::board::macros::index!($row),
+                crate::board::macros::index!($column),
+            )
+        };
+        (iter $row:expr, { $( $column:expr ),* }) => {
+            [$(pos!($row, $column)),*].into_iter()
+        };
+        (iter $row:expr, $column:expr) => (std::iter::once(pos!($row, $column)));
+        () => {
+            crate::board::macros::pos!(1, 2)
+        };
+    }
+    pub(crate) use super::cell::macros::{cell, cell_val, index};
+    pub(crate) use {board, board_cell, board_row, pos};
+
+    pub(crate) fn make_board(b: Vec<Vec<super::Cell>>) -> Board {
+        let mut final_board: Board = Default::default();
+
+        for (r, row) in b.into_iter().enumerate().take(9) {
+            for (c, cell) in row.into_iter().enumerate().take(9) {
+                final_board.0[r * 9 + c] = cell;
+            }
+        }
+        final_board
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{macros::*, Board, BuildError, CellPos, Classic, ParseError, SolveError};
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::collections::BTreeSet;
+    use std::str::FromStr;
+
+    #[test]
+    fn peers_are_the_row_column_and_house() {
+        let board = board!([]);
+        let peers: Vec<_> = board.peers(pos!(4, 4)).collect();
+
+        assert_eq!(peers.len(), 20);
+        assert!(peers.contains(&pos!(4, 0)));
+        assert!(peers.contains(&pos!(8, 4)));
+        assert!(peers.contains(&pos!(3, 5)));
+        assert!(!peers.contains(&pos!(4, 4)));
+        assert!(!peers.contains(&pos!(2, 2)));
+    }
+
+    #[test]
+    fn branch_picks_the_fewest_candidates() {
+        let board = board!([[1, ?, { 2, 3 }, 4] [?, { 3, 5, 6 }]]);
+        let (cell, mut vals) = board.branch(None).unwrap();
+        vals.sort();
+
+        assert_eq!(cell, pos!(0, 2));
+        assert_eq!(vals, [cell_val!(2), cell_val!(3)]);
+        assert_eq!(
+            cell.make_concrete_board(&board, vals[0]).cell(pos!(0, 2)),
+            &cell!(2)
+        );
+    }
+    #[test]
+    fn branch_can_break_ties_at_random() {
+        let board = board!([[1, { 2, 3 }, { 2, 3 }, { 2, 3 }]]);
+        let picks: BTreeSet<_> = (0..20)
+            .filter_map(|seed| board.branch(Some(&mut StdRng::seed_from_u64(seed))))
+            .map(|(cell, _)| cell)
+            .collect();
+
+        assert_eq!(board.branch(None).unwrap().0, pos!(0, 1));
+        assert_eq!(picks, [pos!(0, 1), pos!(0, 2), pos!(0, 3)].into());
+    }
+    #[test]
+    fn clear_recomputes_candidates_from_peers() {
+        let mut board = board!([[1, 2, 3] [{ 5, 6 }, 4]]);
+        assert!(board.clear(pos!(1, 1), &vec![vec![false; 9]; 9]));
+
+        assert_eq!(board.cell(pos!(1, 1)), &cell!(? 4, 5, 6, 7, 8, 9));
+        // 4 comes back, but nothing else that was ruled out does
+        assert_eq!(board.cell(pos!(1, 0)), &cell!(? 4, 5, 6));
+    }
+    #[test]
+    fn clear_leaves_values_other_peers_rule_out() {
+        let mut board = board!([[1, ?, { 5, 6 }, 4] [?, ?, 4] [?, ?, { 5, 6 }]]);
+        assert!(board.clear(pos!(1, 2), &vec![vec![false; 9]; 9]));
+
+        assert_eq!(board.cell(pos!(1, 2)), &cell!(? 2, 3, 4, 5, 6, 7, 8, 9));
+        // the 4 in the first row still rules it out of (0, 2)
+        assert_eq!(board.cell(pos!(0, 2)), &cell!(? 5, 6));
+        assert_eq!(board.cell(pos!(2, 2)), &cell!(? 4, 5, 6));
+    }
+    #[test]
+    fn clear_leaves_givens_alone() {
+        let mut board = board!([[1, 2, 3]]);
+        let givens = board.givens();
+        *board.mut_cell(pos!(1, 1)) = cell!(4);
+        let solved = board.clone();
+
+        assert!(!board.clear(pos!(0, 0), &givens));
+        // a cell the mask doesn't reach counts as a given
+        assert!(!board.clear(pos!(1, 1), &givens[..1]));
+        assert_eq!(board, solved);
+        assert!(board.clear(pos!(1, 1), &givens));
+        assert_eq!(board.cell(pos!(1, 1)), &cell!(? 4, 5, 6, 7, 8, 9));
+        // an empty cell has nothing to clear
+        assert!(board.clear(pos!(1, 1), &givens));
+        assert_eq!(board.cell(pos!(1, 1)), &cell!(? 4, 5, 6, 7, 8, 9));
+        assert_eq!(CellPos::<Classic>::at(9, 0), None);
+    }
+    #[test]
+    fn build_names_the_cell_with_a_bad_value() {
+        let mut lines = vec![vec![None; 9]; 9];
+        lines[2][4] = Some(300);
+        let why = Board::build_wide(lines).unwrap_err();
+
+        assert_eq!(
+            why,
+            SolveError::Build(BuildError::Value {
+                row: 2,
+                column: 4,
+                value: 300
+            })
+        );
+        assert_eq!(
+            why.to_string(),
+            "300 in row 3, column 5 is not a valid cell value (1-9)"
+        );
+        let mut lines = vec![vec![None; 9]; 9];
+        lines[0][0] = Some(0);
+        assert!(matches!(
+            Board::build(lines),
+            Err(SolveError::Build(BuildError::Value { value: 0, .. }))
+        ));
+    }
+    #[test]
+    fn line_strings_round_trip() {
+        let line =
+            "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+        let board: Board = line.parse().unwrap();
+
+        assert_eq!(board.to_line_string(), line);
+        assert_eq!(board.to_string(), line);
+        assert_eq!(
+            board.givens(),
+            Board::from_str(&board.to_string()).unwrap().givens()
+        );
+        assert_eq!(line.replace('.', "0").parse(), Ok(board));
+    }
+    #[test]
+    fn line_strings_need_81_digits() {
+        assert_eq!("123".parse::<Board>(), Err(ParseError::CellCount(3)));
+        assert_eq!(
+            format!("x{}", ".".repeat(80)).parse::<Board>(),
+            Err(ParseError::Character('x'))
+        );
+    }
+    #[test]
+    fn from_candidates_fills_in_single_candidates() {
+        let mut lines = vec![vec![(1..=9).collect::<Vec<u8>>(); 9]; 9];
+        lines[0][0] = vec![3, 1, 3];
+        lines[0][1] = vec![7];
+        let board = Board::from_candidates(lines.clone()).unwrap();
+
+        assert_eq!(board.cell(pos!(0, 0)), &cell!(? 1, 3));
+        assert_eq!(board.cell(pos!(0, 1)), &cell!(7));
+        assert_eq!(board.cell(pos!(0, 2)), &cell!(? 1, 2, 3, 4, 5, 6, 7, 8, 9));
+
+        lines[4][5] = vec![];
+        assert_eq!(
+            Board::from_candidates(lines),
+            Err(SolveError::Build(BuildError::NoCandidates {
+                row: 4,
+                column: 5
+            }))
+        );
+    }
+    #[test]
+    fn givens_marks_concrete_cells() {
+        let board = board!([[1, ?, { 2, 3 }, 4]]);
+        let givens = board.givens();
+
+        assert_eq!(givens[0][..5], [true, false, false, true, false]);
+        assert!(givens[1..].iter().flatten().all(|&given| !given));
+    }
+    #[test]
+    fn cell_pos_all_is_row_major() {
+        let all: Vec<_> = CellPos::all().collect();
+
+        assert_eq!(all.len(), 81);
+        assert_eq!(all[10], pos!(1, 1));
+        assert!(all.iter().enumerate().all(|(i, pos)| pos.index() == i));
+        assert_eq!(CellPos::at(8, 2), Some(pos!(8, 2)));
+        assert_eq!(CellPos::<Classic>::at(9, 0), None);
+    }
+}
@@ -0,0 +1,125 @@
+//! the shape of where a puzzle's givens are, ignoring their values
+use super::Board;
+use std::fmt;
+
+/// a way the pattern of givens maps onto itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Symmetry {
+    /// the same after a half turn, which is what most published puzzles use
+    Rotational180,
+    /// the same after a quarter turn
+    Rotational90,
+    /// the same flipped top to bottom
+    Horizontal,
+    /// the same flipped left to right
+    Vertical,
+    /// the same flipped over the diagonal from the top left
+    Diagonal,
+    /// the same flipped over the diagonal from the top right
+    AntiDiagonal,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 6] = [
+        Symmetry::Rotational180,
+        Symmetry::Rotational90,
+        Symmetry::Horizontal,
+        Symmetry::Vertical,
+        Symmetry::Diagonal,
+        Symmetry::AntiDiagonal,
+    ];
+
+    /// where the cell at `row` and `column` goes
+    fn map(self, row: usize, column: usize) -> (usize, usize) {
+        match self {
+            Symmetry::Rotational180 => (8 - row, 8 - column),
+            Symmetry::Rotational90 => (column, 8 - row),
+            Symmetry::Horizontal => (8 - row, column),
+            Symmetry::Vertical => (row, 8 - column),
+            Symmetry::Diagonal => (column, row),
+            Symmetry::AntiDiagonal => (8 - column, 8 - row),
+        }
+    }
+}
+impl fmt::Display for Symmetry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Symmetry::Rotational180 => "rotational (half turn)",
+            Symmetry::Rotational90 => "rotational (quarter turn)",
+            Symmetry::Horizontal => "mirror (top to bottom)",
+            Symmetry::Vertical => "mirror (left to right)",
+            Symmetry::Diagonal => "mirror (diagonal)",
+            Symmetry::AntiDiagonal => "mirror (anti-diagonal)",
+        })
+    }
+}
+
+/// how many givens are in each house, band, and stack
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Density {
+    /// by band, then by stack, so `houses[0][2]` is the top right house
+    pub houses: [[usize; 3]; 3],
+    /// the rows of houses, top to bottom
+    pub bands: [usize; 3],
+    /// the columns of houses, left to right
+    pub stacks: [usize; 3],
+}
+
+impl Board {
+    /// every symmetry the pattern of concrete cells has, in the order of `Symmetry::ALL`
+    ///
+    /// only where the givens are matters, not their values. an empty list means there is none
+    pub fn given_symmetries(&self) -> Vec<Symmetry> {
+        let givens = self.givens();
+        Symmetry::ALL
+            .into_iter()
+            .filter(|symmetry| {
+                (0..81).all(|i| {
+                    let (row, column) = symmetry.map(i / 9, i % 9);
+                    givens[i / 9][i % 9] == givens[row][column]
+                })
+            })
+            .collect()
+    }
+    /// how the concrete cells are spread over the houses, bands, and stacks
+    pub fn given_density(&self) -> Density {
+        let mut density = Density::default();
+        for (row, givens) in self.givens().iter().enumerate() {
+            for (column, &given) in givens.iter().enumerate() {
+                if given {
+                    density.houses[row / 3][column / 3] += 1;
+                    density.bands[row / 3] += 1;
+                    density.stacks[column / 3] += 1;
+                }
+            }
+        }
+        density
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn given_symmetries_finds_half_turns() {
+        // off the diagonals, so a half turn is the only match
+        let board = board!([[?, 1] [] [] [] [] [] [] [] [?, ?, ?, ?, ?, ?, ?, 2]]);
+
+        assert_eq!(board.given_symmetries(), [Symmetry::Rotational180]);
+    }
+    #[test]
+    fn given_symmetries_can_be_empty() {
+        assert!(board!([[1, 2]]).given_symmetries().is_empty());
+        assert_eq!(board!([]).given_symmetries(), Symmetry::ALL);
+    }
+    #[test]
+    fn given_density_counts_by_house() {
+        let density = board!([[1, ?, ?, 2] [] [] [?, ?, ?, ?, ?, ?, ?, ?, 3]]).given_density();
+
+        assert_eq!(density.houses, [[1, 1, 0], [0, 0, 1], [0, 0, 0]]);
+        assert_eq!(density.bands, [2, 1, 0]);
+        assert_eq!(density.stacks, [1, 1, 1]);
+    }
+}
@@ -0,0 +1,116 @@
+//! the geometry of a board with boxes of any shape
+//!
+//! `Board` and everything it is made of are generic over a `Shape`, which is a `Size`, with
+//! `Classic` as the default. `Size<2, 2>` is the 4x4 mini sudoku, `Size<2, 3>` the 6x6 one with
+//! houses two rows tall, and `Size<4, 4>` the 16x16 hexadoku. a Latin square of side `n` is
+//! `Size<1, n>`, since houses one row tall are just the rows again
+use std::{
+    fmt,
+    hash::Hash,
+    panic::{RefUnwindSafe, UnwindSafe},
+};
+
+/// a board whose houses are `BOX_ROWS` rows by `BOX_COLUMNS` columns
+///
+/// there are as many houses as cells in a house, so the board is `SIDE` cells across and down
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Size<const BOX_ROWS: usize, const BOX_COLUMNS: usize>;
+
+/// the classic 9x9 board, with 3x3 houses
+pub type Classic = Size<3, 3>;
+
+/// the dimensions a `Board` is generic over, which only `Size` has
+///
+/// every row, column, and house is numbered the same way whatever the shape, so code written
+/// against a `Shape` works for all of them
+pub trait Shape:
+    fmt::Debug
+    + Clone
+    + Copy
+    + Default
+    + PartialEq
+    + Eq
+    + PartialOrd
+    + Ord
+    + Hash
+    + Send
+    + Sync
+    + UnwindSafe
+    + RefUnwindSafe
+    + 'static
+{
+    const BOX_ROWS: usize;
+    const BOX_COLUMNS: usize;
+    /// how many rows, columns, and houses there are, how many cells each has, and the largest
+    /// value
+    const SIDE: usize = Self::BOX_ROWS * Self::BOX_COLUMNS;
+    /// how many cells the board has
+    const CELLS: usize = Self::SIDE * Self::SIDE;
+
+    /// the house the cell at `row` and `column` is in
+    ///
+    /// houses are numbered left to right, top to bottom, so 4 is the center of the classic board
+    fn house_of(row: usize, column: usize) -> usize {
+        // a band of houses is BOX_ROWS rows tall and holds SIDE / BOX_COLUMNS = BOX_ROWS houses
+        row / Self::BOX_ROWS * Self::BOX_ROWS + column / Self::BOX_COLUMNS
+    }
+    /// the `(row, column)` of the `i`th cell of `house`, counting row-major within the house
+    fn house_cell(house: usize, i: usize) -> (usize, usize) {
+        (
+            house / Self::BOX_ROWS * Self::BOX_ROWS + i / Self::BOX_COLUMNS,
+            house % Self::BOX_ROWS * Self::BOX_COLUMNS + i % Self::BOX_COLUMNS,
+        )
+    }
+    /// where the cell at `row` and `column` is in row-major order
+    fn index(row: usize, column: usize) -> usize {
+        row * Self::SIDE + column
+    }
+    /// every `(row, column)` in row-major order
+    fn cells() -> impl Iterator<Item = (usize, usize)> {
+        (0..Self::CELLS).map(|i| (i / Self::SIDE, i % Self::SIDE))
+    }
+}
+
+impl<const BOX_ROWS: usize, const BOX_COLUMNS: usize> Shape for Size<BOX_ROWS, BOX_COLUMNS> {
+    const BOX_ROWS: usize = BOX_ROWS;
+    const BOX_COLUMNS: usize = BOX_COLUMNS;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn classic_houses_are_three_by_three() {
+        assert_eq!((Classic::SIDE, Classic::CELLS), (9, 81));
+        assert_eq!(Classic::house_of(4, 2), 3);
+        assert_eq!(Classic::house_cell(3, 5), (4, 2));
+        assert_eq!(Classic::index(8, 8), 80);
+    }
+    #[test]
+    fn other_sizes_share_the_numbering() {
+        type Six = Size<2, 3>;
+        type Sixteen = Size<4, 4>;
+
+        assert_eq!(Six::SIDE, 6);
+        // the houses of a 6x6 board are two across and three down
+        assert_eq!(Six::house_of(1, 3), 1);
+        assert_eq!(Six::house_of(2, 0), 2);
+        assert_eq!(Six::house_cell(5, 5), (5, 5));
+        assert_eq!(Sixteen::house_of(15, 15), 15);
+        for house in 0..Sixteen::SIDE {
+            for i in 0..Sixteen::SIDE {
+                let (row, column) = Sixteen::house_cell(house, i);
+                assert_eq!(Sixteen::house_of(row, column), house);
+            }
+        }
+        assert_eq!(Sixteen::cells().count(), 256);
+    }
+    #[test]
+    fn latin_squares_have_a_house_for_each_row() {
+        type Latin = Size<1, 5>;
+
+        assert_eq!(Latin::SIDE, 5);
+        assert!((0..5).all(|row| (0..5).all(|column| Latin::house_of(row, column) == row)));
+    }
+}
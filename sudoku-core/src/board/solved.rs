@@ -0,0 +1,109 @@
+use super::{Board, Cell, Classic, Shape, SIDE};
+use crate::UpdateError;
+
+/// a board with a value in every cell
+///
+/// only a `SolvedBoard` can be turned into plain values, so a partial board can't be passed off
+/// as a solution by mistake
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SolvedBoard<S: Shape = Classic>(Board<S>);
+
+impl<S: Shape> SolvedBoard<S> {
+    pub fn board(&self) -> &Board<S> {
+        &self.0
+    }
+    pub fn into_board(self) -> Board<S> {
+        self.0
+    }
+    /// the value of each cell, row by row
+    pub fn values(&self) -> Vec<Vec<usize>> {
+        self.0
+            .values()
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|val| val.expect("a solved board has no candidates left"))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl<S: Shape> TryFrom<Board<S>> for SolvedBoard<S> {
+    type Error = UpdateError;
+    /// fails with `UpdateError::Incomplete` if any cell still has candidates
+    fn try_from(board: Board<S>) -> Result<Self, Self::Error> {
+        if board.is_finished() {
+            Ok(Self(board))
+        } else {
+            Err(UpdateError::Incomplete)
+        }
+    }
+}
+
+impl From<SolvedBoard> for [[usize; SIDE]; SIDE] {
+    fn from(solved: SolvedBoard) -> Self {
+        let mut arr = [[0; SIDE]; SIDE];
+        for (r, row) in solved.values().into_iter().enumerate() {
+            arr[r].copy_from_slice(&row);
+        }
+        arr
+    }
+}
+
+impl<S: Shape> Board<S> {
+    /// true if every cell has a value
+    pub fn is_solved(&self) -> bool {
+        self.is_finished()
+    }
+    /// the values each cell could still be, in order
+    ///
+    /// a cell with a value only has that value
+    pub fn candidates(&self) -> Vec<Vec<Vec<usize>>> {
+        self.rows()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| match cell {
+                        Cell::Concrete(val) => vec![val.into_inner()],
+                        Cell::Possibilities(set) => {
+                            let mut vals: Vec<_> = set.iter().map(|val| val.into_inner()).collect();
+                            vals.sort();
+                            vals
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn solved_board_rejects_partial_boards() {
+        assert_eq!(
+            SolvedBoard::try_from(board!([[1]])),
+            Err(UpdateError::Incomplete)
+        );
+    }
+    #[test]
+    fn solved_board_gives_its_values() {
+        let solution = board!([]).solve().unwrap();
+        let values: [[usize; 9]; 9] = SolvedBoard::try_from(solution).unwrap().into();
+
+        assert!(values.iter().all(|row| {
+            let mut row = row.to_vec();
+            row.sort();
+            row == (1..=9).collect::<Vec<_>>()
+        }));
+    }
+    #[test]
+    fn candidates_are_sorted() {
+        let board = board!([[1, { 3, 2 }]]);
+
+        assert_eq!(board.candidates()[0][..2], [vec![1], vec![2, 3]]);
+    }
+}
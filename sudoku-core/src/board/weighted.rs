@@ -0,0 +1,95 @@
+use super::{units, Board, Classic};
+use crate::SolveError;
+
+/// a given with how sure the reader was of it, from 0 to 1
+pub type WeightedCell = Option<(u8, f32)>;
+
+impl Board {
+    /// builds a board from givens that might be wrong, such as digits read from a photo
+    ///
+    /// givens less certain than `min_confidence` are left blank. while two givens in a row,
+    /// column, or house clash, the less certain of them is dropped. returns the board along with
+    /// the `(row, column)` of every given dropped to settle a clash
+    pub fn build_weighted(
+        lines: Vec<Vec<WeightedCell>>,
+        min_confidence: f32,
+    ) -> Result<(Self, Vec<(usize, usize)>), SolveError> {
+        let mut lines: Vec<Vec<WeightedCell>> = lines
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|cell| cell.filter(|&(_, confidence)| confidence >= min_confidence))
+                    .collect()
+            })
+            .collect();
+        let mut dropped = vec![];
+        while let Some((r, c)) = least_certain_clash(&lines) {
+            lines[r][c] = None;
+            dropped.push((r, c));
+        }
+        let givens = lines
+            .into_iter()
+            .map(|row| row.into_iter().map(|cell| Some(cell?.0)).collect())
+            .collect();
+        Ok((Self::build(givens)?, dropped))
+    }
+}
+
+/// the least certain of all the givens that share a unit with an equal given
+fn least_certain_clash(lines: &[Vec<WeightedCell>]) -> Option<(usize, usize)> {
+    let at = |r: usize, c: usize| *lines.get(r)?.get(c)?;
+    units::<Classic>()
+        .flat_map(|unit| {
+            let givens: Vec<_> = unit
+                .into_iter()
+                .map(|pos| (pos.row.into_inner(), pos.column.into_inner()))
+                .filter_map(|(r, c)| Some(((r, c), at(r, c)?)))
+                .collect();
+            givens
+                .iter()
+                .filter(|(pos, (val, _))| {
+                    givens
+                        .iter()
+                        .any(|(other, (other_val, _))| other != pos && other_val == val)
+                })
+                .copied()
+                .collect::<Vec<_>>()
+        })
+        .min_by(|(_, (_, a)), (_, (_, b))| a.total_cmp(b))
+        .map(|(pos, _)| pos)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn empty() -> Vec<Vec<WeightedCell>> {
+        vec![vec![None; 9]; 9]
+    }
+
+    #[test]
+    fn build_weighted_leaves_unsure_cells_blank() {
+        let mut lines = empty();
+        lines[0][0] = Some((1, 0.9));
+        lines[0][1] = Some((2, 0.2));
+        let (board, dropped) = Board::build_weighted(lines, 0.5).unwrap();
+
+        assert_eq!(board.givens()[0][..2], [true, false]);
+        assert!(dropped.is_empty());
+    }
+    #[test]
+    fn build_weighted_drops_the_less_certain_clash() {
+        let mut lines = empty();
+        lines[0][0] = Some((1, 0.9));
+        lines[0][8] = Some((1, 0.6));
+        lines[4][0] = Some((1, 0.7));
+        let (board, dropped) = Board::build_weighted(lines, 0.5).unwrap();
+
+        assert_eq!(dropped, vec![(0, 8), (4, 0)]);
+        assert_eq!(board.givens().iter().flatten().filter(|&&g| g).count(), 1);
+    }
+    #[test]
+    fn build_weighted_still_checks_the_shape() {
+        assert!(Board::build_weighted(vec![vec![None; 9]; 8], 0.5).is_err());
+    }
+}
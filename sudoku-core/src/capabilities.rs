@@ -0,0 +1,158 @@
+//! a report of what this build can do, for front-ends that may talk to more than one version
+//!
+//! each part of the crate lists what it provides (`io::formats`, `SolverBackend::ALL`,
+//! `strategy_names`), and `Capabilities::current` gathers them up. a binary built on top adds its
+//! own formats and features before reporting
+
+use crate::{io, strategy_names, SolverBackend};
+
+/// bumped whenever a key is removed or changes meaning in `Capabilities::to_json`
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// the kinds of puzzle the solver understands
+pub const VARIANTS: &[&str] = &[
+    "classic",
+    "latin",
+    "windoku",
+    "diagonal",
+    "killer",
+    "anti-knight",
+    "non-consecutive",
+    "arrow",
+    "sandwich",
+    "greater-than",
+    "even-odd",
+];
+
+/// a file or text format and which ways it can go
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Format {
+    pub name: &'static str,
+    pub read: bool,
+    pub write: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    /// the version of `sudoku-core`
+    pub version: &'static str,
+    /// cargo features the build was made with
+    pub features: Vec<&'static str>,
+    pub formats: Vec<Format>,
+    /// see `SolverBackend`
+    pub engines: Vec<&'static str>,
+    /// see `strategy_names`
+    pub strategies: Vec<&'static str>,
+    /// see `VARIANTS`
+    pub variants: Vec<&'static str>,
+}
+
+impl Capabilities {
+    /// everything the library itself provides
+    pub fn current() -> Self {
+        Capabilities {
+            version: env!("CARGO_PKG_VERSION"),
+            features: vec![],
+            formats: io::formats(),
+            engines: SolverBackend::ALL.map(SolverBackend::name).to_vec(),
+            strategies: strategy_names(),
+            variants: VARIANTS.to_vec(),
+        }
+    }
+    pub fn with_format(mut self, format: Format) -> Self {
+        self.formats.push(format);
+        self
+    }
+    pub fn with_feature(mut self, feature: &'static str) -> Self {
+        self.features.push(feature);
+        self
+    }
+    /// the report as a single line of JSON
+    ///
+    /// the keys always come out in the same order, and new keys are only ever added
+    pub fn to_json(&self) -> String {
+        let list = |items: &[&str]| {
+            let items: Vec<_> = items.iter().map(|item| json_string(item)).collect();
+            format!("[{}]", items.join(","))
+        };
+        let formats: Vec<_> = self
+            .formats
+            .iter()
+            .map(|Format { name, read, write }| {
+                format!(
+                    "{{\"name\":{},\"read\":{read},\"write\":{write}}}",
+                    json_string(name)
+                )
+            })
+            .collect();
+        format!(
+            "{{\"schema\":{SCHEMA_VERSION},\"version\":{},\"features\":{},\"formats\":[{}],\"engines\":{},\"strategies\":{},\"variants\":{}}}",
+            json_string(self.version),
+            list(&self.features),
+            formats.join(","),
+            list(&self.engines),
+            list(&self.strategies),
+            list(&self.variants),
+        )
+    }
+}
+
+/// `text` quoted as a JSON string
+pub(crate) fn json_string(text: &str) -> String {
+    let mut out = "\"".to_string();
+    for c in text.chars() {
+        match c {
+            '"' => out += "\\\"",
+            '\\' => out += "\\\\",
+            c if c.is_control() => out += &format!("\\u{:04x}", c as u32),
+            c => out.push(c),
+        }
+    }
+    out + "\""
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn current_lists_every_engine_and_strategy() {
+        let capabilities = Capabilities::current();
+
+        assert_eq!(capabilities.engines, vec!["backtracking", "dlx", "anneal"]);
+        assert_eq!(capabilities.strategies, strategy_names());
+        assert!(capabilities
+            .formats
+            .iter()
+            .any(|format| format.name == "pencilmarks"));
+    }
+    #[test]
+    fn to_json_keeps_its_keys_in_order() {
+        let json = Capabilities {
+            version: "1.0.0",
+            features: vec!["count-allocs"],
+            formats: vec![Format {
+                name: "csv",
+                read: true,
+                write: false,
+            }],
+            engines: vec!["dlx"],
+            strategies: vec!["bug+1"],
+            variants: vec![],
+        }
+        .to_json();
+
+        assert_eq!(
+            json,
+            concat!(
+                "{\"schema\":1,\"version\":\"1.0.0\",\"features\":[\"count-allocs\"],",
+                "\"formats\":[{\"name\":\"csv\",\"read\":true,\"write\":false}],",
+                "\"engines\":[\"dlx\"],\"strategies\":[\"bug+1\"],\"variants\":[]}"
+            )
+        );
+    }
+    #[test]
+    fn json_string_escapes_quotes() {
+        assert_eq!(json_string("a \"b\"\n"), "\"a \\\"b\\\"\\u000a\"");
+    }
+}
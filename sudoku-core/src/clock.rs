@@ -0,0 +1,153 @@
+//! where the solver gets the time from
+//!
+//! `std::time::Instant` panics in the browser, so everything that measures time goes through a
+//! `Clock` instead: `SystemClock` natively, `PerformanceClock` (`performance.now()`) on wasm, and
+//! `MockClock` in tests
+
+use std::{
+    fmt,
+    panic::RefUnwindSafe,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, OnceLock,
+    },
+    time::Duration,
+};
+
+/// a monotonic source of time
+///
+/// only the difference between two readings means anything. clocks have to be unwind safe so a
+/// solve can still catch internal panics
+pub trait Clock: Send + Sync + RefUnwindSafe {
+    /// the time since some fixed point, which never goes backwards
+    fn now(&self) -> Duration;
+}
+
+/// `std::time::Instant`, measured from when the clock was made
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock(std::time::Instant);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self(std::time::Instant::now())
+    }
+}
+#[cfg(not(target_arch = "wasm32"))]
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.0.elapsed()
+    }
+}
+
+/// the browser's `performance.now()`, measured from when the page loaded
+#[cfg(target_arch = "wasm32")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PerformanceClock;
+
+#[cfg(target_arch = "wasm32")]
+impl Clock for PerformanceClock {
+    fn now(&self) -> Duration {
+        let millis = web_sys::window()
+            .and_then(|window| window.performance())
+            .map_or(0.0, |performance| performance.now());
+        Duration::from_secs_f64(millis / 1000.0)
+    }
+}
+
+/// the clock used when none is given
+#[cfg(not(target_arch = "wasm32"))]
+pub type DefaultClock = SystemClock;
+/// the clock used when none is given
+#[cfg(target_arch = "wasm32")]
+pub type DefaultClock = PerformanceClock;
+
+/// a clock that only moves when it is told to
+///
+/// clones share the same time, so a test can keep one and hand the other to the solver
+#[derive(Debug, Clone, Default)]
+pub struct MockClock(Arc<AtomicU64>);
+
+impl MockClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// moves the clock forward by `by`
+    pub fn advance(&self, by: Duration) {
+        self.0.fetch_add(by.as_nanos() as u64, Ordering::SeqCst);
+    }
+}
+impl Clock for MockClock {
+    fn now(&self) -> Duration {
+        Duration::from_nanos(self.0.load(Ordering::SeqCst))
+    }
+}
+
+/// a clock that can be stored in `SolveOptions`
+///
+/// two shared clocks are equal if they are the same clock, and every default is the same
+/// `DefaultClock`
+#[derive(Clone)]
+pub struct SharedClock(Arc<dyn Clock>);
+
+impl SharedClock {
+    pub fn new(clock: impl Clock + 'static) -> Self {
+        Self(Arc::new(clock))
+    }
+}
+impl Clock for SharedClock {
+    fn now(&self) -> Duration {
+        self.0.now()
+    }
+}
+impl Default for SharedClock {
+    fn default() -> Self {
+        static DEFAULT: OnceLock<SharedClock> = OnceLock::new();
+        DEFAULT
+            .get_or_init(|| Self::new(DefaultClock::default()))
+            .clone()
+    }
+}
+impl fmt::Debug for SharedClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedClock")
+    }
+}
+impl PartialEq for SharedClock {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+impl Eq for SharedClock {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn mock_clock_only_moves_when_advanced() {
+        let clock = MockClock::new();
+        let shared = SharedClock::new(clock.clone());
+
+        assert_eq!(shared.now(), Duration::ZERO);
+        clock.advance(Duration::from_millis(5));
+        assert_eq!(shared.now(), Duration::from_millis(5));
+    }
+    #[test]
+    fn shared_clocks_are_equal_to_their_clones() {
+        let clock = SharedClock::new(MockClock::new());
+
+        assert_eq!(clock, clock.clone());
+        assert_ne!(clock, SharedClock::new(MockClock::new()));
+        assert_eq!(SharedClock::default(), SharedClock::default());
+    }
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn system_clock_never_goes_backwards() {
+        let clock = SystemClock::default();
+        let before = clock.now();
+
+        assert!(clock.now() >= before);
+    }
+}
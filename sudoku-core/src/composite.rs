@@ -0,0 +1,290 @@
+//! puzzles made of several classic boards that share cells, such as a samurai's five boards
+//! overlapping at the corners of the middle one
+//!
+//! each board is placed at a row and column offset on one larger sheet, and boards that land on
+//! the same cell of the sheet share it. each board is propagated on its own with the classic
+//! rules, then every shared cell keeps only the candidates all of its boards still allow, and
+//! that repeats until nothing changes. the search guesses the cell with the fewest candidates on
+//! any board, like the backtracking backend does on one
+//!
+//! with the `schema` feature, a layout can also be read from the `boards` of a puzzle document,
+//! see `schema::v2`
+use crate::{
+    board::{Cell, CellPos},
+    errors::catch_internal,
+    Board, BuildError, CellVal, SolveError, UpdateError,
+};
+use std::{collections::BTreeMap, panic::AssertUnwindSafe};
+
+/// classic boards placed on one sheet, sharing the cells where they overlap
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Composite {
+    /// each board's top left cell on the sheet, with the board
+    boards: Vec<((usize, usize), Board)>,
+}
+
+impl Composite {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// the five boards of a samurai, top left, top right, middle, bottom left, then bottom right
+    ///
+    /// the middle board shares a corner house with each of the others
+    pub fn samurai(boards: [Board; 5]) -> Result<Self, BuildError> {
+        let offsets = [(0, 0), (0, 12), (6, 6), (12, 0), (12, 12)];
+        offsets
+            .into_iter()
+            .zip(boards)
+            .try_fold(Composite::new(), |composite, ((row, column), board)| {
+                composite.with_board(row, column, board)
+            })
+    }
+    /// adds `board` with its top left cell at `row` and `column` of the sheet
+    ///
+    /// fails with `BuildError::Overlap` if it fills in a shared cell differently from a board
+    /// already there
+    pub fn with_board(
+        mut self,
+        row: usize,
+        column: usize,
+        board: Board,
+    ) -> Result<Self, BuildError> {
+        self.boards.push(((row, column), board));
+        for shared in self.shared() {
+            let mut values = shared
+                .iter()
+                .filter_map(|&(i, pos)| match self.cell(i, pos) {
+                    &Cell::Concrete(val) => Some(val),
+                    Cell::Possibilities(_) => None,
+                });
+            if let Some(first) = values.next() {
+                if values.any(|val| val != first) {
+                    let (i, pos) = shared[0];
+                    let ((top, left), _) = self.boards[i];
+                    Err(BuildError::Overlap {
+                        row: top + pos.row(),
+                        column: left + pos.column(),
+                    })?
+                }
+            }
+        }
+        Ok(self)
+    }
+    /// each board with its top left cell on the sheet, in the order they were added
+    pub fn boards(&self) -> impl Iterator<Item = ((usize, usize), &Board)> {
+        self.boards.iter().map(|(offset, board)| (*offset, board))
+    }
+    pub fn is_finished(&self) -> bool {
+        self.boards.iter().all(|(_, board)| board.is_finished())
+    }
+    /// removes the candidates each board's units rule out and keeps shared cells in agreement
+    /// across boards, until nothing changes
+    pub fn propagate(&mut self) -> Result<(), UpdateError> {
+        let shared = self.shared();
+        loop {
+            let before = self.clone();
+            for (_, board) in &mut self.boards {
+                let propagation = board.clone().propagate();
+                if let Some(why) = propagation.contradiction {
+                    Err(why)?
+                }
+                *board = propagation.board;
+            }
+            for cells in &shared {
+                self.agree(cells)?;
+            }
+            if *self == before {
+                return Ok(());
+            }
+        }
+    }
+    /// every board filled in, agreeing on every shared cell
+    ///
+    /// the first solution found is returned, whether or not it is the only one
+    pub fn solve(self) -> Result<Composite, SolveError> {
+        catch_internal("solving a composite", AssertUnwindSafe(|| self.search()))?
+    }
+    /// each guess pushes a frame on a heap allocated stack instead of recurring, so deep
+    /// searches can't overflow the call stack
+    fn search(mut self) -> Result<Composite, SolveError> {
+        self.propagate()?;
+        if self.is_finished() {
+            return Ok(self);
+        }
+        let mut stack = vec![Frame::new(self)?];
+        while let Some(frame) = stack.last_mut() {
+            let Some(mut guess) = frame.next() else {
+                stack.pop();
+                continue;
+            };
+            if guess.propagate().is_err() {
+                continue;
+            }
+            if guess.is_finished() {
+                return Ok(guess);
+            }
+            stack.extend(Frame::new(guess).ok());
+        }
+        Err(UpdateError::Impossible.into())
+    }
+    fn cell(&self, i: usize, pos: CellPos) -> &Cell {
+        self.boards[i].1.cell(pos)
+    }
+    /// the cells of the sheet that more than one board covers, each as every board's index and
+    /// its own position for the cell
+    fn shared(&self) -> Vec<Vec<(usize, CellPos)>> {
+        let mut sheet: BTreeMap<(usize, usize), Vec<(usize, CellPos)>> = BTreeMap::new();
+        for (i, ((top, left), _)) in self.boards.iter().enumerate() {
+            for pos in CellPos::all() {
+                sheet
+                    .entry((top + pos.row(), left + pos.column()))
+                    .or_default()
+                    .push((i, pos));
+            }
+        }
+        sheet
+            .into_values()
+            .filter(|cells| cells.len() > 1)
+            .collect()
+    }
+    /// narrows each board's copy of a shared cell to what every copy allows
+    fn agree(&mut self, cells: &[(usize, CellPos)]) -> Result<(), UpdateError> {
+        let mut agreed: Option<Cell> = None;
+        for &(i, pos) in cells {
+            let cell = self.cell(i, pos);
+            agreed = Some(match (agreed, cell) {
+                (None, cell) => cell.clone(),
+                (Some(Cell::Concrete(a)), &Cell::Concrete(b)) if a != b => {
+                    Err(UpdateError::InvalidConcrete)?
+                }
+                (Some(Cell::Concrete(val)), Cell::Possibilities(set)) if !set.contains(val) => {
+                    Err(UpdateError::InvalidConcrete)?
+                }
+                (Some(Cell::Possibilities(set)), &Cell::Concrete(val)) if !set.contains(val) => {
+                    Err(UpdateError::InvalidConcrete)?
+                }
+                (Some(_), &Cell::Concrete(val)) | (Some(Cell::Concrete(val)), _) => {
+                    Cell::Concrete(val)
+                }
+                (Some(Cell::Possibilities(a)), Cell::Possibilities(b)) => {
+                    let set = a.intersection(b);
+                    match set.single() {
+                        Some(val) => Cell::Concrete(val),
+                        None if set.is_empty() => Err(UpdateError::Impossible)?,
+                        None => Cell::Possibilities(set),
+                    }
+                }
+            });
+        }
+        if let Some(agreed) = agreed {
+            for &(i, pos) in cells {
+                *self.boards[i].1.mut_cell(pos) = agreed.clone();
+            }
+        }
+        Ok(())
+    }
+}
+
+/// the cell with the fewest candidates on any board of a composite, and the values it has left
+/// to try
+struct Frame {
+    composite: Composite,
+    board: usize,
+    pos: CellPos,
+    vals: Vec<CellVal>,
+}
+
+impl Frame {
+    /// fails if a board has a cell with no candidates left
+    fn new(composite: Composite) -> Result<Self, UpdateError> {
+        let (board, (pos, mut vals)) = composite
+            .boards
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (_, board))| Some((i, board.branch(None)?)))
+            .min_by_key(|(_, (_, vals))| vals.len())
+            .ok_or(UpdateError::Impossible)?;
+        vals.sort();
+        Ok(Frame {
+            composite,
+            board,
+            pos,
+            vals,
+        })
+    }
+}
+impl Iterator for Frame {
+    type Item = Composite;
+
+    fn next(&mut self) -> Option<Composite> {
+        if self.vals.is_empty() {
+            return None;
+        }
+        let mut guess = self.composite.clone();
+        *guess.boards[self.board].1.mut_cell(self.pos) = Cell::Concrete(self.vals.remove(0));
+        Some(guess)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    /// two boards, the second starting at the first's bottom right house
+    fn corners(a: Board, b: Board) -> Composite {
+        Composite::new()
+            .with_board(0, 0, a)
+            .unwrap()
+            .with_board(6, 6, b)
+            .unwrap()
+    }
+
+    #[test]
+    fn givens_cross_to_the_other_board() {
+        let mut composite = corners(
+            board!([[] [] [] [] [] [] [?, ?, ?, ?, ?, ?, 5]]),
+            board!([]),
+        );
+        composite.propagate().unwrap();
+        let boards: Vec<_> = composite.boards().map(|(_, board)| board).collect();
+
+        assert_eq!(boards[1].cell(pos!(0, 0)), &cell!(5));
+        // the 5 rules itself out of the second board's top row beyond the shared house
+        assert!(!boards[1].cell(pos!(0, 8)).is_possible(cell_val!(5)));
+    }
+    #[test]
+    fn shared_cells_have_to_agree() {
+        let why = Composite::new()
+            .with_board(0, 0, board!([[] [] [] [] [] [] [?, ?, ?, ?, ?, ?, 5]]))
+            .unwrap()
+            .with_board(6, 6, board!([[4]]))
+            .unwrap_err();
+
+        assert_eq!(why, BuildError::Overlap { row: 6, column: 6 });
+    }
+    #[test]
+    fn solutions_fill_every_board_the_same_where_they_overlap() {
+        let solution = corners(board!([[1, 2, 3]]), board!([[] [] [] [] [] [] [] [] [9]]))
+            .solve()
+            .unwrap();
+        let boards: Vec<_> = solution.boards().map(|(_, board)| board).collect();
+
+        assert!(solution.is_finished());
+        for row in 0..3 {
+            for column in 0..3 {
+                assert_eq!(
+                    boards[0].cell(pos!(6 + row, 6 + column)),
+                    boards[1].cell(pos!(row, column))
+                );
+            }
+        }
+    }
+    #[test]
+    fn samurai_shares_a_house_with_each_corner() {
+        let samurai = Composite::samurai(std::array::from_fn(|_| board!([]))).unwrap();
+
+        assert_eq!(samurai.shared().len(), 4 * 9);
+        assert!(samurai.solve().unwrap().is_finished());
+    }
+}
@@ -0,0 +1,286 @@
+use crate::{board::Shape, Board, Classic, RuleSet, SolverBackend, Unit};
+use std::{any::Any, env, panic};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum UpdateError {
+    #[error("found and incorrect concrete value in a cell")]
+    InvalidConcrete,
+    #[error("tried to set a concrete value where there already was one")]
+    MultipleConcrete,
+    #[error("we didn't get past take off")]
+    InitError,
+    #[error("didn't finish")]
+    Incomplete,
+    #[error("no possibilities left")]
+    Impossible,
+    #[error("the search went deeper than the depth limit")]
+    DepthLimit,
+    #[error("the solve ran out of time")]
+    Timeout,
+    #[error("the solve was cancelled")]
+    Cancelled,
+    /// a panic caught while doing what it names, which is a bug in this crate. the panic's
+    /// message is printed by the panic hook, and kept in `SolveError::Internal` where the error
+    /// type has room for it
+    #[error("internal error while {0}")]
+    Internal(&'static str),
+}
+
+#[derive(Error, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum BuildError {
+    #[error("invalid number of rows")]
+    RowCount,
+    #[error("invalid number of cells in row {0}")]
+    CellCount(usize),
+    /// a given outside 1-9, with its `row` and `column` counted from 0
+    #[error("{value} in row {}, column {} is not a valid cell value (1-9)", row + 1, column + 1)]
+    Value {
+        row: usize,
+        column: usize,
+        value: i64,
+    },
+    /// a cell given no candidates at all, with its `row` and `column` counted from 0
+    #[error("row {}, column {} has no candidates", row + 1, column + 1)]
+    NoCandidates { row: usize, column: usize },
+    /// a given outside 1 to the `side` of a board that isn't the classic 9x9 one, with its `row`
+    /// and `column` counted from 0
+    #[error(
+        "{value} in row {}, column {} is not a valid cell value (1-{side})",
+        row + 1,
+        column + 1
+    )]
+    OutOfRange {
+        row: usize,
+        column: usize,
+        value: i64,
+        side: usize,
+    },
+    /// a killer cage with a repeated cell, or no cells, or more than a row has
+    #[error("a cage needs 1 different cell up to a row's worth but was given {0}")]
+    CageCells(usize),
+    #[error("no {cells} different values add up to {sum}")]
+    CageSum { sum: usize, cells: usize },
+    /// an arrow through its own circle or a repeated cell, or with no cells or more than a row
+    /// has
+    #[error(
+        "an arrow needs 1 different cell up to a row's worth apart from its circle but was given \
+         {0}"
+    )]
+    ArrowCells(usize),
+    /// a sandwich clue on a house or off the board, or with a sum over 2 + 3 + ... up to one
+    /// less than the largest value, 35 on the classic board
+    #[error(
+        "a sandwich needs a row or column and a sum that fits between the crusts but was given \
+         {sum} on {unit:?}"
+    )]
+    Sandwich { unit: Unit, sum: usize },
+    /// a greater-than sign between cells that aren't next to each other, each as its `(row,
+    /// column)` counted from 0
+    #[error(
+        "a greater-than sign needs two cells next to each other, not {greater:?} and {less:?}"
+    )]
+    Inequality {
+        greater: (usize, usize),
+        less: (usize, usize),
+    },
+    /// boards of a `Composite` with different givens in a cell they share, with its `row` and
+    /// `column` on the composite's sheet counted from 0
+    #[error("the boards disagree on row {}, column {} where they overlap", row + 1, column + 1)]
+    Overlap { row: usize, column: usize },
+}
+
+/// errors reading a board from text
+#[derive(Error, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ParseError {
+    #[error("expected 81 cells but found {0}")]
+    CellCount(usize),
+    /// a 16x16 grid, see `io::read_hex`
+    #[error("expected 256 cells but found {0}")]
+    HexCellCount(usize),
+    /// a sukaku written as one line, see `io::read_sukaku`
+    #[error("expected 729 candidate places but found {0}")]
+    SukakuLength(usize),
+    /// a cell with none of its candidates marked, with its `row` and `column` counted from 0
+    #[error("row {}, column {} has no candidates", row + 1, column + 1)]
+    NoCandidates { row: usize, column: usize },
+    #[error("unexpected character {0:?}")]
+    Character(char),
+    /// a board read with a `SymbolMap`, see `io::SymbolMap::read`
+    #[error("expected {expected} cells but found {found}")]
+    SymbolCellCount { expected: usize, found: usize },
+    /// a symbol a `SymbolMap` can't use, because it repeats one or is a blank
+    #[error("{0:?} can't be a symbol")]
+    Symbol(char),
+    #[error("not the code of any solution")]
+    AnswerCode,
+    #[error("not a saved solver state")]
+    SolverState,
+    #[error("not a solve trace")]
+    Trace,
+    #[error("not a difficulty model")]
+    Model,
+    #[error("not a saved solution store")]
+    SolutionStore,
+}
+
+/// any error from one of the public entry points, for a board of shape `S`
+#[derive(Error, Debug, PartialEq, Eq, Clone)]
+pub enum SolveError<S: Shape = Classic> {
+    #[error(transparent)]
+    Build(#[from] BuildError),
+    #[error(transparent)]
+    Update(#[from] UpdateError),
+    /// the solve was stopped early, with `why` being `UpdateError::Timeout` or
+    /// `UpdateError::Cancelled`
+    #[error("{why}")]
+    Stopped {
+        why: UpdateError,
+        /// the board with the most cells filled in that the search got to
+        best: Board<S>,
+    },
+    /// the backend can't follow one of the `SolveOptions::variants`
+    #[error("the {backend} backend can't solve {variant} puzzles")]
+    Unsupported {
+        backend: &'static str,
+        variant: &'static str,
+    },
+    /// no backend has this name, see `SolverBackend::name`
+    #[error(
+        "there is no {0:?} backend, the backends are {}",
+        SolverBackend::ALL.map(SolverBackend::name).join(", ")
+    )]
+    UnknownBackend(String),
+    /// no rule preset has this name, see `RuleSet::PRESETS`
+    #[error(
+        "there is no {0:?} preset, the presets are {}",
+        RuleSet::PRESETS.join(", ")
+    )]
+    UnknownPreset(String),
+    /// something that should never happen did, which is a bug in this crate
+    #[error("internal error while {context}: {message}")]
+    Internal {
+        context: &'static str,
+        message: String,
+    },
+}
+
+/// a panic caught by `catch_internal`, which becomes `SolveError::Internal` for a board of any
+/// shape, or `UpdateError::Internal` without its message
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Panicked {
+    context: &'static str,
+    message: String,
+}
+impl<S: Shape> From<Panicked> for SolveError<S> {
+    fn from(Panicked { context, message }: Panicked) -> Self {
+        SolveError::Internal { context, message }
+    }
+}
+impl From<Panicked> for UpdateError {
+    fn from(Panicked { context, .. }: Panicked) -> Self {
+        UpdateError::Internal(context)
+    }
+}
+
+/// set to `0` to catch panics in the solver even with debug assertions on, or to anything else
+/// to let them through in a release build too, so a bug stops at the panic with its backtrace
+pub const PANIC_VAR: &str = "SUDOKU_PANIC";
+
+/// what each call to `catch_internal` says it was doing, so an `UpdateError::Internal` read back
+/// from text can name the same one
+pub(crate) const CONTEXTS: [&str; 11] = [
+    "building the board",
+    "building the board from candidates",
+    "counting solutions",
+    "generating a puzzle",
+    "propagating",
+    "sampling a solution",
+    "solving",
+    "solving a composite",
+    "solving one step at a time",
+    "solving with dancing links",
+    "solving with simulated annealing",
+];
+
+/// runs `f`, turning a panic into `SolveError::Internal` instead of taking down the caller
+///
+/// with debug assertions on the panic is left alone, so bugs are loud during development, unless
+/// `PANIC_VAR` says otherwise. `context` has to be one of `CONTEXTS`
+pub(crate) fn catch_internal<T>(
+    context: &'static str,
+    f: impl FnOnce() -> T + panic::UnwindSafe,
+) -> Result<T, Panicked> {
+    debug_assert!(CONTEXTS.contains(&context), "{context:?} isn't in CONTEXTS");
+    let passthrough = match env::var_os(PANIC_VAR) {
+        Some(var) if !var.is_empty() => var != "0",
+        _ => cfg!(debug_assertions),
+    };
+    catch_internal_if(!passthrough, context, f)
+}
+
+/// `catch_internal`, catching the panic only if `catch` is set
+fn catch_internal_if<T>(
+    catch: bool,
+    context: &'static str,
+    f: impl FnOnce() -> T + panic::UnwindSafe,
+) -> Result<T, Panicked> {
+    if !catch {
+        return Ok(f());
+    }
+    panic::catch_unwind(f).map_err(|payload| Panicked {
+        context,
+        message: panic_message(payload),
+    })
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&'static str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn panic_message_reads_formatted_panics() {
+        let payload = panic::catch_unwind(|| panic!("bad index {}", 10)).unwrap_err();
+        assert_eq!(panic_message(payload), "bad index 10");
+    }
+    #[test]
+    fn panic_message_reads_static_panics() {
+        let payload = panic::catch_unwind(|| panic!("bad index")).unwrap_err();
+        assert_eq!(panic_message(payload), "bad index");
+    }
+    #[test]
+    fn catch_internal_passes_results_through() {
+        assert_eq!(catch_internal("solving", || 5), Ok(5));
+    }
+    #[test]
+    fn catch_internal_catches_panics() {
+        assert_eq!(
+            catch_internal_if(true, "testing", || panic!("oops"))
+                .map_err(SolveError::<Classic>::from),
+            Err::<(), _>(SolveError::Internal {
+                context: "testing",
+                message: "oops".to_string()
+            })
+        );
+        assert_eq!(
+            catch_internal_if(true, "testing", || panic!("oops")).map_err(UpdateError::from),
+            Err::<(), _>(UpdateError::Internal("testing"))
+        );
+    }
+    #[test]
+    fn catch_internal_can_let_panics_through() {
+        let caught = panic::catch_unwind(|| catch_internal_if(false, "testing", || panic!("oops")));
+
+        assert_eq!(panic_message(caught.unwrap_err()), "oops");
+    }
+}
@@ -0,0 +1,202 @@
+//! machine readable events for wiring runs into other logging and metrics pipelines
+//!
+//! a run sends what happens to an `EventSink` as it goes. `JsonLines` writes one line of JSON
+//! per event to stdout or a file, and `Callback` hands each event to a closure, so operators
+//! don't have to parse the human readable output
+
+use crate::{capabilities::json_string, SolveStats, UpdateError};
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+};
+
+/// something that happened during a run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// a run on `input` started
+    Started { input: String },
+    /// the puzzle from `input` was solved, with `solution` as 81 digits
+    Solved {
+        input: String,
+        stats: SolveStats,
+        solution: String,
+    },
+    /// the solve of `input` was stopped by a timeout or cancel token, with `best` as 81 cells,
+    /// `.` for the ones it didn't fill in
+    Stopped {
+        input: String,
+        why: UpdateError,
+        best: String,
+    },
+    /// the run failed with `error`
+    Failed { error: String },
+    /// what a run found, as named values in the order they should be shown
+    Report {
+        name: &'static str,
+        fields: Vec<(&'static str, String)>,
+    },
+}
+
+impl Event {
+    /// the `event` key of the JSON
+    pub fn name(&self) -> &'static str {
+        match self {
+            Event::Started { .. } => "started",
+            Event::Solved { .. } => "solved",
+            Event::Stopped { .. } => "stopped",
+            Event::Failed { .. } => "failed",
+            Event::Report { .. } => "report",
+        }
+    }
+    /// the event as a single line of JSON, always starting with its `event` name
+    pub fn to_json(&self) -> String {
+        let mut fields = vec![("event", json_string(self.name()))];
+        match self {
+            Event::Started { input } => fields.push(("input", json_string(input))),
+            Event::Solved {
+                input,
+                stats,
+                solution,
+            } => fields.extend([
+                ("input", json_string(input)),
+                ("guesses", stats.guesses.to_string()),
+                ("max_depth", stats.max_depth.to_string()),
+                ("elapsed_ms", stats.elapsed.as_millis().to_string()),
+                ("eliminations", json_counts(&stats.eliminations)),
+                ("solution", json_string(solution)),
+            ]),
+            Event::Stopped { input, why, best } => fields.extend([
+                ("input", json_string(input)),
+                ("why", json_string(&format!("{why:?}"))),
+                ("best", json_string(best)),
+            ]),
+            Event::Failed { error } => fields.push(("error", json_string(error))),
+            Event::Report { name, fields: rest } => {
+                fields.push(("name", json_string(name)));
+                fields.extend(rest.iter().map(|(key, val)| (*key, json_string(val))));
+            }
+        }
+        let fields: Vec<_> = fields
+            .iter()
+            .map(|(key, val)| format!("{}:{val}", json_string(key)))
+            .collect();
+        format!("{{{}}}", fields.join(","))
+    }
+}
+
+/// a JSON object with a key per technique
+fn json_counts(counts: &BTreeMap<&'static str, usize>) -> String {
+    let counts: Vec<_> = counts
+        .iter()
+        .map(|(key, count)| format!("{}:{count}", json_string(key)))
+        .collect();
+    format!("{{{}}}", counts.join(","))
+}
+
+/// where a run's events go
+pub trait EventSink {
+    fn emit(&mut self, event: &Event) -> io::Result<()>;
+}
+
+impl<S: EventSink + ?Sized> EventSink for &mut S {
+    fn emit(&mut self, event: &Event) -> io::Result<()> {
+        (**self).emit(event)
+    }
+}
+impl<S: EventSink + ?Sized> EventSink for Box<S> {
+    fn emit(&mut self, event: &Event) -> io::Result<()> {
+        (**self).emit(event)
+    }
+}
+/// sends every event to both sinks, the first one first
+impl<A: EventSink, B: EventSink> EventSink for (A, B) {
+    fn emit(&mut self, event: &Event) -> io::Result<()> {
+        self.0.emit(event)?;
+        self.1.emit(event)
+    }
+}
+
+/// drops every event, for runs nobody is listening to
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Discard;
+impl EventSink for Discard {
+    fn emit(&mut self, _: &Event) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// writes each event as a line of JSON, flushing after each one so a reader sees it right away
+#[derive(Debug)]
+pub struct JsonLines<W>(pub W);
+impl JsonLines<io::Stdout> {
+    pub fn stdout() -> Self {
+        JsonLines(io::stdout())
+    }
+}
+impl<W: Write> EventSink for JsonLines<W> {
+    fn emit(&mut self, event: &Event) -> io::Result<()> {
+        writeln!(self.0, "{}", event.to_json())?;
+        self.0.flush()
+    }
+}
+
+/// hands each event to a closure
+pub struct Callback<F>(pub F);
+impl<F: FnMut(&Event)> EventSink for Callback<F> {
+    fn emit(&mut self, event: &Event) -> io::Result<()> {
+        (self.0)(event);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn json_lines_writes_a_line_per_event() {
+        let mut sink = JsonLines(vec![]);
+        sink.emit(&Event::Started {
+            input: "a \"b\".csv".to_string(),
+        })
+        .unwrap();
+        sink.emit(&Event::Solved {
+            input: "a.csv".to_string(),
+            stats: SolveStats {
+                guesses: 3,
+                elapsed: Duration::from_millis(12),
+                eliminations: BTreeMap::from([("singles", 40), ("naked pairs", 2)]),
+                ..Default::default()
+            },
+            solution: "123".to_string(),
+        })
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(sink.0).unwrap(),
+            concat!(
+                "{\"event\":\"started\",\"input\":\"a \\\"b\\\".csv\"}\n",
+                "{\"event\":\"solved\",\"input\":\"a.csv\",\"guesses\":3,\"max_depth\":0,",
+                "\"elapsed_ms\":12,\"eliminations\":{\"naked pairs\":2,\"singles\":40},",
+                "\"solution\":\"123\"}\n"
+            )
+        );
+    }
+    #[test]
+    fn callbacks_see_every_event() {
+        let mut seen = vec![];
+        let mut sink = Callback(|event: &Event| seen.push(event.name()));
+        sink.emit(&Event::Failed {
+            error: "oops".to_string(),
+        })
+        .unwrap();
+        sink.emit(&Event::Report {
+            name: "dedup",
+            fields: vec![("kept", "1".to_string())],
+        })
+        .unwrap();
+
+        assert_eq!(seen, ["failed", "report"]);
+    }
+}
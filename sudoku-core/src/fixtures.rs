@@ -0,0 +1,565 @@
+//! positions that each need one known strategy, to check the strategies still find them and
+//! don't fire where they shouldn't
+//!
+//! only built with the `strategy-fixtures` feature. `tests/strategy_fixtures.rs` runs `check` over
+//! every fixture, and other crates can run it over positions of their own the same way
+
+use crate::{io::read_pencilmarks, strategy::strategies, Board, SolveOptions};
+
+/// a candidate as `(row, column, value)`, with rows and columns from 0
+type Candidate = (usize, usize, usize);
+/// a fixture's strategy, its grid of candidates, its eliminations, and the other strategies that
+/// fire on it
+type Entry = (
+    &'static str,
+    &'static str,
+    &'static [Candidate],
+    &'static [&'static str],
+);
+
+/// a position, the strategy it needs, and what the strategy should rule out there
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fixture {
+    /// the strategy's name from `strategy_names`
+    pub technique: &'static str,
+    pub board: Board,
+    /// the candidates the strategy removes, as `(row, column, value)` with rows and columns from
+    /// 0, in order
+    pub eliminations: Vec<Candidate>,
+    /// the strategies tried after it that find something here too, in the order they are tried
+    ///
+    /// most harder patterns also cover the easier ones, so this is mostly expected, but pinning
+    /// it down catches a strategy that starts firing where it shouldn't
+    pub also_fired: Vec<&'static str>,
+}
+
+/// why a fixture didn't pass `check`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Misfire {
+    /// the position doesn't have exactly one solution, so a strategy could rule out anything on
+    /// it
+    Unsolvable,
+    /// the unit checks still remove candidates, so the solver would never try a strategy there
+    Unpropagated,
+    /// another strategy found something first, or `None` if none did
+    FiredFirst(Option<&'static str>),
+    /// the strategy fired but removed these candidates instead
+    Eliminated(Vec<Candidate>),
+    /// the strategies after it that fired were these instead of `Fixture::also_fired`
+    AlsoFired(Vec<&'static str>),
+}
+
+// each position is taken from the solve of a generated puzzle, at the step where the strategy
+// was first needed, so it has one solution and the unit checks have nothing left to remove. the
+// eliminations are what the strategy rules out there, followed by what every later strategy
+// still finds on the same position
+const FIXTURES: [Entry; 23] = [
+    (
+        "pointing",
+        "78 17 158 4 6 9 2 15 3
+         4 2 59 3 7 1 6 59 8
+         6 3 19 8 2 5 7 19 4
+         78 17 148 17 5 3 9 6 2
+         2 179 19 17 8 6 3 4 5
+         5 6 3 2 9 4 1 8 7
+         3 8 6 5 1 7 4 2 9
+         1 5 7 9 4 2 8 3 6
+         9 4 2 6 3 8 5 7 1",
+        &[(4, 2, 9)],
+        &[
+            "naked pair",
+            "naked triple",
+            "x-wing",
+            "skyscraper",
+            "turbot fish",
+            "simple coloring",
+            "w-wing",
+            "naked quad",
+            "xyz-wing",
+            "als-xz",
+            "forcing chain",
+        ],
+    ),
+    (
+        "claiming",
+        "2 8 3 5 6 1 7 4 9
+         4 9 7 8 2 3 6 5 1
+         5 6 1 7 9 4 8 3 2
+         6 2 4 13 135 8 35 9 7
+         9 3 5 6 4 7 1 2 8
+         1 7 8 9 35 25 35 6 4
+         8 1 29 23 35 259 4 7 6
+         3 4 269 12 17 29 29 8 5
+         7 5 29 4 8 6 29 1 3",
+        &[(6, 5, 2), (7, 5, 2)],
+        &[
+            "naked pair",
+            "naked triple",
+            "finned x-wing",
+            "simple coloring",
+            "xy-wing",
+            "w-wing",
+            "naked quad",
+            "swordfish",
+            "finned swordfish",
+            "xyz-wing",
+            "als-xz",
+            "forcing chain",
+        ],
+    ),
+    (
+        "naked pair",
+        "13 13 8 6 9 4 5 7 2
+         5 9 4 2 7 1 8 3 6
+         2 67 67 8 3 5 1 4 9
+         6 2 3 5 4 8 7 9 1
+         8 4 5 7 1 9 6 2 3
+         17 17 9 3 6 2 4 8 5
+         17 1678 167 9 2 67 3 5 4
+         379 367 67 4 5 67 2 1 8
+         4 5 2 1 8 3 9 6 7",
+        &[(7, 0, 7), (7, 1, 6), (7, 1, 7)],
+        &[
+            "naked triple",
+            "x-wing",
+            "skyscraper",
+            "turbot fish",
+            "w-wing",
+            "naked quad",
+            "xyz-wing",
+            "als-xz",
+            "forcing chain",
+        ],
+    ),
+    (
+        "hidden pair",
+        "6 145 78 3 17 58 2 14 479
+         3 14 278 9 17 28 5 6 47
+         9 15 27 4 6 25 8 13 37
+         8 9 6 1 5 7 3 24 24
+         5 3 4 6 2 9 7 8 1
+         2 7 1 8 4 3 6 9 5
+         1 8 5 7 9 6 4 23 23
+         7 6 9 2 3 4 1 5 8
+         4 2 3 5 8 1 9 7 6",
+        &[(1, 2, 7)],
+        &[
+            "naked triple",
+            "xy-wing",
+            "w-wing",
+            "naked quad",
+            "als-xz",
+            "forcing chain",
+        ],
+    ),
+    (
+        "naked triple",
+        "6 2 7 5 8 3 4 1 9
+         5 4 8 1 9 7 6 3 2
+         39 39 1 2 6 4 5 8 7
+         2 5 9 8 1 6 7 4 3
+         8 37 6 4 37 5 9 2 1
+         37 1 4 37 2 9 8 5 6
+         4 67 3 67 5 2 1 9 8
+         1 69 2 69 4 8 3 7 5
+         79 789 5 379 37 1 2 6 4",
+        &[(8, 1, 7), (8, 1, 9)],
+        &[
+            "finned x-wing",
+            "skyscraper",
+            "two-string kite",
+            "turbot fish",
+            "simple coloring",
+            "xy-wing",
+            "w-wing",
+            "naked quad",
+            "xyz-wing",
+            "als-xz",
+            "forcing chain",
+        ],
+    ),
+    (
+        "hidden triple",
+        "9 2 3 57 6 578 17 48 14
+         6 8 7 1 34 34 5 9 2
+         4 1 5 27 9 278 67 68 3
+         5 3 1 24 7 6 9 24 8
+         8 9 4 235 1 235 23 7 6
+         2 7 6 8 34 9 13 5 14
+         13 4 8 9 5 13 26 26 7
+         37 6 9 347 2 347 8 1 5
+         17 5 2 6 8 17 4 3 9",
+        &[(0, 5, 7), (2, 5, 7), (4, 5, 3)],
+        &["naked quad", "als-xz", "forcing chain"],
+    ),
+    (
+        "x-wing",
+        "2 4 5 3 1 6 7 9 8
+         9 1 8 24 7 5 6 24 3
+         7 3 6 9 8 24 5 124 14
+         1 7 3 8 4 9 2 5 6
+         4 8 2 6 5 17 3 17 9
+         6 5 9 27 3 127 4 8 17
+         5 6 47 47 9 8 1 3 2
+         8 2 47 1 6 3 9 47 5
+         3 9 1 5 2 47 8 6 47",
+        &[(2, 7, 4)],
+        &[
+            "finned x-wing",
+            "skyscraper",
+            "two-string kite",
+            "turbot fish",
+            "simple coloring",
+            "w-wing",
+            "swordfish",
+            "finned swordfish",
+            "als-xz",
+            "forcing chain",
+        ],
+    ),
+    (
+        "finned x-wing",
+        "6 2 7 5 8 3 4 1 9
+         5 4 8 1 9 7 6 3 2
+         39 39 1 2 6 4 5 8 7
+         2 5 9 8 1 6 7 4 3
+         8 37 6 4 37 5 9 2 1
+         37 1 4 37 2 9 8 5 6
+         4 67 3 67 5 2 1 9 8
+         1 69 2 69 4 8 3 7 5
+         79 8 5 379 37 1 2 6 4",
+        &[(5, 3, 7)],
+        &[
+            "skyscraper",
+            "two-string kite",
+            "turbot fish",
+            "simple coloring",
+            "w-wing",
+            "als-xz",
+            "bug+1",
+            "forcing chain",
+        ],
+    ),
+    (
+        "skyscraper",
+        "6 8 4 2 5 3 9 1 7
+         9 1237 237 4 78 178 238 5 6
+         5 17 237 6 9 178 4 23 38
+         28 6 5 1 37 79 238 239 4
+         4 23 1 38 6 89 5 7 29
+         7 9 38 5 2 4 1 6 38
+         38 4 6 89 1 2 7 39 5
+         1 27 278 3789 38 5 6 4 29
+         23 5 79 79 4 6 23 8 1",
+        &[(8, 3, 9)],
+        &[
+            "turbot fish",
+            "simple coloring",
+            "naked quad",
+            "finned swordfish",
+            "jellyfish",
+            "als-xz",
+            "forcing chain",
+        ],
+    ),
+    (
+        "two-string kite",
+        "1 467 5 8 27 247 679 367 39
+         3 47 8 9 6 47 2 5 1
+         67 9 2 5 3 1 67 4 8
+         2 3 1 6 9 57 57 8 4
+         567 67 9 4 27 8 15 367 23
+         567 8 4 3 1 257 5679 67 29
+         4 5 7 2 8 9 3 1 6
+         8 2 6 1 5 3 4 9 7
+         9 1 3 7 4 6 8 2 5",
+        &[(4, 1, 7)],
+        &[
+            "turbot fish",
+            "naked quad",
+            "finned swordfish",
+            "als-xz",
+            "forcing chain",
+        ],
+    ),
+    (
+        "turbot fish",
+        "4 25 3 1 7 8 6 25 9
+         9 6 17 5 3 2 14 8 47
+         8 125 127 9 4 6 25 17 3
+         3 4 12 7 12 5 9 6 8
+         6 78 5 4 18 9 3 17 2
+         12 78 9 6 128 3 15 4 57
+         5 9 4 2 6 7 8 3 1
+         7 3 6 8 9 1 245 25 45
+         12 12 8 3 5 4 7 9 6",
+        &[(5, 6, 1)],
+        &["finned swordfish", "als-xz", "forcing chain"],
+    ),
+    (
+        "simple coloring",
+        "4 19 7 6 8 19 2 3 5
+         2 6 5 3 7 4 8 1 9
+         8 139 39 19 2 5 6 4 7
+         39 7 39 8 1 2 5 6 4
+         6 2 8 5 4 79 3 79 1
+         1 5 4 79 6 3 79 2 8
+         37 34 2 14 9 8 17 5 6
+         5 49 6 147 3 17 19 8 2
+         79 8 1 2 5 6 4 79 3",
+        &[
+            (0, 1, 9),
+            (2, 2, 9),
+            (2, 3, 9),
+            (3, 0, 9),
+            (4, 5, 9),
+            (5, 6, 9),
+            (7, 1, 9),
+            (8, 7, 9),
+        ],
+        &["w-wing", "finned swordfish", "als-xz", "forcing chain"],
+    ),
+    (
+        "xy-wing",
+        "59 15 19 4 3 7 8 2 6
+         8 3 6 9 1 2 4 5 7
+         7 2 4 5 6 8 1 3 9
+         3 8 2 7 9 4 6 1 5
+         69 67 79 1 2 5 3 8 4
+         1 4 5 6 8 3 9 7 2
+         26 167 17 3 5 9 27 4 8
+         25 57 3 8 4 6 27 9 1
+         4 9 8 2 7 1 5 6 3",
+        &[(0, 2, 1), (6, 1, 1)],
+        &["xyz-wing", "als-xz", "bug+1", "forcing chain"],
+    ),
+    (
+        "w-wing",
+        "4 9 5 1 8 6 3 7 2
+         2 18 38 7 35 4 19 6 159
+         13 6 7 2 35 9 4 8 15
+         35 7 38 4 2 15 18 9 6
+         6 2 1 3 9 8 5 4 7
+         9 58 4 6 7 15 2 3 18
+         15 15 9 8 4 7 6 2 3
+         8 3 6 9 1 2 7 5 4
+         7 4 2 5 6 3 89 1 89",
+        &[(1, 6, 1)],
+        &["als-xz", "bug+1", "forcing chain"],
+    ),
+    (
+        "naked quad",
+        "26 3 1 8 7 26 5 4 9
+         24 7 9 5 24 3 6 8 1
+         8 5 46 9 46 1 7 2 3
+         1 4 8 6 3 9 2 7 5
+         9 2 5 7 1 8 3 6 4
+         37 6 37 24 25 245 9 1 8
+         5 8 47 24 269 246 1 3 67
+         36 9 36 1 8 7 4 5 2
+         47 1 2 3 56 456 8 9 67",
+        &[(6, 4, 2), (6, 4, 6)],
+        &["als-xz", "forcing chain"],
+    ),
+    (
+        "hidden quad",
+        "1 567 3 2789 26789 678 569 2569 4
+         29 56 8 4 1269 16 1569 7 12369
+         249 467 26 1279 5 3 169 8 1269
+         248 46 9 12578 12378 1578 145678 23456 1268
+         5 3 7 6 1289 4 189 29 1289
+         248 1 26 25789 23789 578 456789 234569 2689
+         7 2 4 158 168 1568 3 69 689
+         6 8 5 3 4 9 2 1 7
+         3 9 1 78 678 2 468 46 5",
+        &[
+            (3, 6, 1),
+            (3, 6, 6),
+            (3, 6, 8),
+            (3, 7, 2),
+            (3, 7, 6),
+            (5, 6, 6),
+            (5, 6, 8),
+            (5, 6, 9),
+            (5, 7, 2),
+            (5, 7, 6),
+            (5, 7, 9),
+        ],
+        &["als-xz"],
+    ),
+    (
+        "swordfish",
+        "9 4 1 8 2 7 6 3 5
+         5 8 3 4 9 6 1 7 2
+         27 27 6 3 1 5 8 9 4
+         4 6 7 2 8 3 5 1 9
+         1 5 2 79 6 49 3 48 78
+         8 3 9 15 57 14 2 46 67
+         37 1 48 6 347 2 9 5 38
+         2367 279 5 79 347 89 47 26 1
+         2367 279 48 15 3457 189 47 268 368",
+        &[(8, 5, 8)],
+        &["als-xz", "forcing chain"],
+    ),
+    (
+        "finned swordfish",
+        "5 2 8 3 7 16 4 16 9
+         136 4 7 12 9 126 38 5 168
+         136 9 13 5 8 4 23 7 126
+         9 7 16 4 3 5 28 126 1268
+         8 16 4 7 2 9 5 136 136
+         23 5 23 6 1 8 9 4 7
+         12 8 5 12 6 3 7 9 4
+         4 36 26 9 5 7 1 8 23
+         7 13 9 8 4 12 6 23 5",
+        &[(3, 7, 1)],
+        &["als-xz"],
+    ),
+    (
+        "jellyfish",
+        "24 26 245 3 7 256 89 89 1
+         269 1268 12589 269 28 2568 7 3 4
+         79 78 3 4 1 89 2 5 6
+         5 29 29 7 4 1 3 6 8
+         1 3 6 29 28 289 4 7 5
+         8 4 7 5 6 3 19 19 2
+         2469 5 2489 1 3 26 68 48 7
+         3 268 248 268 5 7 168 148 9
+         67 1678 18 68 9 4 5 2 3",
+        &[(3, 2, 9)],
+        &["als-xz", "forcing chain"],
+    ),
+    (
+        "xyz-wing",
+        "4 6 1 8 2 5 3 9 7
+         2 8 9 7 3 4 6 1 5
+         7 3 5 1 6 9 8 4 2
+         3 2 7 5 9 6 4 8 1
+         156 145 46 2 47 8 57 3 9
+         59 49 8 3 47 1 2 57 6
+         19 149 24 6 5 3 179 27 8
+         8 59 26 4 1 7 59 26 3
+         156 7 3 9 8 2 15 56 4",
+        &[(7, 1, 9)],
+        &["als-xz", "forcing chain"],
+    ),
+    (
+        "als-xz",
+        "8 4 5 9 3 1 6 2 7
+         7 3 2 68 68 5 1 9 4
+         1 6 9 24 47 27 8 5 3
+         5 2 1 36 67 37 9 4 8
+         6 8 7 5 9 4 3 1 2
+         3 9 4 28 1 28 7 6 5
+         2 1 6 348 48 38 5 7 9
+         4 7 8 1 5 9 2 3 6
+         9 5 3 7 2 6 4 8 1",
+        &[(5, 3, 8)],
+        &["bug+1", "forcing chain"],
+    ),
+    (
+        "bug+1",
+        "2 9 38 38 5 4 7 6 1
+         4 37 1 237 27 6 9 8 5
+         6 57 58 78 1 9 3 24 24
+         3 1 27 6 27 8 4 5 9
+         5 4 27 1 9 3 6 27 8
+         9 8 6 27 4 5 1 3 27
+         18 35 9 4 6 2 58 17 37
+         7 6 34 5 8 1 2 9 34
+         18 2 45 9 3 7 58 14 6",
+        &[(1, 3, 2), (1, 3, 3)],
+        &["forcing chain"],
+    ),
+    (
+        "forcing chain",
+        "2 6 8 13 57 17 4 9 35
+         9 1 7 4 35 8 2 56 356
+         3 5 4 6 2 9 1 7 8
+         8 7 2 9 1 5 6 3 4
+         6 39 5 8 4 37 79 1 2
+         4 39 1 2 37 6 5 8 79
+         5 8 9 17 6 2 3 4 17
+         7 4 6 35 9 13 8 2 15
+         1 2 3 57 8 4 79 56 679",
+        &[(0, 4, 7)],
+        &[],
+    ),
+];
+
+/// every fixture, in the order the solver tries their strategies
+pub fn fixtures() -> Vec<Fixture> {
+    FIXTURES
+        .iter()
+        .map(|&(technique, grid, eliminations, also_fired)| Fixture {
+            technique,
+            board: read_pencilmarks(grid).expect("every fixture has 81 cells"),
+            eliminations: eliminations.to_vec(),
+            also_fired: also_fired.to_vec(),
+        })
+        .collect()
+}
+
+/// the first strategy, in the order the solver tries them, that finds something on `board`, with
+/// the candidates it removes in the same form as `Fixture::eliminations`
+///
+/// every strategy is tried, bug+1 included, so `board` should have one solution
+pub fn first_to_fire(board: &Board) -> Option<(&'static str, Vec<Candidate>)> {
+    let options = SolveOptions {
+        unique_solution: true,
+        ..Default::default()
+    };
+    let deduction = strategies(&options)
+        .iter()
+        .find_map(|strategy| strategy.find(board))?;
+    let eliminations = deduction
+        .eliminations
+        .iter()
+        .map(|elimination| {
+            let pos = elimination.pos;
+            (pos.row(), pos.column(), elimination.val.into_inner())
+        })
+        .collect();
+    Some((deduction.strategy, eliminations))
+}
+
+/// every strategy that finds something on `board`, in the order the solver tries them
+///
+/// like `first_to_fire`, bug+1 is tried too
+pub fn all_to_fire(board: &Board) -> Vec<&'static str> {
+    let options = SolveOptions {
+        unique_solution: true,
+        ..Default::default()
+    };
+    strategies(&options)
+        .iter()
+        .filter(|strategy| strategy.find(board).is_some())
+        .map(|strategy| strategy.name())
+        .collect()
+}
+
+/// checks that the fixture is a position the solver could reach, that its strategy is the first
+/// to find something there, that it rules out exactly the fixture's eliminations, and that the
+/// strategies tried after it that also find something are exactly `also_fired`
+pub fn check(fixture: &Fixture) -> Result<(), Misfire> {
+    if fixture.board.count_solutions(2) != Ok(1) {
+        Err(Misfire::Unsolvable)?
+    }
+    let propagation = fixture.board.clone().propagate();
+    if propagation.contradiction.is_some() || propagation.board != fixture.board {
+        Err(Misfire::Unpropagated)?
+    }
+    match first_to_fire(&fixture.board) {
+        Some((technique, eliminations)) if technique == fixture.technique => {
+            if eliminations != fixture.eliminations {
+                Err(Misfire::Eliminated(eliminations))?
+            }
+            let also_fired = all_to_fire(&fixture.board).split_off(1);
+            if also_fired != fixture.also_fired {
+                Err(Misfire::AlsoFired(also_fired))?
+            }
+            Ok(())
+        }
+        fired => Err(Misfire::FiredFirst(fired.map(|(technique, _)| technique))),
+    }
+}
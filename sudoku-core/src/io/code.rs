@@ -0,0 +1,150 @@
+//! short codes for solutions, so an answer key can be handed out without showing the answer
+//!
+//! each cell is written as which of its possible values it has, given the cells before it in
+//! row-major order. that packs any valid solution into at most 104 bits, written as 21 Crockford
+//! base32 characters in groups of seven (`0V4F0QZ-7M2XA91-KD3H8PS`)
+use crate::{
+    board::{Cell, CellPos, Shape},
+    Board, CellVal, Classic, ParseError, SolvedBoard,
+};
+
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+/// 32^21 is more than the product of how many values each cell could have, even in the worst case
+const LENGTH: usize = 21;
+
+/// the number behind a solution's answer code, which takes at most 104 bits, or `None` if two
+/// cells that see each other share a value
+pub(crate) fn solution_number(solution: &SolvedBoard) -> Option<u128> {
+    let grid: [[usize; 9]; 9] = solution.clone().into();
+    let grid: Vec<usize> = grid.into_iter().flatten().collect();
+    let (mut number, mut radix) = (0u128, 1u128);
+    for (i, &val) in grid.iter().enumerate() {
+        let left = remaining(&grid[..i]);
+        number += left.iter().position(|&other| other == val)? as u128 * radix;
+        radix *= left.len() as u128;
+    }
+    Some(number)
+}
+
+/// the solution `solution_number` gave `number`
+pub(crate) fn numbered_solution(mut number: u128) -> Result<SolvedBoard, ParseError> {
+    let mut grid = vec![];
+    for _ in 0..81 {
+        let left = remaining(&grid);
+        if left.is_empty() {
+            Err(ParseError::AnswerCode)?
+        }
+        grid.push(left[(number % left.len() as u128) as usize]);
+        number /= left.len() as u128;
+    }
+    // a real number uses up every bit
+    if number != 0 {
+        Err(ParseError::AnswerCode)?
+    }
+    let board: Board = CellPos::all()
+        .zip(grid)
+        .map(|(pos, val)| {
+            let val = CellVal::new(val).expect("remaining only gives values 1-9");
+            (pos, Cell::Concrete(val))
+        })
+        .collect();
+    SolvedBoard::try_from(board).map_err(|_| ParseError::AnswerCode)
+}
+
+/// the answer code for a solution, or `None` if two cells that see each other share a value
+pub fn write_answer_code(solution: &SolvedBoard) -> Option<String> {
+    let mut code = solution_number(solution)?;
+    let mut chars = [b'0'; LENGTH];
+    for char in chars.iter_mut().rev() {
+        *char = ALPHABET[(code % 32) as usize];
+        code /= 32;
+    }
+    let groups: Vec<_> = chars
+        .chunks(7)
+        .map(|group| String::from_utf8_lossy(group).into_owned())
+        .collect();
+    Some(groups.join("-"))
+}
+
+/// the solution an answer code stands for
+///
+/// case, dashes, and whitespace don't matter, and `I`, `L`, and `O` are read as `1`, `1`, and `0`.
+/// most mistyped codes are rejected, but not all of them
+pub fn read_answer_code(code: &str) -> Result<SolvedBoard, ParseError> {
+    let mut value = 0u128;
+    let mut length = 0;
+    for c in code.chars().filter(|&c| c != '-' && !c.is_whitespace()) {
+        let c = match c.to_ascii_uppercase() {
+            'I' | 'L' => '1',
+            'O' => '0',
+            c => c,
+        };
+        let digit = ALPHABET
+            .iter()
+            .position(|&known| known as char == c)
+            .ok_or(ParseError::Character(c))?;
+        value = value * 32 + digit as u128;
+        length += 1;
+        if length > LENGTH {
+            Err(ParseError::AnswerCode)?
+        }
+    }
+    if length != LENGTH {
+        Err(ParseError::AnswerCode)?
+    }
+    numbered_solution(value)
+}
+
+/// the values the next cell can have after `before`, the cells ahead of it in row-major order
+fn remaining(before: &[usize]) -> Vec<usize> {
+    let i = before.len();
+    let sees = |j: usize| {
+        let house = |k: usize| Classic::house_of(k / 9, k % 9);
+        j / 9 == i / 9 || j % 9 == i % 9 || house(j) == house(i)
+    };
+    (1..=9)
+        .filter(|&val| {
+            !before
+                .iter()
+                .enumerate()
+                .any(|(j, &other)| other == val && sees(j))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    fn solution() -> SolvedBoard {
+        board!([[5, 3, ?, ?, 7]])
+            .solve()
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn answer_codes_round_trip() {
+        let solution = solution();
+        let code = write_answer_code(&solution).unwrap();
+
+        assert_eq!(code.len(), 23);
+        assert_eq!(read_answer_code(&code), Ok(solution.clone()));
+        assert_eq!(
+            read_answer_code(&code.to_lowercase().replace('-', " ")),
+            Ok(solution)
+        );
+    }
+    #[test]
+    fn read_answer_code_rejects_codes_without_a_solution() {
+        // taking the smallest value every time gets stuck in the second row
+        assert_eq!(
+            read_answer_code("0000000-0000000-0000000"),
+            Err(ParseError::AnswerCode)
+        );
+        assert_eq!(read_answer_code("ZZZZZ"), Err(ParseError::AnswerCode));
+        assert_eq!(read_answer_code("U"), Err(ParseError::Character('U')));
+    }
+}
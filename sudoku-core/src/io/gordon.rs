@@ -0,0 +1,65 @@
+//! the run-length packing some large collections of puzzles are stored in
+//!
+//! ```text
+//! 53b7d6b195d98d6a8c6c34b8a3b17c2c6a6d28d419b5d8b79
+//! ```
+//!
+//! a digit is a given, and a letter is a run of blanks: `a` is one, `b` two, up to `z` for 26,
+//! in either case. `.` and `0` are single blanks, so a plain 81 character puzzle reads the same,
+//! and whitespace is ignored
+use crate::{
+    board::{Candidates, Cell, CellPos},
+    Board, CellVal, ParseError,
+};
+
+/// reads a board from a packed puzzle
+pub fn read_gordon(text: &str) -> Result<Board, ParseError> {
+    let mut cells = vec![];
+    for c in text.chars().filter(|c| !c.is_whitespace()) {
+        match c {
+            '.' | '0' => cells.push(None),
+            '1'..='9' => cells.push(c.to_digit(10).and_then(|d| CellVal::new(d as usize).ok())),
+            'a'..='z' | 'A'..='Z' => {
+                let run = c.to_ascii_lowercase() as usize - 'a' as usize + 1;
+                cells.extend(std::iter::repeat_n(None, run));
+            }
+            _ => Err(ParseError::Character(c))?,
+        }
+    }
+    if cells.len() != 81 {
+        Err(ParseError::CellCount(cells.len()))?
+    }
+    Ok(CellPos::all()
+        .zip(cells)
+        .map(|(pos, val)| match val {
+            Some(val) => (pos, Cell::Concrete(val)),
+            None => (pos, Cell::Possibilities(Candidates::full())),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    const PLAIN: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+
+    #[test]
+    fn runs_of_letters_are_blanks() {
+        let packed = "53b7d6b195d98d6a8c6c34b8a3b17c2c6a6d28d419b5d8b79";
+
+        assert_eq!(read_gordon(packed), read_gordon(PLAIN));
+        assert_eq!(read_gordon(&packed.to_uppercase()), read_gordon(PLAIN));
+        let board = read_gordon(packed).unwrap();
+        assert_eq!(board.cell(pos!(0, 1)), &cell!(3));
+        assert_eq!(board.cell(pos!(0, 2)), &cell!(? 1, 2, 3, 4, 5, 6, 7, 8, 9));
+    }
+    #[test]
+    fn read_gordon_counts_the_cells() {
+        assert_eq!(read_gordon("z"), Err(ParseError::CellCount(26)));
+        assert_eq!(read_gordon("zzzc!"), Err(ParseError::Character('!')));
+        assert!(read_gordon("zzzc").is_ok());
+    }
+}
@@ -0,0 +1,189 @@
+//! the structure of a board as a graph, written as DOT or GraphML for outside graph tools
+//!
+//! cells are named `r{row}c{column}` and candidates `r{row}c{column}v{value}`, counting rows and
+//! columns from 0
+
+use crate::{
+    board::{units, Cell, CellPos, Column, Row, ToSet},
+    strategy::StrongLinks,
+    Board, CellVal,
+};
+
+/// an undirected graph with a kind on every node and edge
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Graph {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node {
+    pub id: String,
+    pub label: String,
+    /// `cell`, `row`, `column`, `house`, or `candidate`
+    pub kind: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+    /// `in` from a cell to a unit it is in, or `strong` or `weak` between candidates
+    pub kind: &'static str,
+}
+
+impl Board {
+    /// every cell joined to the row, column, and house it is in
+    ///
+    /// cells are labelled with their value, or `.` if they aren't solved
+    pub fn constraint_graph(&self) -> Graph {
+        let mut graph = Graph::default();
+        for pos in CellPos::all() {
+            let label = match self.cell(pos) {
+                Cell::Concrete(val) => val.into_inner().to_string(),
+                Cell::Possibilities(_) => ".".to_string(),
+            };
+            graph.node(cell_id(pos), label, "cell");
+        }
+        for (i, unit) in units().enumerate() {
+            let kind = ["row", "column", "house"][i / 9];
+            let id = format!("{kind}{}", i % 9);
+            graph.node(id.clone(), id.clone(), kind);
+            for pos in unit {
+                graph.edge(cell_id(pos), id.clone(), "in");
+            }
+        }
+        graph
+    }
+    /// every candidate joined to the candidates it rules out
+    ///
+    /// an edge is `strong` when one end or the other has to be true: the only two candidates in
+    /// a cell, or the only two places for a value in a unit. every other edge is `weak`
+    pub fn candidate_graph(&self) -> Graph {
+        let mut graph = Graph::default();
+        let links = StrongLinks::new(self);
+        let candidates: Vec<(CellPos, CellVal)> = CellPos::all()
+            .flat_map(|pos| CellVal::cell_vals().map(move |val| (pos, val)))
+            .filter(|&(pos, val)| self.cell(pos).is_possible(val))
+            .collect();
+        for &(pos, val) in &candidates {
+            graph.node(
+                candidate_id(pos, val),
+                val.into_inner().to_string(),
+                "candidate",
+            );
+        }
+        for (i, &(a, a_val)) in candidates.iter().enumerate() {
+            for &(b, b_val) in &candidates[i + 1..] {
+                let strong = if a == b {
+                    matches!(self.cell(a), Cell::Possibilities(set) if set.len() == 2)
+                } else if a_val == b_val && self.sees(a, b) {
+                    links.of(a_val).contains(&(a, b))
+                } else {
+                    continue;
+                };
+                let kind = if strong { "strong" } else { "weak" };
+                graph.edge(candidate_id(a, a_val), candidate_id(b, b_val), kind);
+            }
+        }
+        graph
+    }
+}
+
+impl Graph {
+    fn node(&mut self, id: String, label: String, kind: &'static str) {
+        self.nodes.push(Node { id, label, kind });
+    }
+    fn edge(&mut self, from: String, to: String, kind: &'static str) {
+        self.edges.push(Edge { from, to, kind });
+    }
+    /// the graph in graphviz's DOT language
+    pub fn to_dot(&self) -> String {
+        let mut out = "graph sudoku {\n".to_string();
+        for Node { id, label, kind } in &self.nodes {
+            out += &format!("    {id} [label=\"{label}\", kind=\"{kind}\"];\n");
+        }
+        for Edge { from, to, kind } in &self.edges {
+            out += &format!("    {from} -- {to} [kind=\"{kind}\"];\n");
+        }
+        out + "}\n"
+    }
+    /// the graph as a GraphML document, with `label` and `kind` data on the nodes and edges
+    pub fn to_graphml(&self) -> String {
+        let mut out = concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n",
+            "  <key id=\"kind\" for=\"all\" attr.name=\"kind\" attr.type=\"string\"/>\n",
+            "  <graph id=\"sudoku\" edgedefault=\"undirected\">\n",
+        )
+        .to_string();
+        for Node { id, label, kind } in &self.nodes {
+            out += &format!(
+                "    <node id=\"{id}\"><data key=\"label\">{label}</data><data key=\"kind\">{kind}</data></node>\n"
+            );
+        }
+        for Edge { from, to, kind } in &self.edges {
+            out += &format!(
+                "    <edge source=\"{from}\" target=\"{to}\"><data key=\"kind\">{kind}</data></edge>\n"
+            );
+        }
+        out + "  </graph>\n</graphml>\n"
+    }
+}
+
+fn cell_id(pos: CellPos) -> String {
+    format!(
+        "r{}c{}",
+        Row::containing(pos).into_inner(),
+        Column::containing(pos).into_inner()
+    )
+}
+fn candidate_id(pos: CellPos, val: CellVal) -> String {
+    format!("{}v{}", cell_id(pos), val.into_inner())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn constraint_graph_joins_every_cell_to_three_units() {
+        let graph = board!([]).constraint_graph();
+
+        assert_eq!(graph.nodes.len(), 81 + 27);
+        assert_eq!(graph.edges.len(), 81 * 3);
+        assert!(graph.edges.contains(&Edge {
+            from: "r4c4".into(),
+            to: "house4".into(),
+            kind: "in"
+        }));
+    }
+    #[test]
+    fn candidate_graph_marks_strong_links() {
+        let board = board!([[{ 1, 2 }, 3, 4, 5, { 1, 6 }, 7, 8, 9, 2]]);
+        let graph = board.candidate_graph();
+        let kind = |from: &str, to: &str| {
+            graph
+                .edges
+                .iter()
+                .find(|edge| edge.from == from && edge.to == to)
+                .map(|edge| edge.kind)
+        };
+
+        assert_eq!(kind("r0c0v1", "r0c0v2"), Some("strong"));
+        assert_eq!(kind("r0c0v1", "r0c4v1"), Some("strong"));
+        assert_eq!(kind("r0c0v1", "r1c0v1"), Some("weak"));
+        assert_eq!(kind("r0c0v1", "r1c1v2"), None);
+    }
+    #[test]
+    fn graphs_write_as_dot_and_graphml() {
+        let graph = board!([]).constraint_graph();
+
+        assert!(graph.to_dot().contains("    r0c0 -- row0 [kind=\"in\"];\n"));
+        assert!(graph
+            .to_graphml()
+            .contains("<edge source=\"r0c0\" target=\"row0\"><data key=\"kind\">in</data></edge>"));
+    }
+}
@@ -0,0 +1,184 @@
+//! boards written one character a cell, the way hexadoku is usually printed
+//!
+//! a `SymbolMap` says which character stands for each value. `read_hex` and `write_hex` use the
+//! digits `0` to `F` for the values 1 to 16, and `.` for a blank. whitespace is ignored, so a
+//! board can be one line of 256 characters or sixteen lines of sixteen
+use crate::{board::Shape, Board, ParseError, Size};
+
+/// how many cells across a hex board is
+const SIDE: usize = 16;
+
+/// characters read as a blank, as long as the alphabet doesn't use them for a value
+const BLANKS: [char; 4] = ['.', '-', '_', '0'];
+
+/// the characters a board's values are written with, the first for 1, the next for 2, and so on
+///
+/// symbols are read in either case and written in the case they were given in. a blank is
+/// written as `.`, and read from any of `.`, `-`, `_`, or `0` the alphabet leaves free, so files
+/// that mark blanks differently can all be read without one of them meaning a value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolMap {
+    symbols: Vec<char>,
+}
+
+impl SymbolMap {
+    /// a map from `symbols`, which can't repeat a character, even in another case, or use `.`
+    pub fn new(symbols: &str) -> Result<Self, ParseError> {
+        let symbols: Vec<char> = symbols.chars().collect();
+        for (i, &c) in symbols.iter().enumerate() {
+            let repeated = symbols[..i]
+                .iter()
+                .any(|other| other.to_lowercase().eq(c.to_lowercase()));
+            if c == '.' || c.is_whitespace() || repeated {
+                Err(ParseError::Symbol(c))?
+            }
+        }
+        Ok(Self { symbols })
+    }
+    /// `0` to `F`, the usual hexadoku alphabet
+    pub fn hex() -> Self {
+        Self::new("0123456789ABCDEF").expect("hex digits are distinct")
+    }
+    /// `1` to `9` and then `A` to `G`, so a 9x9 board reads the same as usual
+    pub fn digits_then_letters() -> Self {
+        Self::new("123456789ABCDEFG").expect("the digits and letters are distinct")
+    }
+    /// `A` to `Z`, for boards up to 26 across with no digits at all
+    pub fn letters() -> Self {
+        Self::new("ABCDEFGHIJKLMNOPQRSTUVWXYZ").expect("letters are distinct")
+    }
+    /// the value `c` stands for, or `None` for a blank
+    fn value(&self, c: char) -> Result<Option<i64>, ParseError> {
+        let found = self
+            .symbols
+            .iter()
+            .position(|symbol| symbol.to_lowercase().eq(c.to_lowercase()));
+        match found {
+            Some(i) => Ok(Some(i as i64 + 1)),
+            None if BLANKS.contains(&c) => Ok(None),
+            None => Err(ParseError::Character(c)),
+        }
+    }
+    /// reads a board of shape `S`, whose side has to be at most the number of symbols
+    pub fn read<S: Shape>(&self, text: &str) -> Result<Board<S>, ParseError> {
+        let cells = text
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| self.value(c))
+            .collect::<Result<Vec<_>, _>>()?;
+        if cells.len() != S::CELLS {
+            Err(ParseError::SymbolCellCount {
+                expected: S::CELLS,
+                found: cells.len(),
+            })?
+        }
+        let lines = cells.chunks(S::SIDE).map(<[_]>::to_vec).collect();
+        // a symbol past the side of the board is out of range for it
+        Board::from_givens(lines).map_err(|_| {
+            let c = cells.iter().flatten().find(|&&val| val as usize > S::SIDE);
+            ParseError::Character(c.map_or('?', |&val| self.symbols[val as usize - 1]))
+        })
+    }
+    /// writes a board one row a line
+    ///
+    /// # Panics
+    ///
+    /// if the board is wider than there are symbols
+    pub fn write<S: Shape>(&self, board: &Board<S>) -> String {
+        assert!(
+            S::SIDE <= self.symbols.len(),
+            "{} symbols can't write a board {} across",
+            self.symbols.len(),
+            S::SIDE
+        );
+        let mut text = String::new();
+        for row in board.values() {
+            text.extend(row.iter().map(|val| match val {
+                None => '.',
+                Some(val) => self.symbols[val - 1],
+            }));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+/// reads a 16x16 board
+pub fn read_hex(text: &str) -> Result<Board<Size<4, 4>>, ParseError> {
+    SymbolMap::hex().read(text).map_err(|err| match err {
+        ParseError::SymbolCellCount { found, .. } => ParseError::HexCellCount(found),
+        err => err,
+    })
+}
+
+/// writes a board one row a line, for boards at most 16 across
+///
+/// # Panics
+///
+/// if the board is more than 16 across, since its values won't fit in a hex digit
+pub fn write_hex<S: Shape>(board: &Board<S>) -> String {
+    assert!(S::SIDE <= SIDE, "a hex board is at most {SIDE} across");
+    SymbolMap::hex().write(board)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Classic;
+
+    #[test]
+    fn hex_boards_round_trip() {
+        let text = format!("0123456789ABCDEF\n{}", ".".repeat(240));
+        let board = read_hex(&text).unwrap();
+        let values = board.values();
+
+        assert_eq!(values[0][0], Some(1));
+        assert_eq!(values[0][15], Some(16));
+        assert_eq!(values[1][0], None);
+        assert_eq!(read_hex(&write_hex(&board)), Ok(board));
+        assert!(
+            write_hex(&read_hex(&text.to_lowercase()).unwrap()).starts_with("0123456789ABCDEF\n")
+        );
+    }
+    #[test]
+    fn hex_boards_have_256_cells() {
+        assert_eq!(read_hex("0123"), Err(ParseError::HexCellCount(4)));
+        assert_eq!(
+            read_hex(&format!("G{}", ".".repeat(255))),
+            Err(ParseError::Character('G'))
+        );
+    }
+    #[test]
+    fn other_alphabets_read_in_either_case() {
+        let text = format!("abcdefghijklmnop\n{}", "-".repeat(240));
+        let board = SymbolMap::letters().read::<Size<4, 4>>(&text).unwrap();
+
+        assert_eq!(board.values()[0][15], Some(16));
+        assert_eq!(board.values()[1][0], None);
+        assert!(SymbolMap::letters()
+            .write(&board)
+            .starts_with("ABCDEFGHIJKLMNOP\n................\n"));
+        // 0 isn't one of the symbols, so it is a blank
+        let text = format!("123456789ABCDEFG{}", "0".repeat(240));
+        let board = SymbolMap::digits_then_letters()
+            .read::<Size<4, 4>>(&text)
+            .unwrap();
+        assert_eq!(
+            SymbolMap::digits_then_letters().read(&SymbolMap::digits_then_letters().write(&board)),
+            Ok(board)
+        );
+    }
+    #[test]
+    fn symbol_maps_are_unambiguous() {
+        assert_eq!(SymbolMap::new("abcA"), Err(ParseError::Symbol('A')));
+        assert_eq!(SymbolMap::new("12.4"), Err(ParseError::Symbol('.')));
+        // in hex 0 is the value 1, so only the other markers are blanks
+        assert_eq!(SymbolMap::hex().value('0'), Ok(Some(1)));
+        assert_eq!(SymbolMap::hex().value('-'), Ok(None));
+        // a symbol past the side of the board is out of range
+        assert_eq!(
+            SymbolMap::letters().read::<Classic>(&format!("J{}", ".".repeat(80))),
+            Err(ParseError::Character('J'))
+        );
+    }
+}
@@ -0,0 +1,62 @@
+//! reading and writing boards as text
+mod code;
+mod gordon;
+mod graph;
+mod hex;
+mod pencilmarks;
+mod sukaku;
+mod svg;
+mod trace;
+
+pub(crate) use code::{numbered_solution, solution_number};
+pub use code::{read_answer_code, write_answer_code};
+pub use gordon::read_gordon;
+pub use graph::{Edge, Graph, Node};
+pub use hex::{read_hex, write_hex, SymbolMap};
+pub use pencilmarks::{read_pencilmarks, write_pencilmarks};
+pub use sukaku::{read_sukaku, write_sukaku};
+pub use svg::{board_svg, heat_map_svg};
+pub use trace::{replay_trace, write_trace};
+
+use crate::capabilities::Format;
+
+/// the lines of `text`, ending at `\n`, `\r\n`, or a lone `\r`
+///
+/// files passed between Windows, Linux, and old Macs can mix all three, and a `\r` left at the
+/// end of a line would otherwise be read as part of its last cell
+pub fn lines(text: &str) -> impl Iterator<Item = &str> {
+    text.lines()
+        .flat_map(|line| line.strip_suffix('\r').unwrap_or(line).split('\r'))
+}
+
+/// the formats this module reads or writes
+pub fn formats() -> Vec<Format> {
+    let format = |name, read, write| Format { name, read, write };
+    vec![
+        format("pencilmarks", true, true),
+        format("sukaku", true, true),
+        format("answer-code", true, true),
+        format("gordon", true, false),
+        format("hex", true, true),
+        format("dot", false, true),
+        format("graphml", false, true),
+        format("svg", false, true),
+        format("trace", true, true),
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn lines_splits_on_every_line_ending() {
+        let text = "1,2\r\n3,4\n5,6\r7,8\r";
+
+        assert_eq!(
+            lines(text).collect::<Vec<_>>(),
+            ["1,2", "3,4", "5,6", "7,8"]
+        );
+        assert_eq!(lines("1,2").collect::<Vec<_>>(), ["1,2"]);
+    }
+}
@@ -0,0 +1,149 @@
+//! the "pm grid" used by Hodoku and the SudokuWiki forums
+//!
+//! ```text
+//! .----------------.----------------.----------------.
+//! | 5   3    124   | 26   7    2468 | 1489 1249 248  |
+//! ...
+//! :----------------+----------------+----------------:
+//! ...
+//! '----------------'----------------'----------------'
+//! ```
+//!
+//! every cell is a group of digits: a single digit is a solved cell, more than one are the
+//! candidates left. the borders are ignored when reading, so hand edited grids still work
+use crate::{
+    board::{Cell, CellPos},
+    Board, CellVal, ParseError,
+};
+
+const BORDER: &[char] = &['.', '-', '|', ':', '+', '\'', '*'];
+
+/// reads a board from a pencilmark grid
+pub fn read_pencilmarks(text: &str) -> Result<Board, ParseError> {
+    let mut groups = vec![];
+    let mut group: Option<Vec<CellVal>> = None;
+    for c in text.chars() {
+        match c.to_digit(10).map(|d| CellVal::new(d as usize)) {
+            Some(Ok(val)) => group.get_or_insert_with(Vec::new).push(val),
+            Some(Err(_)) => Err(ParseError::Character(c))?,
+            None if c.is_whitespace() || BORDER.contains(&c) => groups.extend(group.take()),
+            None => Err(ParseError::Character(c))?,
+        }
+    }
+    groups.extend(group);
+    if groups.len() != 81 {
+        Err(ParseError::CellCount(groups.len()))?
+    }
+    Ok(CellPos::all()
+        .zip(groups)
+        .map(|(pos, vals)| match vals[..] {
+            [val] => (pos, Cell::Concrete(val)),
+            _ => (pos, Cell::Possibilities(vals.into_iter().collect())),
+        })
+        .collect())
+}
+
+/// writes a board as a pencilmark grid, lining up the columns
+pub fn write_pencilmarks(board: &Board) -> String {
+    let groups: Vec<Vec<String>> = board
+        .rows()
+        .map(|row| {
+            row.iter()
+                .map(|cell| match cell {
+                    Cell::Concrete(val) => val.into_inner().to_string(),
+                    Cell::Possibilities(set) => {
+                        let mut vals: Vec<_> = set.iter().map(|val| val.into_inner()).collect();
+                        vals.sort();
+                        vals.iter().map(usize::to_string).collect()
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    let widths: Vec<usize> = (0..9)
+        .map(|c| groups.iter().map(|row| row[c].len()).max().unwrap_or(1))
+        .collect();
+    let border = |left: char, middle: char, right: char| {
+        let segments: Vec<String> = widths
+            .chunks(3)
+            .map(|stack| "-".repeat(stack.iter().sum::<usize>() + 4))
+            .collect();
+        format!("{left}{}{right}\n", segments.join(&middle.to_string()))
+    };
+
+    let mut out = border('.', '.', '.');
+    for (r, row) in groups.iter().enumerate() {
+        if r == 3 || r == 6 {
+            out += &border(':', '+', ':');
+        }
+        for (c, group) in row.iter().enumerate() {
+            if c % 3 == 0 {
+                out += "| ";
+            }
+            out += &format!("{group:<width$} ", width = widths[c]);
+        }
+        out += "|\n";
+    }
+    out + &border('\'', '\'', '\'')
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    const GRID: &str = "\
+.-------------------.--------------.---------------.
+| 5   3     1246    | 2689 7  2468 | 14689 1249 248 |
+| 6   247   247     | 1    9  5    | 3478  234  2478 |
+| 12  9     8       | 23   34 234  | 1345  6    247 |
+:-------------------+--------------+---------------:
+| 8   12457 123579  | 5679 6  1457 | 4579  2459 3   |
+| 4   257   25679   | 8    5  3    | 579   259  1   |
+| 7   145   1359    | 59   2  149  | 4589  459  6   |
+:-------------------+--------------+---------------:
+| 139 6     134579  | 357  35 37   | 2     8    47  |
+| 23  278   237     | 4    1  9    | 367   3    5   |
+| 123 1245  12345   | 2356 8  26   | 1346  7    9   |
+'-------------------'--------------'---------------'
+";
+
+    #[test]
+    fn read_pencilmarks_reads_candidates_and_solved_cells() {
+        let board = read_pencilmarks(GRID).unwrap();
+
+        assert_eq!(board.cell(pos!(0, 0)), &cell!(5));
+        assert_eq!(board.cell(pos!(0, 2)), &cell!(? 1, 2, 4, 6));
+        assert_eq!(board.cell(pos!(8, 8)), &cell!(9));
+    }
+    #[test]
+    fn pencilmarks_round_trip() {
+        let board = read_pencilmarks(GRID).unwrap();
+
+        assert_eq!(read_pencilmarks(&write_pencilmarks(&board)), Ok(board));
+    }
+    #[test]
+    fn write_pencilmarks_lines_up_columns() {
+        let text = write_pencilmarks(&board!([[1, { 2, 3 }]]));
+        let lines: Vec<_> = text.lines().collect();
+
+        assert_eq!(lines.len(), 13);
+        assert!(lines[1].starts_with("| 1         23        123456789 |"));
+        assert!(lines.iter().all(|line| line.len() == lines[0].len()));
+    }
+    #[test]
+    fn read_pencilmarks_ignores_line_endings() {
+        let windows = GRID.replace('\n', "\r\n");
+
+        assert_eq!(read_pencilmarks(&windows), read_pencilmarks(GRID));
+        assert_eq!(read_pencilmarks(GRID.trim_end()), read_pencilmarks(GRID));
+    }
+    #[test]
+    fn read_pencilmarks_counts_cells() {
+        assert_eq!(read_pencilmarks("| 1 2 3 |"), Err(ParseError::CellCount(3)));
+    }
+    #[test]
+    fn read_pencilmarks_rejects_zero() {
+        assert_eq!(read_pencilmarks("10"), Err(ParseError::Character('0')));
+    }
+}
@@ -0,0 +1,125 @@
+//! sukaku, the puzzles given as every cell's candidates instead of as givens
+//!
+//! ```text
+//! 123456789.2..5..8.......7..123456789...
+//! ```
+//!
+//! the usual form is one line of 729 places, nine for each cell in row-major order: the place for
+//! a value holds its digit if the value is a candidate and `.` or `0` if it isn't. 81 groups of
+//! digits split by whitespace, such as `129 4 3578 ...`, are read as well. either way a cell with
+//! one candidate is a solved cell
+use crate::{
+    board::{Cell, CellPos},
+    Board, CellVal, ParseError,
+};
+
+/// reads a board from a sukaku in either form
+pub fn read_sukaku(text: &str) -> Result<Board, ParseError> {
+    let words: Vec<_> = text.split_whitespace().collect();
+    let cells = if words.len() == 81 {
+        words
+            .iter()
+            .map(|word| {
+                word.chars()
+                    .map(|c| match c.to_digit(10) {
+                        Some(d @ 1..=9) => Ok(d as u8),
+                        _ => Err(ParseError::Character(c)),
+                    })
+                    .collect()
+            })
+            .collect::<Result<Vec<Vec<u8>>, _>>()?
+    } else {
+        let places: Vec<_> = words.concat().chars().collect();
+        if places.len() != 729 {
+            Err(ParseError::SukakuLength(places.len()))?
+        }
+        places
+            .chunks(9)
+            .map(|cell| {
+                (1..=9)
+                    .zip(cell)
+                    .filter_map(|(val, &c)| match c {
+                        '.' | '0' => None,
+                        _ if c.to_digit(10) == Some(val) => Some(Ok(val as u8)),
+                        _ => Some(Err(ParseError::Character(c))),
+                    })
+                    .collect()
+            })
+            .collect::<Result<Vec<Vec<u8>>, _>>()?
+    };
+    if let Some(i) = cells.iter().position(Vec::is_empty) {
+        Err(ParseError::NoCandidates {
+            row: i / 9,
+            column: i % 9,
+        })?
+    }
+    let rows = cells.chunks(9).map(<[_]>::to_vec).collect();
+    Ok(Board::from_candidates(rows).expect("every cell has candidates from 1-9"))
+}
+
+/// writes a board as a one line sukaku, with a solved cell's value as its only candidate
+pub fn write_sukaku(board: &Board) -> String {
+    CellPos::all()
+        .flat_map(|pos| {
+            let cell = board.cell(pos);
+            CellVal::cell_vals().map(move |val| match cell {
+                Cell::Concrete(concrete) if *concrete == val => val.into_inner().to_string(),
+                Cell::Possibilities(set) if set.contains(val) => val.into_inner().to_string(),
+                Cell::Concrete(_) | Cell::Possibilities(_) => ".".to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn read_sukaku_reads_a_line_of_places() {
+        let mut line = "123456789".repeat(81);
+        line.replace_range(..18, "1.3......000006000");
+
+        assert_eq!(
+            read_sukaku(&format!("{line}.")),
+            Err(ParseError::SukakuLength(730))
+        );
+        let board = read_sukaku(&line).unwrap();
+
+        assert_eq!(board.cell(pos!(0, 0)), &cell!(? 1, 3));
+        assert_eq!(board.cell(pos!(0, 1)), &cell!(6));
+        assert_eq!(board.cell(pos!(0, 2)), &cell!(? 1, 2, 3, 4, 5, 6, 7, 8, 9));
+    }
+    #[test]
+    fn read_sukaku_reads_groups() {
+        let mut groups = vec!["123456789"; 81];
+        groups[0] = "13";
+        groups[80] = "9";
+        let board = read_sukaku(&groups.join(" ")).unwrap();
+
+        assert_eq!(board.cell(pos!(0, 0)), &cell!(? 1, 3));
+        assert_eq!(board.cell(pos!(8, 8)), &cell!(9));
+    }
+    #[test]
+    fn read_sukaku_checks_every_place() {
+        // a 2 in the place for 1
+        let line = format!("2........{}", "123456789".repeat(80));
+        assert_eq!(read_sukaku(&line), Err(ParseError::Character('2')));
+
+        let line = format!("{}.........", "123456789".repeat(80));
+        assert_eq!(
+            read_sukaku(&line),
+            Err(ParseError::NoCandidates { row: 8, column: 8 })
+        );
+    }
+    #[test]
+    fn sukaku_round_trips() {
+        let board = board!([[1, { 2, 3 }, { 4, 5, 6 }]]);
+        let line = write_sukaku(&board);
+
+        assert_eq!(line.len(), 729);
+        assert!(line.starts_with("1.........23.........456..."));
+        assert_eq!(read_sukaku(&line), Ok(board));
+    }
+}
@@ -0,0 +1,127 @@
+//! drawing boards and their analyses as SVG images
+
+use crate::{Board, HeatMap};
+
+/// the width and height of a cell in pixels
+const CELL: usize = 60;
+
+/// a grid where each cell is shaded by how uncertain its value is, from white for a value every
+/// sample agreed on to red for one where every value was as likely
+///
+/// each value a cell took is written in its spot of the cell (1 top left to 9 bottom right), faded
+/// by how rarely it came up
+pub fn heat_map_svg(map: &HeatMap) -> String {
+    let size = CELL * 9;
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{size}\" height=\"{size}\" viewBox=\"0 0 {size} {size}\">\n"
+    );
+    let max_entropy = 9f64.log2();
+    for row in 0..9 {
+        for column in 0..9 {
+            let (x, y) = (column * CELL, row * CELL);
+            let shade = (255.0 * (1.0 - map.entropy(row, column) / max_entropy)).round() as u8;
+            out += &format!(
+                "  <rect x=\"{x}\" y=\"{y}\" width=\"{CELL}\" height=\"{CELL}\" fill=\"rgb(255,{shade},{shade})\" stroke=\"#999\"/>\n"
+            );
+            for value in 1..=9 {
+                let probability = map.probability(row, column, value);
+                if probability == 0.0 {
+                    continue;
+                }
+                let x = x + (value - 1) % 3 * CELL / 3 + CELL / 6;
+                let y = y + (value - 1) / 3 * CELL / 3 + CELL / 6;
+                out += &format!(
+                    "  <text x=\"{x}\" y=\"{y}\" font-size=\"14\" text-anchor=\"middle\" dominant-baseline=\"central\" fill-opacity=\"{probability:.2}\">{value}</text>\n"
+                );
+            }
+        }
+    }
+    out + &borders() + "</svg>\n"
+}
+
+/// the board's values, with the cells `givens` marks in bold black and the rest, which the solver
+/// filled in, in a lighter blue
+///
+/// drawing a solution with the givens of its puzzle shows what solving added, and drawing a
+/// puzzle with its own `Board::givens` shows every value as given
+pub fn board_svg(board: &Board, givens: &[Vec<bool>]) -> String {
+    let size = CELL * 9;
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{size}\" height=\"{size}\" viewBox=\"0 0 {size} {size}\">\n"
+    );
+    let values: [[Option<usize>; 9]; 9] = board.clone().into();
+    for (row, (values, givens)) in values.iter().zip(givens).enumerate() {
+        for (column, (value, &given)) in values.iter().zip(givens).enumerate() {
+            let (x, y) = (column * CELL, row * CELL);
+            out += &format!(
+                "  <rect x=\"{x}\" y=\"{y}\" width=\"{CELL}\" height=\"{CELL}\" fill=\"white\" stroke=\"#999\"/>\n"
+            );
+            let Some(value) = value else {
+                continue;
+            };
+            let (weight, fill) = match given {
+                true => ("bold", "black"),
+                false => ("normal", "#36c"),
+            };
+            let (x, y) = (x + CELL / 2, y + CELL / 2);
+            out += &format!(
+                "  <text x=\"{x}\" y=\"{y}\" font-size=\"32\" font-weight=\"{weight}\" fill=\"{fill}\" text-anchor=\"middle\" dominant-baseline=\"central\">{value}</text>\n"
+            );
+        }
+    }
+    out + &borders() + "</svg>\n"
+}
+
+/// the thick lines around the houses, which go on top of the cells
+fn borders() -> String {
+    let size = CELL * 9;
+    let mut out = String::new();
+    for i in (0..=9).step_by(3) {
+        let at = i * CELL;
+        out += &format!(
+            "  <line x1=\"{at}\" y1=\"0\" x2=\"{at}\" y2=\"{size}\" stroke=\"black\" stroke-width=\"3\"/>\n"
+        );
+        out += &format!(
+            "  <line x1=\"0\" y1=\"{at}\" x2=\"{size}\" y2=\"{at}\" stroke=\"black\" stroke-width=\"3\"/>\n"
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn heat_map_svg_shades_uncertain_cells() {
+        let mut map = HeatMap {
+            samples: 2,
+            counts: [[[0; 9]; 9]; 9],
+        };
+        for row in &mut map.counts {
+            for cell in row {
+                cell[0] = 2;
+            }
+        }
+        map.counts[0][0] = [1, 1, 0, 0, 0, 0, 0, 0, 0];
+        let svg = heat_map_svg(&map);
+
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<rect").count(), 81);
+        assert_eq!(svg.matches("fill=\"rgb(255,255,255)\"").count(), 80);
+        assert!(svg.contains("fill-opacity=\"0.50\">2</text>"));
+    }
+    #[test]
+    fn board_svg_sets_solved_values_apart() {
+        let puzzle = board!([[1, ?, 3]]);
+        let mut solution = puzzle.clone();
+        *solution.mut_cell(pos!(0, 1)) = cell!(2);
+        let svg = board_svg(&solution, &puzzle.givens());
+
+        assert_eq!(svg.matches("<rect").count(), 81);
+        assert_eq!(svg.matches("<text").count(), 3);
+        assert!(svg.contains("font-weight=\"bold\" fill=\"black\" text-anchor=\"middle\" dominant-baseline=\"central\">3</text>"));
+        assert!(svg.contains("font-weight=\"normal\" fill=\"#36c\" text-anchor=\"middle\" dominant-baseline=\"central\">2</text>"));
+    }
+}
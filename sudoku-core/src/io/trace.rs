@@ -0,0 +1,155 @@
+//! solve traces, the board a solve started from and what each step changed
+//!
+//! ```text
+//! solve-trace 1
+//! start <81 cells>
+//! step <technique> <cell 0-80>=<cell> ...   (one per step, in order)
+//! ```
+//!
+//! a cell is a digit for a value or `?` followed by the candidates left, the same as in a saved
+//! `SolverState`. a step only lists the cells it changed, with dashes for spaces in the
+//! technique's name, so a trace grows with how much the solve did rather than with a whole
+//! board of candidates per step
+use crate::{
+    board::{Candidates, Cell, CellPos},
+    strategy_names, Board, CellVal, ParseError, SolveStep,
+};
+use std::fmt::Write;
+
+const HEADER: &str = "solve-trace 1";
+
+fn write_cell(text: &mut String, cell: &Cell) {
+    match cell {
+        Cell::Concrete(val) => {
+            let _ = write!(text, "{}", val.into_inner());
+        }
+        Cell::Possibilities(set) => {
+            let mut vals: Vec<_> = set.iter().map(CellVal::into_inner).collect();
+            vals.sort();
+            text.push('?');
+            text.extend(vals.iter().map(|val| char::from(b'0' + *val as u8)));
+        }
+    }
+}
+
+fn read_cell(text: &str) -> Result<Cell, ParseError> {
+    let (open, digits) = match text.strip_prefix('?') {
+        Some(digits) => (true, digits),
+        None => (false, text),
+    };
+    let vals = digits
+        .chars()
+        .map(|c| {
+            c.to_digit(10)
+                .and_then(|d| CellVal::new(d as usize).ok())
+                .ok_or(ParseError::Character(c))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    match (open, &vals[..]) {
+        (true, _) => Ok(Cell::Possibilities(
+            vals.into_iter().collect::<Candidates>(),
+        )),
+        (false, &[val]) => Ok(Cell::Concrete(val)),
+        (false, _) => Err(ParseError::Trace),
+    }
+}
+
+/// a trace of a solve from `start` through `steps`, such as those of `Board::solve_steps`
+pub fn write_trace(start: &Board, steps: &[SolveStep]) -> String {
+    let mut text = format!("{HEADER}\nstart");
+    for pos in CellPos::all() {
+        text.push(' ');
+        write_cell(&mut text, start.cell(pos));
+    }
+    text.push('\n');
+    let mut before = start;
+    for step in steps {
+        text.push_str("step ");
+        text.push_str(&step.technique.replace(' ', "-"));
+        for pos in CellPos::all() {
+            let cell = step.board_after.cell(pos);
+            if cell != before.cell(pos) {
+                let _ = write!(text, " {}=", pos.index());
+                write_cell(&mut text, cell);
+            }
+        }
+        text.push('\n');
+        before = &step.board_after;
+    }
+    text
+}
+
+/// a technique and the board after it
+type Step = (&'static str, Board);
+
+/// the board a trace starts from, and the technique and board after each of its steps
+pub fn replay_trace(text: &str) -> Result<(Board, Vec<Step>), ParseError> {
+    let mut lines = super::lines(text).filter(|line| !line.trim().is_empty());
+    if lines.next().map(str::trim) != Some(HEADER) {
+        Err(ParseError::Trace)?
+    }
+    let mut words = lines.next().ok_or(ParseError::Trace)?.split_whitespace();
+    if words.next() != Some("start") {
+        Err(ParseError::Trace)?
+    }
+    let cells = words.map(read_cell).collect::<Result<Vec<_>, _>>()?;
+    if cells.len() != 81 {
+        Err(ParseError::CellCount(cells.len()))?
+    }
+    let start: Board = CellPos::all().zip(cells).collect();
+    let mut board = start.clone();
+    let mut steps = vec![];
+    for line in lines {
+        let mut words = line.split_whitespace();
+        if words.next() != Some("step") {
+            Err(ParseError::Trace)?
+        }
+        let name = words.next().ok_or(ParseError::Trace)?;
+        let technique = std::iter::once("singles")
+            .chain(strategy_names())
+            .find(|known| known.replace(' ', "-") == name)
+            .ok_or(ParseError::Trace)?;
+        for change in words {
+            let (index, cell) = change.split_once('=').ok_or(ParseError::Trace)?;
+            let pos = index
+                .parse()
+                .ok()
+                .and_then(|index| CellPos::all().nth(index))
+                .ok_or(ParseError::Trace)?;
+            *board.mut_cell(pos) = read_cell(cell)?;
+        }
+        steps.push((technique, board.clone()));
+    }
+    Ok((start, steps))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn traces_replay_every_step() {
+        let start = board!([[1, 2, 3, 4, 5, 6, 7, 8]]);
+        let steps: Vec<_> = start.clone().solve_steps().collect();
+        let text = write_trace(&start, &steps);
+        let (replayed_start, replayed) = replay_trace(&text).unwrap();
+
+        assert_eq!(replayed_start, start);
+        assert_eq!(replayed.len(), steps.len());
+        for ((technique, board), step) in replayed.iter().zip(&steps) {
+            assert_eq!(*technique, step.technique);
+            assert_eq!(board, &step.board_after);
+        }
+        // the first row check only fills in the 9, so that is all its step lists
+        assert_eq!(text.lines().nth(2), Some("step singles 8=9"));
+    }
+    #[test]
+    fn replay_rejects_other_text() {
+        assert_eq!(replay_trace("start"), Err(ParseError::Trace));
+        let text = write_trace(&board!([]), &[]).replace("start", "begin");
+        assert_eq!(replay_trace(&text), Err(ParseError::Trace));
+        let text = format!("{}step magic 0=1\n", write_trace(&board!([]), &[]));
+        assert_eq!(replay_trace(&text), Err(ParseError::Trace));
+    }
+}
@@ -0,0 +1,65 @@
+//! the steps the solver repeats the most, one at a time, so each can be timed on its own
+//!
+//! only built with the `kernels` feature, which `benches/propagation.rs` needs. none of this is
+//! needed to solve a board, and it changes whenever the solver's insides do
+use crate::{
+    board::{Candidates, Cell},
+    Board, CellPos, CellVal, Propagation, Unit, UpdateError,
+};
+
+/// takes `vals` out of the candidates of the cell at `pos`, leaving a filled in cell alone
+///
+/// fails with `UpdateError::Impossible` if the cell is left with none
+pub fn remove_possibilities(
+    board: &mut Board,
+    pos: CellPos,
+    vals: &[CellVal],
+) -> Result<(), UpdateError> {
+    let vals: Candidates = vals.iter().copied().collect();
+    let cell = board.cell(pos).remove_possibilities(&vals)?;
+    *board.mut_cell(pos) = cell;
+    Ok(())
+}
+
+/// the checks `Board::propagate` makes on every row, column, and house, on just `unit`
+pub fn check_unit(board: &mut Board, unit: Unit) -> Result<(), UpdateError> {
+    board.get_cells(unit.cells().collect()).check_and_update()
+}
+
+/// the first guess the backtracking search would make, the smallest candidate of the cell with
+/// the fewest, propagated through the board
+///
+/// `None` if there is no open cell to guess in
+pub fn guess_and_propagate(board: &Board) -> Option<Propagation> {
+    let (pos, vals) = board.branch(None)?;
+    let mut guessed = board.clone();
+    *guessed.mut_cell(pos) = Cell::Concrete(vals.into_iter().min()?);
+    Some(guessed.propagate())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn remove_possibilities_keeps_the_rest() {
+        let mut board = board!([[{ 1, 2, 3 }, 4]]);
+        remove_possibilities(&mut board, pos!(0, 0), &[cell_val!(1), cell_val!(4)]).unwrap();
+        remove_possibilities(&mut board, pos!(0, 1), &[cell_val!(4)]).unwrap();
+
+        assert_eq!(board, board!([[{ 2, 3 }, 4]]));
+        assert_eq!(
+            remove_possibilities(&mut board, pos!(0, 0), &[cell_val!(2), cell_val!(3)]),
+            Err(UpdateError::Impossible)
+        );
+    }
+    #[test]
+    fn guess_and_propagate_guesses_the_smallest_value() {
+        let propagation = guess_and_propagate(&board!([[{ 5, 7 }]])).unwrap();
+
+        assert_eq!(propagation.board.cell(pos!(0, 0)), &cell!(5));
+        assert!(!propagation.board.cell(pos!(0, 1)).is_possible(cell_val!(5)));
+        assert_eq!(propagation.contradiction, None);
+    }
+}
@@ -0,0 +1,67 @@
+//! solving, grading, and generating sudoku, as the library behind the `sudoku` binary
+//!
+//! it doesn't read or write any files, so it can be embedded without the binary's csv and cli
+//! dependencies. where to start for what the binary does:
+//!
+//! - reading puzzles: `Board::build` and `Board::build_wide` take rows of cells, `str::parse`
+//!   the one line form most collections use, and `Board::from_candidates` a sukaku's candidates.
+//!   `io` reads and writes the other formats, including boards of any size in any alphabet with
+//!   `io::SymbolMap`
+//! - solving: `Board::solve`, or `Solver::builder()` and `Solver::by_name` to pick a
+//!   `SolverBackend` from configuration. `SolveOptions` holds the variants (a `RuleSet::preset`,
+//!   or `Variant`s with their `Cage`s, `Arrow`s, `Sandwich`es, `Inequality` signs, and `Parity`
+//!   marks), the timeout and `CancelToken`, the value order, and profiling. a stopped search can
+//!   be saved and picked up again with `SolverState`
+//! - other sizes: `Board` is generic over the shape of its houses, so `Board<Size<4, 4>>` is a
+//!   hexadoku and `Board<Size<1, 5>>` a Latin square, with the same techniques and backends
+//! - grading: `Board::rate`, `Board::is_logic_solvable`, `Board::hint`, `Board::explain`, and
+//!   `Board::solve_steps` for every step, which `io::write_trace` keeps as a trace. with the
+//!   `difficulty-model` feature, `Board::difficulty` scores a puzzle by the fitted model.
+//!   `Setter` regrades a puzzle as it is being set
+//! - checking a puzzle: `Board::count_solutions`, `Board::given_symmetries` and
+//!   `Board::given_density`, and `Board::heat_map` for one with more than one solution
+//! - generating: `Board::generate` with `GeneratorOptions`, `Board::from_solution` for a chosen
+//!   answer, `Board::generate_sized` for other sizes, and `Board::disguise` for an isomorph
+//! - collections: `Board::canonical_form` to spot isomorphs, and `store::SolutionStore` to keep
+//!   solutions and grades by it
+//! - several boards sharing cells, such as a samurai: `composite::Composite`
+//! - reporting: `events` for a run's events, `metrics` for Prometheus counters, and
+//!   `capabilities` for what the build supports
+//!
+//! the `schema`, `verify`, and `kernels` features add the modules of the same names, and
+//! `strategy-fixtures` adds `fixtures`. a panic in the solver comes back as an internal error
+//! in a release build and is let through with debug assertions on, unless `PANIC_VAR` says
+//! otherwise
+mod board;
+pub mod capabilities;
+pub mod clock;
+pub mod composite;
+mod errors;
+pub mod events;
+#[cfg(feature = "strategy-fixtures")]
+pub mod fixtures;
+pub mod io;
+#[cfg(feature = "kernels")]
+pub mod kernels;
+pub mod metrics;
+#[cfg(feature = "schema")]
+pub mod schema;
+mod solve;
+pub mod store;
+mod strategy;
+#[cfg(feature = "verify")]
+pub mod verify;
+pub use board::{
+    BitSet, Board, CandidateSet, CanonicalForm, CellPos, CellVal, Classic, Density, SetBackend,
+    Shape, Size, SolvedBoard, Symmetry, Unit, WeightedCell,
+};
+pub use errors::{BuildError, ParseError, SolveError, UpdateError, PANIC_VAR};
+pub use solve::{
+    Analysis, Arrow, BoardState, Cage, CancelToken, DigStrategy, Generated, GeneratorOptions,
+    GivenDiff, HeatMap, Inequality, NoBackend, Parity, Propagation, Rating, Reanalysis, RuleSet,
+    Sandwich, Setter, SolveOptions, SolveStats, SolveStep, SolveSteps, Solver, SolverBackend,
+    SolverBuilder, SolverState, Tier, ValueOrder, Variant, PROFILED_UNITS,
+};
+#[cfg(feature = "difficulty-model")]
+pub use solve::{Difficulty, Model};
+pub use strategy::strategy_names;
@@ -0,0 +1,180 @@
+//! counters and histograms of solves in the Prometheus text format
+//!
+//! `Metrics` is an `EventSink`, so it can be handed the same events as any other sink and
+//! rendered with `to_prometheus` for a `/metrics` endpoint or a node exporter textfile
+
+use crate::{
+    events::{Event, EventSink},
+    UpdateError,
+};
+use std::{fmt::Write, io};
+
+/// counts of values that fell at or under each bucket's upper bound
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    /// upper bounds, smallest first, with an implied `+Inf` after the last
+    bounds: Vec<f64>,
+    /// how many values fell in each bucket, not counting the ones before it
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    pub fn new(bounds: &[f64]) -> Self {
+        Histogram {
+            bounds: bounds.to_vec(),
+            counts: vec![0; bounds.len() + 1],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+    pub fn observe(&mut self, val: f64) {
+        let bucket = self
+            .bounds
+            .iter()
+            .position(|&bound| val <= bound)
+            .unwrap_or(self.bounds.len());
+        self.counts[bucket] += 1;
+        self.sum += val;
+        self.count += 1;
+    }
+    fn write(&self, out: &mut String, name: &str, help: &str) {
+        let _ = writeln!(out, "# HELP {name} {help}");
+        let _ = writeln!(out, "# TYPE {name} histogram");
+        let mut total = 0;
+        let bounds = self.bounds.iter().map(f64::to_string);
+        for (bound, count) in bounds.chain(["+Inf".to_string()]).zip(&self.counts) {
+            total += count;
+            let _ = writeln!(out, "{name}_bucket{{le=\"{bound}\"}} {total}");
+        }
+        let _ = writeln!(out, "{name}_sum {}", self.sum);
+        let _ = writeln!(out, "{name}_count {}", self.count);
+    }
+}
+
+/// what a service has solved, tallied from its events
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metrics {
+    pub solves: u64,
+    /// runs that ended in an error, including stopped solves that didn't write a partial board
+    pub failures: u64,
+    /// solves stopped by `SolveOptions::timeout`
+    pub timeouts: u64,
+    /// how long each solve took, in seconds
+    pub latency: Histogram,
+    /// how many guesses each solve made, which stands in for the nodes of the search tree
+    pub guesses: Histogram,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Metrics {
+            solves: 0,
+            failures: 0,
+            timeouts: 0,
+            latency: Histogram::new(&[0.001, 0.01, 0.1, 1.0, 10.0]),
+            guesses: Histogram::new(&[0.0, 10.0, 100.0, 1000.0, 10000.0]),
+        }
+    }
+}
+
+impl Metrics {
+    /// every metric in the Prometheus text exposition format, all prefixed with `sudoku_`
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        let counters = [
+            ("sudoku_solves_total", "puzzles solved", self.solves),
+            ("sudoku_failures_total", "runs that failed", self.failures),
+            (
+                "sudoku_timeouts_total",
+                "solves that timed out",
+                self.timeouts,
+            ),
+        ];
+        for (name, help, val) in counters {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} counter");
+            let _ = writeln!(out, "{name} {val}");
+        }
+        self.latency
+            .write(&mut out, "sudoku_solve_seconds", "how long each solve took");
+        self.guesses.write(
+            &mut out,
+            "sudoku_solve_guesses",
+            "how many guesses each solve made",
+        );
+        out
+    }
+}
+
+impl EventSink for Metrics {
+    fn emit(&mut self, event: &Event) -> io::Result<()> {
+        match event {
+            Event::Solved { stats, .. } => {
+                self.solves += 1;
+                self.latency.observe(stats.elapsed.as_secs_f64());
+                self.guesses.observe(stats.guesses as f64);
+            }
+            Event::Stopped { why, .. } if *why == UpdateError::Timeout => self.timeouts += 1,
+            Event::Failed { .. } => self.failures += 1,
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::SolveStats;
+    use std::time::Duration;
+
+    #[test]
+    fn histograms_are_cumulative() {
+        let mut histogram = Histogram::new(&[1.0, 10.0]);
+        for val in [0.5, 2.0, 3.0, 50.0] {
+            histogram.observe(val);
+        }
+        let mut out = String::new();
+        histogram.write(&mut out, "x", "a test");
+
+        assert_eq!(
+            out,
+            concat!(
+                "# HELP x a test\n# TYPE x histogram\n",
+                "x_bucket{le=\"1\"} 1\nx_bucket{le=\"10\"} 3\nx_bucket{le=\"+Inf\"} 4\n",
+                "x_sum 55.5\nx_count 4\n"
+            )
+        );
+    }
+    #[test]
+    fn metrics_count_events() {
+        let mut metrics = Metrics::default();
+        let solved = Event::Solved {
+            input: "a.csv".to_string(),
+            stats: SolveStats {
+                guesses: 12,
+                elapsed: Duration::from_millis(5),
+                ..Default::default()
+            },
+            solution: String::new(),
+        };
+        let stopped = Event::Stopped {
+            input: "b.csv".to_string(),
+            why: UpdateError::Timeout,
+            best: String::new(),
+        };
+        for event in [&solved, &solved, &stopped] {
+            metrics.emit(event).unwrap();
+        }
+
+        assert_eq!(
+            (metrics.solves, metrics.timeouts, metrics.failures),
+            (2, 1, 0)
+        );
+        let text = metrics.to_prometheus();
+        assert!(text.contains("sudoku_solves_total 2\n"));
+        assert!(text.contains("sudoku_solve_guesses_bucket{le=\"100\"} 2\n"));
+    }
+}
@@ -0,0 +1,132 @@
+//! the versioned JSON documents other tools can hand puzzles to this crate in
+//!
+//! every document has a `version`. `read` migrates a document of any version this crate knows
+//! one version at a time up to `CURRENT` before checking it, so callers only ever see `Puzzle`,
+//! the newest types. a new version gets a module next to `v1` and a step at the end of
+//! `MIGRATIONS` that turns a document of the version before it into one of the new version, and
+//! the version before it gets its types at the end of `OLD_TYPES`
+pub mod v1;
+pub mod v2;
+
+use serde_json::Value;
+use thiserror::Error;
+
+/// the version `read` migrates every document to
+pub const CURRENT: u64 = v2::VERSION;
+
+/// the types of the `CURRENT` version
+pub type Puzzle = v2::Puzzle;
+
+/// the step from each old version to the one after it, starting with version 1
+const MIGRATIONS: &[fn(Value) -> Value] = &[v2::from_v1];
+
+/// reads a document of each old version as that version's types, starting with version 1, so a
+/// problem the types catch keeps its line and column before the document is migrated
+const OLD_TYPES: &[fn(&str) -> serde_json::Result<()>] =
+    &[|text| serde_json::from_str::<v1::Puzzle>(text).map(drop)];
+
+/// why a document doesn't match the schema
+#[derive(Error, Debug, PartialEq, Eq, Clone)]
+pub enum SchemaError {
+    /// not JSON, or not the shape of the version it claims, with the line and column of the
+    /// problem
+    #[error("{0}")]
+    Json(String),
+    #[error("missing the `version` field")]
+    NoVersion,
+    #[error("version {0} isn't one this build reads (1 to {CURRENT})")]
+    Version(u64),
+    /// a field holds a value its type allows but the schema doesn't, such as a short grid
+    #[error("{field}: {message}")]
+    Field {
+        field: &'static str,
+        message: String,
+    },
+}
+
+impl From<serde_json::Error> for SchemaError {
+    fn from(why: serde_json::Error) -> Self {
+        SchemaError::Json(why.to_string())
+    }
+}
+
+/// the document in `text`, migrated to `CURRENT` and checked
+pub fn read(text: &str) -> Result<Puzzle, SchemaError> {
+    let puzzle = parse(text)?;
+    if let Some(why) = puzzle.check().into_iter().next() {
+        Err(why)?
+    }
+    Ok(puzzle)
+}
+
+/// every problem with the document in `text`, empty if it is valid
+///
+/// a document that can't be read as a `Puzzle` has just the one problem that stopped it
+pub fn validate(text: &str) -> Vec<SchemaError> {
+    match parse(text) {
+        Ok(puzzle) => puzzle.check(),
+        Err(why) => vec![why],
+    }
+}
+
+/// the document in `text`, migrated to `CURRENT` but not checked
+fn parse(text: &str) -> Result<Puzzle, SchemaError> {
+    let mut doc: Value = serde_json::from_str(text)?;
+    let version = match doc.get("version") {
+        None => Err(SchemaError::NoVersion)?,
+        Some(version) => version.as_u64().ok_or_else(|| SchemaError::Field {
+            field: "version",
+            message: format!("expected a whole number but found {version}"),
+        })?,
+    };
+    if !(1..=CURRENT).contains(&version) {
+        Err(SchemaError::Version(version))?
+    }
+    if version == CURRENT {
+        // straight from the text, so type errors keep their line and column
+        return Ok(serde_json::from_str(text)?);
+    }
+    OLD_TYPES[version as usize - 1](text)?;
+    for migrate in &MIGRATIONS[version as usize - 1..] {
+        doc = migrate(doc);
+    }
+    Ok(serde_json::from_value(doc)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn read_checks_the_version() {
+        let grid = ".".repeat(81);
+
+        assert!(read(&format!("{{\"version\": 1, \"grid\": \"{grid}\"}}")).is_ok());
+        assert_eq!(read("{\"grid\": \"\"}"), Err(SchemaError::NoVersion));
+        assert_eq!(
+            read("{\"version\": 3, \"grid\": \"\"}"),
+            Err(SchemaError::Version(3))
+        );
+        assert_eq!(
+            read("{\"version\": \"1\"}").unwrap_err().to_string(),
+            "version: expected a whole number but found \"1\""
+        );
+        assert_eq!(MIGRATIONS.len() as u64, CURRENT - 1);
+        assert_eq!(OLD_TYPES.len() as u64, CURRENT - 1);
+    }
+    #[test]
+    fn errors_point_at_the_problem() {
+        let problems = validate("{\"version\": 1,\n \"grid\": \"\", \"author\": \"me\"}");
+
+        assert_eq!(problems.len(), 1);
+        assert!(matches!(&problems[0], SchemaError::Json(why)
+            if why.contains("unknown field `author`") && why.contains("line 2")));
+        assert_eq!(
+            validate("{\"version\": 1, \"grid\": \"12\"}"),
+            [SchemaError::Field {
+                field: "grid",
+                message: "expected 81 cells but found 2".to_string()
+            }]
+        );
+    }
+}
@@ -0,0 +1,188 @@
+//! the first version of the puzzle document
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "grid": "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79",
+//!   "name": "wikipedia",
+//!   "source": "https://en.wikipedia.org/wiki/Sudoku",
+//!   "solution": "534678912672195348198342567859761423426853791713924856961537284287419635345286179"
+//! }
+//! ```
+//!
+//! only `version` and `grid` are required, and any other key is an error. a `"rules"` key names
+//! one of `RuleSet::PRESETS` for puzzles with more than the classic rules, such as `"x"`
+use super::SchemaError;
+use crate::{Board, RuleSet};
+use serde::{Deserialize, Serialize};
+
+pub const VERSION: u64 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Puzzle {
+    /// always `VERSION`
+    pub version: u64,
+    /// the 81 cells row by row, a digit for a given and `.` or `0` for a blank
+    pub grid: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// where the puzzle came from, such as a book or a url
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// the 81 digits of the answer, if it is known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solution: Option<String>,
+    /// the preset the puzzle is played under, classic if there is none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<String>,
+}
+
+/// the value of each cell of `text`, or why `field` can't be a grid
+pub(super) fn cells(
+    field: &'static str,
+    text: &str,
+    blanks: bool,
+) -> Result<Vec<Option<u8>>, SchemaError> {
+    let problem = |message: String| SchemaError::Field { field, message };
+    let count = text.chars().count();
+    if count != 81 {
+        Err(problem(format!("expected 81 cells but found {count}")))?
+    }
+    text.chars()
+        .enumerate()
+        .map(|(i, c)| match c {
+            '1'..='9' => Ok(Some(c as u8 - b'0')),
+            '.' | '0' if blanks => Ok(None),
+            _ => Err(problem(format!(
+                "unexpected character {c:?} in r{}c{}",
+                i / 9 + 1,
+                i % 9 + 1
+            ))),
+        })
+        .collect()
+}
+
+impl Puzzle {
+    /// the givens of `board`, without a name, source, or solution
+    pub fn new(board: &Board) -> Self {
+        let values: [[Option<usize>; 9]; 9] = board.clone().into();
+        let grid = values
+            .iter()
+            .flatten()
+            .map(|val| val.map_or('.', |val| char::from(b'0' + val as u8)))
+            .collect();
+        Puzzle {
+            version: VERSION,
+            grid,
+            name: None,
+            source: None,
+            solution: None,
+            rules: None,
+        }
+    }
+    /// every way the fields break the rules the types can't, empty if there are none
+    pub fn check(&self) -> Vec<SchemaError> {
+        let mut problems = vec![];
+        let grid = cells("grid", &self.grid, true).map_err(|why| problems.push(why));
+        let solution = match &self.solution {
+            Some(solution) => cells("solution", solution, false)
+                .map(Some)
+                .map_err(|why| problems.push(why)),
+            None => Ok(None),
+        };
+        if let (Ok(grid), Ok(Some(solution))) = (grid, solution) {
+            for (i, (given, val)) in grid.iter().zip(&solution).enumerate() {
+                if let (Some(given), Some(val)) = (given, val) {
+                    if given != val {
+                        problems.push(SchemaError::Field {
+                            field: "solution",
+                            message: format!(
+                                "r{}c{} is {val} but the grid gives {given}",
+                                i / 9 + 1,
+                                i % 9 + 1
+                            ),
+                        });
+                    }
+                }
+            }
+        }
+        if let Err(why) = self.rules() {
+            problems.push(why);
+        }
+        problems
+    }
+    /// the preset named by `rules`, or the classic rules if there isn't one
+    ///
+    /// a killer preset has no cages, since the schema doesn't hold them
+    pub fn rules(&self) -> Result<RuleSet, SchemaError> {
+        RuleSet::preset(self.rules.as_deref().unwrap_or("classic")).map_err(|why| {
+            SchemaError::Field {
+                field: "rules",
+                message: why.to_string(),
+            }
+        })
+    }
+    /// the board of `grid`, failing with the first problem `check` finds
+    pub fn board(&self) -> Result<Board, SchemaError> {
+        if let Some(why) = self.check().into_iter().next() {
+            Err(why)?
+        }
+        let cells = cells("grid", &self.grid, true)?;
+        let rows = cells.chunks(9).map(<[_]>::to_vec).collect();
+        Ok(Board::build(rows).expect("a checked grid has 81 cells of 1-9"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn check_lists_every_problem() {
+        let mut puzzle = Puzzle::new(&Board::build(vec![vec![None; 9]; 9]).unwrap());
+        puzzle.grid.replace_range(..2, "1x");
+        puzzle.solution = Some("2".repeat(80));
+
+        assert_eq!(
+            puzzle.check(),
+            [
+                SchemaError::Field {
+                    field: "grid",
+                    message: "unexpected character 'x' in r1c2".to_string()
+                },
+                SchemaError::Field {
+                    field: "solution",
+                    message: "expected 81 cells but found 80".to_string()
+                },
+            ]
+        );
+    }
+    #[test]
+    fn the_solution_has_to_agree_with_the_grid() {
+        let mut puzzle = Puzzle::new(&Board::build(vec![vec![None; 9]; 9]).unwrap());
+        puzzle.grid.replace_range(..1, "5");
+        puzzle.solution = Some("4".repeat(81));
+
+        assert_eq!(
+            puzzle.board().unwrap_err().to_string(),
+            "solution: r1c1 is 4 but the grid gives 5"
+        );
+        puzzle.solution = None;
+        assert_eq!(Puzzle::new(&puzzle.board().unwrap()), puzzle);
+    }
+    #[test]
+    fn rules_name_a_preset() {
+        let mut puzzle = Puzzle::new(&Board::build(vec![vec![None; 9]; 9]).unwrap());
+        assert_eq!(puzzle.rules().unwrap().name(), "classic");
+
+        puzzle.rules = Some("x".to_string());
+        assert_eq!(puzzle.rules().unwrap(), RuleSet::preset("x").unwrap());
+
+        puzzle.rules = Some("jigsaw".to_string());
+        assert!(matches!(
+            &puzzle.check()[..],
+            [SchemaError::Field { field: "rules", .. }]
+        ));
+    }
+}
@@ -0,0 +1,337 @@
+//! the second version of the puzzle document, which can also lay out a composite puzzle
+//!
+//! ```json
+//! {
+//!   "version": 2,
+//!   "boards": [
+//!     { "row": 0, "column": 0, "grid": "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79" },
+//!     { "row": 6, "column": 6, "grid": "................................................................................." }
+//!   ],
+//!   "name": "two corners"
+//! }
+//! ```
+//!
+//! a puzzle has either a `grid`, as in version 1, or `boards`, each a grid with its top left cell
+//! at `row` and `column` of one larger sheet, sharing the cells where they overlap (see
+//! `Composite`). a `solution` is only for a `grid`, and the boards of a composite are played
+//! under the classic rules
+use super::{v1::cells, SchemaError};
+use crate::{composite::Composite, Board, RuleSet};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub const VERSION: u64 = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Puzzle {
+    /// always `VERSION`
+    pub version: u64,
+    /// the 81 cells row by row, a digit for a given and `.` or `0` for a blank, unless the
+    /// puzzle has `boards`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grid: Option<String>,
+    /// the boards of a composite puzzle, in the order they are added to it
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub boards: Vec<PlacedBoard>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// where the puzzle came from, such as a book or a url
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// the 81 digits of the answer to `grid`, if it is known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solution: Option<String>,
+    /// the preset the puzzle is played under, classic if there is none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<String>,
+}
+
+/// one board of a composite, with its top left cell at `row` and `column` of the sheet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PlacedBoard {
+    pub row: usize,
+    pub column: usize,
+    /// the board's 81 cells, the same as a puzzle's `grid`
+    pub grid: String,
+}
+
+/// a version 1 document as version 2, which only adds fields
+pub(super) fn from_v1(mut doc: Value) -> Value {
+    doc["version"] = VERSION.into();
+    doc
+}
+
+/// the 81 cells of `board` row by row, `.` for a blank
+fn grid(board: &Board) -> String {
+    let values: [[Option<usize>; 9]; 9] = board.clone().into();
+    values
+        .iter()
+        .flatten()
+        .map(|val| val.map_or('.', |val| char::from(b'0' + val as u8)))
+        .collect()
+}
+
+/// the board of a checked grid
+fn board(field: &'static str, text: &str) -> Result<Board, SchemaError> {
+    let cells = cells(field, text, true)?;
+    let rows = cells.chunks(9).map(<[_]>::to_vec).collect();
+    Ok(Board::build(rows).expect("a checked grid has 81 cells of 1-9"))
+}
+
+impl Puzzle {
+    /// the givens of `board`, without a name, source, or solution
+    pub fn new(board: &Board) -> Self {
+        Puzzle {
+            grid: Some(grid(board)),
+            ..Puzzle::empty()
+        }
+    }
+    /// the boards of `composite` and where they are, without a name or source
+    pub fn from_composite(composite: &Composite) -> Self {
+        let boards = composite
+            .boards()
+            .map(|((row, column), board)| PlacedBoard {
+                row,
+                column,
+                grid: grid(board),
+            })
+            .collect();
+        Puzzle {
+            boards,
+            ..Puzzle::empty()
+        }
+    }
+    fn empty() -> Self {
+        Puzzle {
+            version: VERSION,
+            grid: None,
+            boards: vec![],
+            name: None,
+            source: None,
+            solution: None,
+            rules: None,
+        }
+    }
+    /// every way the fields break the rules the types can't, empty if there are none
+    pub fn check(&self) -> Vec<SchemaError> {
+        let mut problems = vec![];
+        let problem = |field, message: &str| SchemaError::Field {
+            field,
+            message: message.to_string(),
+        };
+        match (&self.grid, self.boards.is_empty()) {
+            (None, true) => problems.push(problem("grid", "a puzzle needs a grid or boards")),
+            (Some(_), false) => {
+                problems.push(problem("boards", "a puzzle has a grid or boards, not both"))
+            }
+            (Some(grid), true) => self.check_grid(grid, &mut problems),
+            (None, false) => self.check_boards(&mut problems),
+        }
+        if let Err(why) = self.rules() {
+            problems.push(why);
+        }
+        problems
+    }
+    /// the problems with a puzzle on one board, as in version 1
+    fn check_grid(&self, grid: &str, problems: &mut Vec<SchemaError>) {
+        let grid = cells("grid", grid, true).map_err(|why| problems.push(why));
+        let solution = match &self.solution {
+            Some(solution) => cells("solution", solution, false)
+                .map(Some)
+                .map_err(|why| problems.push(why)),
+            None => Ok(None),
+        };
+        if let (Ok(grid), Ok(Some(solution))) = (grid, solution) {
+            for (i, (given, val)) in grid.iter().zip(&solution).enumerate() {
+                if let (Some(given), Some(val)) = (given, val) {
+                    if given != val {
+                        problems.push(SchemaError::Field {
+                            field: "solution",
+                            message: format!(
+                                "r{}c{} is {val} but the grid gives {given}",
+                                i / 9 + 1,
+                                i % 9 + 1
+                            ),
+                        });
+                    }
+                }
+            }
+        }
+    }
+    /// the problems with a composite, each board's grid and then whether they agree where they
+    /// overlap
+    fn check_boards(&self, problems: &mut Vec<SchemaError>) {
+        let mut composite = Some(Composite::new());
+        for (i, placed) in self.boards.iter().enumerate() {
+            match board("boards", &placed.grid) {
+                Ok(board) => {
+                    composite = composite.and_then(|composite| {
+                        composite
+                            .with_board(placed.row, placed.column, board)
+                            .map_err(|why| {
+                                problems.push(SchemaError::Field {
+                                    field: "boards",
+                                    message: why.to_string(),
+                                })
+                            })
+                            .ok()
+                    })
+                }
+                Err(SchemaError::Field { message, .. }) => {
+                    problems.push(SchemaError::Field {
+                        field: "boards",
+                        message: format!("board {}: {message}", i + 1),
+                    });
+                    composite = None;
+                }
+                Err(why) => problems.push(why),
+            }
+        }
+        if self.solution.is_some() {
+            problems.push(SchemaError::Field {
+                field: "solution",
+                message: "only a puzzle on one grid can have a solution".to_string(),
+            });
+        }
+        if self
+            .rules
+            .as_deref()
+            .is_some_and(|rules| rules != "classic")
+        {
+            problems.push(SchemaError::Field {
+                field: "rules",
+                message: "the boards of a composite are played under the classic rules".to_string(),
+            });
+        }
+    }
+    /// the preset named by `rules`, or the classic rules if there isn't one
+    ///
+    /// a killer preset has no cages, since the schema doesn't hold them
+    pub fn rules(&self) -> Result<RuleSet, SchemaError> {
+        RuleSet::preset(self.rules.as_deref().unwrap_or("classic")).map_err(|why| {
+            SchemaError::Field {
+                field: "rules",
+                message: why.to_string(),
+            }
+        })
+    }
+    /// the board of `grid`, failing with the first problem `check` finds, or if the puzzle is a
+    /// composite
+    pub fn board(&self) -> Result<Board, SchemaError> {
+        if let Some(why) = self.check().into_iter().next() {
+            Err(why)?
+        }
+        match &self.grid {
+            Some(grid) => board("grid", grid),
+            None => Err(SchemaError::Field {
+                field: "grid",
+                message: "the puzzle is made of boards, see `composite`".to_string(),
+            }),
+        }
+    }
+    /// the composite of `boards`, failing with the first problem `check` finds
+    ///
+    /// a puzzle with a `grid` is a composite of just that board
+    pub fn composite(&self) -> Result<Composite, SchemaError> {
+        if let Some(why) = self.check().into_iter().next() {
+            Err(why)?
+        }
+        if let Some(grid) = &self.grid {
+            return Ok(Composite::new()
+                .with_board(0, 0, board("grid", grid)?)
+                .expect("one board can't disagree with itself"));
+        }
+        self.boards
+            .iter()
+            .try_fold(Composite::new(), |composite, placed| {
+                let board = board("boards", &placed.grid)?;
+                Ok(composite
+                    .with_board(placed.row, placed.column, board)
+                    .expect("checked boards agree where they overlap"))
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    fn corners() -> Composite {
+        Composite::new()
+            .with_board(
+                0,
+                0,
+                board!([[5, 3] [] [] [] [] [] [] [] [?, ?, ?, ?, ?, ?, 1]]),
+            )
+            .unwrap()
+            .with_board(6, 6, board!([[] [] [1]]))
+            .unwrap()
+    }
+
+    #[test]
+    fn composites_round_trip() {
+        let puzzle = Puzzle::from_composite(&corners());
+        let text = serde_json::to_string(&puzzle).unwrap();
+
+        assert!(text.starts_with("{\"version\":2,\"boards\":[{\"row\":0,\"column\":0,"));
+        assert_eq!(puzzle.check(), []);
+        assert_eq!(puzzle.composite(), Ok(corners()));
+        assert_eq!(
+            puzzle.board().unwrap_err().to_string(),
+            "grid: the puzzle is made of boards, see `composite`"
+        );
+    }
+    #[test]
+    fn boards_have_to_agree_where_they_overlap() {
+        let mut puzzle = Puzzle::from_composite(&corners());
+        puzzle.boards[1].grid.replace_range(18..19, "2");
+        puzzle.boards.push(PlacedBoard {
+            row: 12,
+            column: 12,
+            grid: "1".repeat(80),
+        });
+        puzzle.solution = Some("1".repeat(81));
+
+        assert_eq!(
+            puzzle.check(),
+            [
+                SchemaError::Field {
+                    field: "boards",
+                    message: "the boards disagree on row 9, column 7 where they overlap"
+                        .to_string()
+                },
+                SchemaError::Field {
+                    field: "boards",
+                    message: "board 3: expected 81 cells but found 80".to_string()
+                },
+                SchemaError::Field {
+                    field: "solution",
+                    message: "only a puzzle on one grid can have a solution".to_string()
+                },
+            ]
+        );
+    }
+    #[test]
+    fn a_puzzle_has_a_grid_or_boards() {
+        let mut puzzle = Puzzle::new(&board!([[1]]));
+        assert_eq!(puzzle.composite().unwrap().boards().count(), 1);
+
+        puzzle.boards = Puzzle::from_composite(&corners()).boards;
+        assert!(matches!(
+            &puzzle.check()[..],
+            [SchemaError::Field {
+                field: "boards",
+                ..
+            }]
+        ));
+        puzzle.grid = None;
+        puzzle.boards.clear();
+        assert!(matches!(
+            &puzzle.check()[..],
+            [SchemaError::Field { field: "grid", .. }]
+        ));
+    }
+}
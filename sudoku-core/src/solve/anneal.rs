@@ -0,0 +1,240 @@
+//! simulated annealing, which fills every house and then swaps cells within houses until no
+//! row or column has a repeat
+//!
+//! it can't prove anything: a puzzle without a solution only runs out of moves, and a puzzle
+//! with several gets whichever one it lands on. it is here as a baseline to benchmark the
+//! deterministic backends against, and can beat them on nearly empty grids
+use super::{SolveOptions, SolveStats};
+use crate::{
+    board::{geometry::house_cells, Shape},
+    clock::Clock,
+    errors::catch_internal,
+    Board, SolveError, UpdateError,
+};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use std::time::Duration;
+
+/// how many swaps to try before giving up with `UpdateError::Incomplete`
+const MAX_MOVES: usize = 500_000;
+/// the temperature at the start and after every reheat
+const START_TEMPERATURE: f64 = 1.0;
+/// what the temperature is multiplied by after each round of one move per free cell
+const COOLING: f64 = 0.99;
+/// how many rounds the cost can go without improving before the temperature is reset
+const STUCK_ROUNDS: usize = 100;
+/// how many moves go by between checks of the timeout and cancel token
+const CHECK_EVERY: usize = 1024;
+
+/// how many of the values 1 to `side` are missing from a unit
+fn missing(side: usize, vals: impl Iterator<Item = usize>) -> usize {
+    let seen = vals.fold(0u64, |seen, val| seen | 1 << val);
+    side - seen.count_ones() as usize
+}
+
+/// a filled in grid that may break the row and column rules, but never the house rule
+struct Grid {
+    values: Vec<Vec<usize>>,
+    /// the cells of each house that aren't fixed, for houses with at least two to swap
+    houses: Vec<Vec<(usize, usize)>>,
+}
+
+impl Grid {
+    /// the board's values, with the blanks of each house filled with the values it is missing
+    fn fill<S: Shape, R: Rng + ?Sized>(board: &Board<S>, rng: &mut R) -> Self {
+        let given = board.values();
+        let mut values = vec![vec![0; S::SIDE]; S::SIDE];
+        let mut houses = vec![];
+        for house in house_cells::<S>() {
+            let cells = house.into_iter();
+            let mut vals: Vec<_> = (1..=S::SIDE)
+                .filter(|&val| !cells.clone().any(|(row, col)| given[row][col] == Some(val)))
+                .collect();
+            vals.shuffle(rng);
+            let free: Vec<_> = cells
+                .clone()
+                .filter(|&(row, col)| given[row][col].is_none())
+                .collect();
+            for (row, col) in cells {
+                values[row][col] = given[row][col].unwrap_or(0);
+            }
+            for (&(row, col), val) in free.iter().zip(vals) {
+                values[row][col] = val;
+            }
+            if free.len() > 1 {
+                houses.push(free);
+            }
+        }
+        Grid { values, houses }
+    }
+    fn row_cost(&self, row: usize) -> usize {
+        missing(self.values.len(), self.values[row].iter().copied())
+    }
+    fn column_cost(&self, col: usize) -> usize {
+        missing(self.values.len(), self.values.iter().map(|row| row[col]))
+    }
+    /// how many values are missing from every row and column
+    fn cost(&self) -> usize {
+        (0..self.values.len())
+            .map(|i| self.row_cost(i) + self.column_cost(i))
+            .sum()
+    }
+    /// how many values are missing from the rows and columns through both cells
+    fn cost_around(&self, [(r1, c1), (r2, c2)]: [(usize, usize); 2]) -> usize {
+        let rows = if r1 == r2 {
+            self.row_cost(r1)
+        } else {
+            self.row_cost(r1) + self.row_cost(r2)
+        };
+        let columns = if c1 == c2 {
+            self.column_cost(c1)
+        } else {
+            self.column_cost(c1) + self.column_cost(c2)
+        };
+        rows + columns
+    }
+    /// two free cells of the same house, if any house has two
+    fn pick<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<[(usize, usize); 2]> {
+        let house = self.houses.choose(rng)?;
+        let mut cells = house.choose_multiple(rng, 2);
+        Some([*cells.next()?, *cells.next()?])
+    }
+    fn swap(&mut self, [(r1, c1), (r2, c2)]: [(usize, usize); 2]) {
+        let val = self.values[r1][c1];
+        self.values[r1][c1] = self.values[r2][c2];
+        self.values[r2][c2] = val;
+    }
+}
+
+/// the values of a solution of `board`, a grid of values from 1 to `SIDE`
+fn anneal<S: Shape, R: Rng + ?Sized>(
+    board: &Board<S>,
+    options: &SolveOptions<S>,
+    start: Duration,
+    rng: &mut R,
+    stats: &mut SolveStats,
+) -> Result<Vec<Vec<usize>>, UpdateError> {
+    let mut grid = Grid::fill(board, rng);
+    let free: usize = grid.houses.iter().map(Vec::len).sum();
+    let mut cost = grid.cost();
+    let mut temperature = START_TEMPERATURE;
+    let (mut best, mut stuck) = (cost, 0);
+    for moves in 0..MAX_MOVES {
+        if cost == 0 {
+            return Ok(grid.values);
+        }
+        if moves % CHECK_EVERY == 0 {
+            if let Some(why) = options.stop_reason(start) {
+                Err(why)?
+            }
+        }
+        // with nothing left to swap, the cost can never go down
+        let cells = grid.pick(rng).ok_or(UpdateError::Impossible)?;
+        stats.guesses += 1;
+        let before = grid.cost_around(cells);
+        grid.swap(cells);
+        let after = grid.cost_around(cells);
+        let worse = after as f64 - before as f64;
+        if after <= before || rng.gen::<f64>() < (-worse / temperature).exp() {
+            cost = cost + after - before;
+        } else {
+            grid.swap(cells);
+        }
+        if (moves + 1) % free == 0 {
+            temperature *= COOLING;
+            if cost < best {
+                (best, stuck) = (cost, 0);
+            } else {
+                stuck += 1;
+            }
+            if stuck == STUCK_ROUNDS {
+                temperature = START_TEMPERATURE;
+                (best, stuck) = (cost, 0);
+            }
+        }
+    }
+    match cost {
+        0 => Ok(grid.values),
+        _ => Err(UpdateError::Incomplete),
+    }
+}
+
+impl<S: Shape> Board<S> {
+    /// solves the board by simulated annealing, with an rng seeded by `tie_break_seed` or 0
+    ///
+    /// the strategies, value order, and depth limit aren't used, but the timeout and cancel token
+    /// are. each swap tried counts as a guess
+    pub(crate) fn solve_anneal(
+        self,
+        options: &SolveOptions<S>,
+    ) -> Result<(Board<S>, SolveStats), SolveError<S>> {
+        let start = options.clock.now();
+        // fixing the cells propagation fills in leaves less to search
+        let propagation = self.clone().propagate();
+        if let Some(why) = propagation.contradiction {
+            Err(why)?
+        }
+        let seed = options.tie_break_seed.unwrap_or(0);
+        let (solution, mut stats) = catch_internal("solving with simulated annealing", || {
+            let mut stats = SolveStats {
+                seed: Some(seed),
+                ..Default::default()
+            };
+            let mut rng = StdRng::seed_from_u64(seed);
+            let solution = anneal(&propagation.board, options, start, &mut rng, &mut stats);
+            (solution, stats)
+        })?;
+        stats.elapsed = options.clock.now().saturating_sub(start);
+        let values = match solution {
+            Err(why @ (UpdateError::Timeout | UpdateError::Cancelled)) => {
+                return Err(SolveError::Stopped { why, best: self });
+            }
+            solution => solution?,
+        };
+        let lines = values
+            .iter()
+            .map(|row| row.iter().map(|&val| Some(val as i64)).collect())
+            .collect();
+        Ok((Board::from_givens(lines)?, stats))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{board::macros::*, SolvedBoard};
+
+    #[test]
+    fn anneal_fills_a_nearly_empty_grid() {
+        let (board, stats) = board!([[1, 2, 3]])
+            .solve_anneal(&SolveOptions::default())
+            .unwrap();
+        let values: [[usize; 9]; 9] = SolvedBoard::try_from(board).unwrap().into();
+
+        assert_eq!(values[0][..3], [1, 2, 3]);
+        assert!((0..9).all(|i| missing(9, values[i].iter().copied()) == 0
+            && missing(9, values.iter().map(|row| row[i])) == 0));
+        assert_eq!(stats.seed, Some(0));
+    }
+    #[test]
+    fn anneal_rejects_broken_givens() {
+        assert!(matches!(
+            board!([[1, 1]]).solve_anneal(&SolveOptions::default()),
+            Err(SolveError::Update(_))
+        ));
+    }
+    #[test]
+    fn anneal_stops_at_the_timeout() {
+        let options = SolveOptions {
+            timeout: Some(Duration::ZERO),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            board!([]).solve_anneal(&options),
+            Err(SolveError::Stopped {
+                why: UpdateError::Timeout,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,101 @@
+//! arrows, a circled cell whose value is the sum of the cells along the arrow coming out of it
+//!
+//! the cells along an arrow can repeat a value unless the classic rules stop them, so an arrow
+//! keeps the candidates that some way of filling it adds up to one of the circle's, see `sums`
+use super::sums;
+use crate::{
+    board::{Cell, CellPos, Shape},
+    Board, BuildError, Classic, UpdateError,
+};
+use std::collections::HashSet;
+
+/// a circle whose value is the sum of the `cells` along its arrow
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Arrow<S: Shape = Classic> {
+    circle: CellPos<S>,
+    cells: Vec<CellPos<S>>,
+}
+
+impl<S: Shape> Arrow<S> {
+    /// an arrow of 1 to `SIDE` different cells, none of them the circle
+    pub fn new(circle: CellPos<S>, cells: Vec<CellPos<S>>) -> Result<Self, BuildError> {
+        let count = cells.len();
+        // SIDE cells already add up to at least SIDE, the most a circle can hold
+        if !(1..=S::SIDE).contains(&count)
+            || cells.contains(&circle)
+            || cells.iter().collect::<HashSet<_>>().len() != count
+        {
+            Err(BuildError::ArrowCells(count))?
+        }
+        Ok(Arrow { circle, cells })
+    }
+    pub fn circle(&self) -> CellPos<S> {
+        self.circle
+    }
+    /// the cells along the arrow, not counting the circle
+    pub fn cells(&self) -> &[CellPos<S>] {
+        &self.cells
+    }
+    /// removes the candidates of the circle and arrow that no way of filling them uses, and
+    /// fills in cells left with one, failing if the arrow can't add up to the circle
+    pub(crate) fn check_and_update(&self, board: &mut Board<S>) -> Result<(), UpdateError> {
+        let circle: Vec<_> = match board.cell(self.circle) {
+            &Cell::Concrete(val) => vec![val],
+            Cell::Possibilities(set) => set.iter().collect(),
+        };
+        let (mut circle_kept, mut kept) = (0, None::<Vec<(CellPos<S>, u64)>>);
+        for val in circle {
+            let Some(ways) = sums::kept(board, &self.cells, val.into_inner(), false)? else {
+                continue;
+            };
+            circle_kept |= 1 << val.into_inner();
+            kept = Some(match kept {
+                None => ways,
+                Some(kept) => kept
+                    .into_iter()
+                    .zip(ways)
+                    .map(|((pos, kept), (_, ways))| (pos, kept | ways))
+                    .collect(),
+            });
+        }
+        let kept = kept.ok_or(UpdateError::Impossible)?;
+        sums::narrow(board, kept.into_iter().chain([(self.circle, circle_kept)]))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn arrows_need_cells_apart_from_the_circle() {
+        assert!(Arrow::new(pos!(0, 0), vec![pos!(0, 1), pos!(1, 1)]).is_ok());
+        assert_eq!(
+            Arrow::new(pos!(0, 0), vec![pos!(0, 0), pos!(0, 1)]),
+            Err(BuildError::ArrowCells(2))
+        );
+        assert_eq!(
+            Arrow::new(pos!(0, 0), vec![]),
+            Err(BuildError::ArrowCells(0))
+        );
+    }
+    #[test]
+    fn arrows_keep_the_values_that_add_up() {
+        let mut board = board!([[?, 3] []]);
+        // the circle is at least 3 + 1, and the other cell at most 9 - 3
+        Arrow::new(pos!(0, 0), vec![pos!(0, 1), pos!(1, 1)])
+            .unwrap()
+            .check_and_update(&mut board)
+            .unwrap();
+
+        assert_eq!(board.cell(pos!(0, 0)), &cell!(? 4, 5, 6, 7, 8, 9));
+        assert_eq!(board.cell(pos!(1, 1)), &cell!(? 1, 2, 3, 4, 5, 6));
+        assert_eq!(
+            Arrow::new(pos!(0, 0), vec![pos!(0, 1)])
+                .unwrap()
+                .check_and_update(&mut board!([[2, 3]])),
+            Err(UpdateError::Impossible)
+        );
+    }
+}
@@ -0,0 +1,282 @@
+use super::{CancelToken, RuleSet, SolveOptions, SolveStats, ValueOrder, Variant};
+use crate::{
+    board::Shape,
+    clock::{Clock, SharedClock},
+    Board, Classic, SolveError,
+};
+use std::{str::FromStr, time::Duration};
+
+/// the search used to fill in cells once the strategies run out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SolverBackend {
+    /// depth first search over every possible value of every cell
+    Backtracking,
+    /// Algorithm X with dancing links over the board as an exact cover problem
+    ///
+    /// much faster on hard puzzles, but it doesn't use the strategies, so it finds the answer
+    /// without any of the reasoning a person would use
+    Dlx,
+    /// simulated annealing, which fills the grid and swaps cells to get rid of conflicts
+    ///
+    /// a baseline for benchmarking the others. it is stochastic, seeded by `tie_break_seed`, and
+    /// can give up with `UpdateError::Incomplete` on puzzles that do have a solution
+    Anneal,
+}
+
+impl SolverBackend {
+    /// every backend, in the order they were added
+    pub const ALL: [SolverBackend; 3] = [
+        SolverBackend::Backtracking,
+        SolverBackend::Dlx,
+        SolverBackend::Anneal,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SolverBackend::Backtracking => "backtracking",
+            SolverBackend::Dlx => "dlx",
+            SolverBackend::Anneal => "anneal",
+        }
+    }
+}
+
+/// the backend with the `name`, so one can be picked from a config file or command line
+impl FromStr for SolverBackend {
+    type Err = SolveError;
+
+    fn from_str(name: &str) -> Result<Self, SolveError> {
+        SolverBackend::ALL
+            .into_iter()
+            .find(|backend| backend.name() == name)
+            .ok_or_else(|| SolveError::UnknownBackend(name.to_string()))
+    }
+}
+
+/// marks a `SolverBuilder` that doesn't have a backend yet, so it can't be built
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoBackend;
+
+/// configures a `Solver` for boards of shape `S`
+///
+/// `Solver::builder` starts one for the classic board, and `SolverBuilder::new` for any other.
+/// a backend has to be picked before `build` is available:
+///
+/// ```
+/// use sudoku_core::{Solver, SolverBackend};
+///
+/// let solver = Solver::builder()
+///     .backend(SolverBackend::Backtracking)
+///     .max_depth(40)
+///     .build();
+/// ```
+///
+/// ```compile_fail
+/// let solver = sudoku_core::Solver::builder().max_depth(40).build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct SolverBuilder<B, S: Shape = Classic> {
+    backend: B,
+    options: SolveOptions<S>,
+}
+
+impl<S: Shape> SolverBuilder<NoBackend, S> {
+    pub fn new() -> Self {
+        SolverBuilder {
+            backend: NoBackend,
+            options: SolveOptions::default(),
+        }
+    }
+}
+impl<S: Shape> Default for SolverBuilder<NoBackend, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<B> SolverBuilder<B> {
+    /// adds every variant of a preset, see `RuleSet::preset`
+    pub fn rules(mut self, rules: RuleSet) -> Self {
+        self.options.variants.extend_from_slice(rules.variants());
+        self
+    }
+}
+impl<B, S: Shape> SolverBuilder<B, S> {
+    pub fn backend(self, backend: SolverBackend) -> SolverBuilder<SolverBackend, S> {
+        SolverBuilder {
+            backend,
+            options: self.options,
+        }
+    }
+    /// see `SolveOptions::max_depth`
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.options.max_depth = Some(max_depth);
+        self
+    }
+    /// see `SolveOptions::max_chain_length`
+    pub fn max_chain_length(mut self, max_chain_length: usize) -> Self {
+        self.options.max_chain_length = Some(max_chain_length);
+        self
+    }
+    /// stops the solver from using the strategy with this name, see `SolveOptions::disabled_strategies`
+    pub fn disable_strategy(mut self, name: impl Into<String>) -> Self {
+        self.options.disabled_strategies.insert(name.into());
+        self
+    }
+    /// see `SolveOptions::value_order`
+    pub fn value_order(mut self, value_order: ValueOrder) -> Self {
+        self.options.value_order = value_order;
+        self
+    }
+    /// see `SolveOptions::tie_break_seed`
+    pub fn tie_break_seed(mut self, seed: u64) -> Self {
+        self.options.tie_break_seed = Some(seed);
+        self
+    }
+    /// see `SolveOptions::timeout`
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.options.timeout = Some(timeout);
+        self
+    }
+    /// see `SolveOptions::cancel_token`
+    pub fn cancel_token(mut self, cancel_token: CancelToken) -> Self {
+        self.options.cancel_token = Some(cancel_token);
+        self
+    }
+    /// adds rules on top of the classic ones, see `SolveOptions::variants`
+    pub fn variant(mut self, variant: Variant<S>) -> Self {
+        self.options.variants.push(variant);
+        self
+    }
+    /// see `SolveOptions::profile`
+    pub fn profile(mut self, profile: bool) -> Self {
+        self.options.profile = profile;
+        self
+    }
+    /// see `SolveOptions::clock`
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.options.clock = SharedClock::new(clock);
+        self
+    }
+}
+impl<S: Shape> SolverBuilder<SolverBackend, S> {
+    pub fn build(self) -> Solver<S> {
+        Solver {
+            backend: self.backend,
+            options: self.options,
+        }
+    }
+}
+
+/// a configured solver, which can be reused for any number of boards
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solver<S: Shape = Classic> {
+    backend: SolverBackend,
+    options: SolveOptions<S>,
+}
+
+impl Solver {
+    pub fn builder() -> SolverBuilder<NoBackend> {
+        SolverBuilder::new()
+    }
+    /// a solver with the default options and the backend named `name`, such as `"dlx"`
+    ///
+    /// every solver is the same type whatever its backend, so hosts that pick one at runtime
+    /// don't need generics or trait objects to hold it
+    pub fn by_name(name: &str) -> Result<Solver, SolveError> {
+        Ok(Solver::builder().backend(name.parse()?).build())
+    }
+}
+impl<S: Shape> Solver<S> {
+    pub fn backend(&self) -> SolverBackend {
+        self.backend
+    }
+    pub fn options(&self) -> &SolveOptions<S> {
+        &self.options
+    }
+    pub fn solve(&self, board: Board<S>) -> Result<(Board<S>, SolveStats), SolveError<S>> {
+        match (self.backend, self.options.variants.first()) {
+            (SolverBackend::Backtracking, _) | (_, None) => {}
+            (backend, Some(variant)) => Err(SolveError::Unsupported {
+                backend: backend.name(),
+                variant: variant.name(),
+            })?,
+        }
+        match self.backend {
+            SolverBackend::Backtracking => board.solve_with(&self.options),
+            SolverBackend::Dlx => board.solve_dlx(&self.options),
+            SolverBackend::Anneal => board.solve_anneal(&self.options),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn builder_keeps_options_across_backend() {
+        let solver = Solver::builder()
+            .max_depth(3)
+            .max_chain_length(4)
+            .disable_strategy("x-wing")
+            .value_order(ValueOrder::LeastConstraining)
+            .timeout(Duration::from_secs(2))
+            .backend(SolverBackend::Backtracking)
+            .build();
+
+        assert_eq!(solver.options().max_depth, Some(3));
+        assert_eq!(solver.options().max_chain_length, Some(4));
+        assert!(solver.options().disabled_strategies.contains("x-wing"));
+        assert_eq!(solver.options().value_order, ValueOrder::LeastConstraining);
+        assert_eq!(solver.options().timeout, Some(Duration::from_secs(2)));
+        assert_eq!(solver.backend(), SolverBackend::Backtracking);
+    }
+    #[test]
+    fn solver_uses_its_options() {
+        let solver = Solver::builder()
+            .backend(SolverBackend::Backtracking)
+            .max_depth(1)
+            .build();
+
+        assert!(solver.solve(board!([])).is_err());
+    }
+    #[test]
+    fn solver_uses_its_backend() {
+        let solver = Solver::builder().backend(SolverBackend::Dlx).build();
+        let (board, stats) = solver.solve(board!([[1, 2, 3]])).unwrap();
+
+        assert!(board.is_finished());
+        assert!(stats.guesses > 0);
+    }
+    #[test]
+    fn solvers_can_be_picked_by_name() {
+        for backend in SolverBackend::ALL {
+            assert_eq!(Solver::by_name(backend.name()).unwrap().backend(), backend);
+        }
+        let why = Solver::by_name("quantum").unwrap_err();
+
+        assert_eq!(why, SolveError::UnknownBackend("quantum".to_string()));
+        assert_eq!(
+            why.to_string(),
+            "there is no \"quantum\" backend, the backends are backtracking, dlx, anneal"
+        );
+    }
+    #[test]
+    fn solver_times_with_its_clock() {
+        let clock = crate::clock::MockClock::new();
+        let solver = Solver::builder()
+            .backend(SolverBackend::Backtracking)
+            .clock(clock.clone())
+            .build();
+        clock.advance(std::time::Duration::from_secs(1));
+
+        assert_eq!(
+            solver.options().clock.now(),
+            std::time::Duration::from_secs(1)
+        );
+        assert_eq!(
+            solver.solve(board!([])).unwrap().1.elapsed,
+            Default::default()
+        );
+    }
+}
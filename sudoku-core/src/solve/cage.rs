@@ -0,0 +1,99 @@
+//! killer sudoku cages, groups of cells whose values add up to a given sum without repeating
+//!
+//! a cage keeps the candidates of a cell that some way of filling the whole cage still uses,
+//! see `sums`
+use super::sums;
+use crate::{
+    board::{CellPos, Shape},
+    Board, BuildError, Classic, UpdateError,
+};
+use std::collections::HashSet;
+
+/// cells whose values add up to `sum`, with no value used twice
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Cage<S: Shape = Classic> {
+    cells: Vec<CellPos<S>>,
+    sum: usize,
+}
+
+impl<S: Shape> Cage<S> {
+    /// a cage of 1 to `SIDE` different cells, failing if no values could add up to `sum`
+    pub fn new(cells: Vec<CellPos<S>>, sum: usize) -> Result<Self, BuildError> {
+        let count = cells.len();
+        if !(1..=S::SIDE).contains(&count) || cells.iter().collect::<HashSet<_>>().len() != count {
+            Err(BuildError::CageCells(count))?
+        }
+        // the smallest and largest values that fit, 1 + 2 + ... and SIDE + SIDE - 1 + ...
+        let least = count * (count + 1) / 2;
+        let most = least + count * (S::SIDE - count);
+        if !(least..=most).contains(&sum) {
+            Err(BuildError::CageSum { sum, cells: count })?
+        }
+        Ok(Cage { cells, sum })
+    }
+    pub fn cells(&self) -> &[CellPos<S>] {
+        &self.cells
+    }
+    pub fn sum(&self) -> usize {
+        self.sum
+    }
+    /// removes the candidates no way of filling the cage uses and fills in cells left with one,
+    /// failing if the cage repeats a value or can't reach its sum
+    pub(crate) fn check_and_update(&self, board: &mut Board<S>) -> Result<(), UpdateError> {
+        let kept =
+            sums::kept(board, &self.cells, self.sum, true)?.ok_or(UpdateError::Impossible)?;
+        sums::narrow(board, kept)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn cages_need_a_reachable_sum() {
+        assert!(Cage::new(vec![pos!(0, 0), pos!(0, 1)], 17).is_ok());
+        assert_eq!(
+            Cage::new(vec![pos!(0, 0), pos!(0, 1)], 18),
+            Err(BuildError::CageSum { sum: 18, cells: 2 })
+        );
+        assert_eq!(
+            Cage::new(vec![pos!(0, 0), pos!(0, 0)], 3),
+            Err(BuildError::CageCells(2))
+        );
+        assert_eq!(
+            Cage::<Classic>::new(vec![], 0),
+            Err(BuildError::CageCells(0))
+        );
+    }
+    #[test]
+    fn cages_keep_the_values_their_sum_allows() {
+        let mut board = board!([[?, ?, ?, 4]]);
+        // 3 in two cells can only be 1 and 2
+        Cage::new(vec![pos!(0, 0), pos!(0, 1)], 3)
+            .unwrap()
+            .check_and_update(&mut board)
+            .unwrap();
+        // with the 4 given, the other cell has to be 7
+        Cage::new(vec![pos!(0, 3), pos!(1, 3)], 11)
+            .unwrap()
+            .check_and_update(&mut board)
+            .unwrap();
+
+        assert_eq!(board.cell(pos!(0, 0)), &cell!(? 1, 2));
+        assert_eq!(board.cell(pos!(0, 1)), &cell!(? 1, 2));
+        assert_eq!(board.cell(pos!(1, 3)), &cell!(7));
+        assert_eq!(board.cell(pos!(0, 2)), &cell!(? 1, 2, 3, 4, 5, 6, 7, 8, 9));
+    }
+    #[test]
+    fn cages_reject_sums_they_cant_reach() {
+        let mut board = board!([[1, 2]]);
+        let cage = Cage::new(vec![pos!(0, 0), pos!(0, 1)], 4).unwrap();
+
+        assert_eq!(
+            cage.check_and_update(&mut board),
+            Err(UpdateError::Impossible)
+        );
+    }
+}
@@ -0,0 +1,253 @@
+//! a difficulty score fitted to how hard people rated puzzles, instead of the ladder of `rate`
+//!
+//! a puzzle is described by how many steps each technique took to solve it, and the score is a
+//! linear model over `ln(1 + steps)` of each, so the tenth use of a technique adds less than the
+//! first. the coefficients are fitted offline with `Model::fit` and built in from
+//! `difficulty.model`, which is the `Display` of the fitted model:
+//!
+//! ```text
+//! difficulty-model 1
+//! intercept <number>
+//! <technique> <weight>   (one per technique, with dashes for spaces in names)
+//! ```
+use crate::{strategy_names, Board, ParseError, UpdateError};
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+const HEADER: &str = "difficulty-model 1";
+/// the coefficients built into the crate
+const EMBEDDED: &str = include_str!("difficulty.model");
+/// what a solve that has to guess is counted as, with one step
+const GUESSING: &str = "guessing";
+/// how much `fit` pulls the weights towards 0, enough to settle techniques no puzzle used
+const RIDGE: f64 = 1e-6;
+
+/// how many steps each technique took to solve a puzzle, see `Board::difficulty`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Difficulty {
+    /// with `"guessing"` as 1 if the techniques couldn't finish the puzzle
+    pub usage: BTreeMap<&'static str, usize>,
+}
+
+impl Difficulty {
+    /// the techniques a model weighs: singles, the strategies, and guessing
+    pub fn techniques() -> Vec<&'static str> {
+        let mut names = vec!["singles"];
+        names.extend(strategy_names());
+        names.push(GUESSING);
+        names
+    }
+    /// `ln(1 + steps)` of each of `techniques`
+    fn features(&self) -> Vec<f64> {
+        Self::techniques()
+            .into_iter()
+            .map(|name| (*self.usage.get(name).unwrap_or(&0) as f64).ln_1p())
+            .collect()
+    }
+    /// the score the built in model gives, higher being harder
+    pub fn model_score(&self) -> f64 {
+        self.score_with(&Model::embedded())
+    }
+    pub fn score_with(&self, model: &Model) -> f64 {
+        Self::techniques()
+            .into_iter()
+            .zip(self.features())
+            .map(|(name, x)| model.weights.get(name).unwrap_or(&0.0) * x)
+            .sum::<f64>()
+            + model.intercept
+    }
+}
+
+/// a linear model from how often each technique was used to a difficulty
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Model {
+    pub intercept: f64,
+    /// by the names of `Difficulty::techniques`, with any left out weighing 0
+    pub weights: BTreeMap<&'static str, f64>,
+}
+
+impl Model {
+    /// the model built into the crate, the one `Difficulty::model_score` uses
+    pub fn embedded() -> Self {
+        EMBEDDED.parse().expect("difficulty.model is a model")
+    }
+    /// the model that best fits the `(puzzle, rating)` pairs by least squares
+    pub fn fit(rated: &[(Difficulty, f64)]) -> Self {
+        let names = Difficulty::techniques();
+        // the intercept is the last column, with every row being 1
+        let n = names.len() + 1;
+        let mut a = vec![vec![0.0; n]; n];
+        let mut b = vec![0.0; n];
+        for (difficulty, rating) in rated {
+            let mut x = difficulty.features();
+            x.push(1.0);
+            for ((row, b), xi) in a.iter_mut().zip(&mut b).zip(&x) {
+                for (cell, xj) in row.iter_mut().zip(&x) {
+                    *cell += xi * xj;
+                }
+                *b += xi * rating;
+            }
+        }
+        for (i, row) in a.iter_mut().enumerate() {
+            row[i] += RIDGE;
+        }
+        let mut coefficients = solve_linear(a, b);
+        let intercept = coefficients.pop().unwrap_or(0.0);
+        Model {
+            intercept,
+            weights: names.into_iter().zip(coefficients).collect(),
+        }
+    }
+}
+
+/// the `x` where `a x = b`, for `a` positive definite
+fn solve_linear(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Vec<f64> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))
+            .unwrap_or(col);
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let (pivot_row, pivot_b) = (a[col].clone(), b[col]);
+        for (row, b) in a.iter_mut().zip(&mut b).skip(col + 1) {
+            let factor = row[col] / pivot_row[col];
+            for (cell, pivot) in row.iter_mut().zip(&pivot_row).skip(col) {
+                *cell -= factor * pivot;
+            }
+            *b -= factor * pivot_b;
+        }
+    }
+    let mut x = vec![0.0; n];
+    for (row, (coefficients, b)) in a.iter().zip(&b).enumerate().rev() {
+        let rest: f64 = coefficients[row + 1..]
+            .iter()
+            .zip(&x[row + 1..])
+            .map(|(a, x)| a * x)
+            .sum();
+        x[row] = (b - rest) / coefficients[row];
+    }
+    x
+}
+
+impl fmt::Display for Model {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{HEADER}")?;
+        writeln!(f, "intercept {}", self.intercept)?;
+        for name in Difficulty::techniques() {
+            if let Some(weight) = self.weights.get(name) {
+                writeln!(f, "{} {weight}", name.replace(' ', "-"))?;
+            }
+        }
+        Ok(())
+    }
+}
+impl FromStr for Model {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut lines = crate::io::lines(text).filter(|line| !line.trim().is_empty());
+        if lines.next().map(str::trim) != Some(HEADER) {
+            Err(ParseError::Model)?
+        }
+        let mut model = Model::default();
+        for line in lines {
+            let (name, number) = line.trim().split_once(' ').ok_or(ParseError::Model)?;
+            let number: f64 = number.trim().parse().map_err(|_| ParseError::Model)?;
+            if name == "intercept" {
+                model.intercept = number;
+                continue;
+            }
+            let technique = Difficulty::techniques()
+                .into_iter()
+                .find(|known| known.replace(' ', "-") == name)
+                .ok_or(ParseError::Model)?;
+            model.weights.insert(technique, number);
+        }
+        Ok(model)
+    }
+}
+
+impl Board {
+    /// solves the board with the strategies, one step at a time, and counts the steps of each
+    ///
+    /// fails if a step finds the puzzle can't be solved
+    pub fn difficulty(&self) -> Result<Difficulty, UpdateError> {
+        let mut difficulty = Difficulty::default();
+        let mut steps = self.clone().solve_steps();
+        for step in steps.by_ref() {
+            *difficulty.usage.entry(step.technique).or_default() += 1;
+        }
+        match steps.stopped() {
+            None => {}
+            Some(UpdateError::Incomplete) => {
+                difficulty.usage.insert(GUESSING, 1);
+            }
+            Some(why) => Err(why)?,
+        }
+        Ok(difficulty)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn the_embedded_model_weighs_every_technique() {
+        let model = Model::embedded();
+
+        assert_eq!(model.weights.len(), Difficulty::techniques().len());
+        assert_eq!(model.to_string().parse(), Ok(model));
+        assert_eq!("intercept 1".parse::<Model>(), Err(ParseError::Model));
+        assert_eq!(
+            format!("{HEADER}\nmagic 1").parse::<Model>(),
+            Err(ParseError::Model)
+        );
+    }
+    #[test]
+    fn guessing_scores_above_singles() {
+        let guessing = board!([]).difficulty().unwrap();
+        let singles = board!([
+            [?, 2, 3, 4, 5, 6, 7, 8, 9]
+            [4, 5, 6, 7, 8, 9, 1, 2, 3]
+            [7, 8, 9, 1, 2, 3, 4, 5, 6]
+            [2, 3, 4, 5, 6, 7, 8, 9, 1]
+            [5, 6, 7, 8, 9, 1, 2, 3, 4]
+            [8, 9, 1, 2, 3, 4, 5, 6, 7]
+            [3, 4, 5, 6, 7, 8, 9, 1, 2]
+            [6, 7, 8, 9, 1, 2, 3, 4, 5]
+            [9, 1, 2, 3, 4, 5, 6, 7, 8]
+        ])
+        .difficulty()
+        .unwrap();
+
+        assert_eq!(guessing.usage.get(GUESSING), Some(&1));
+        assert_eq!(singles.usage.keys().collect::<Vec<_>>(), [&"singles"]);
+        assert!(guessing.model_score() > singles.model_score());
+        assert_eq!(
+            board!([[1, 1]]).difficulty(),
+            Err(UpdateError::InvalidConcrete)
+        );
+    }
+    #[test]
+    fn fit_recovers_the_model_it_was_rated_by() {
+        let model = Model::embedded();
+        // every technique alone, used once and three times, and a puzzle of nothing
+        let mut rated = vec![(Difficulty::default(), model.intercept)];
+        for name in Difficulty::techniques() {
+            for steps in [1, 3] {
+                let difficulty = Difficulty {
+                    usage: [(name, steps)].into(),
+                };
+                let rating = difficulty.score_with(&model);
+                rated.push((difficulty, rating));
+            }
+        }
+        let fitted = Model::fit(&rated);
+
+        for (difficulty, rating) in &rated {
+            assert!((difficulty.score_with(&fitted) - rating).abs() < 1e-3);
+        }
+    }
+}
@@ -0,0 +1,489 @@
+//! Knuth's Algorithm X over dancing links
+//!
+//! a solved board is an exact cover: every cell has one value, and every row, column, and house
+//! has each value once. that makes 324 constraints on the classic board (the matrix columns),
+//! four for every cell, and each candidate
+//! value of each cell fills one of each kind (a matrix row). picking rows so every column is
+//! filled exactly once solves the board
+
+use super::{SolveOptions, SolveStats};
+use crate::{
+    board::{Cell, CellPos, CellVal, Shape},
+    clock::Clock,
+    errors::catch_internal,
+    Board, SolveError, UpdateError,
+};
+use std::{ops::ControlFlow, panic::AssertUnwindSafe, time::Duration};
+
+/// the header every column header is linked into
+const ROOT: usize = 0;
+
+/// the sparse exact cover matrix as circular doubly linked lists
+///
+/// node 0 is the root and the nodes after it are the column headers, one for each cell, then for
+/// each value in each row, column, and house. covering a column unlinks it and every row that
+/// fills it, and uncovering relinks them in the reverse order
+struct Dlx {
+    /// how many cells a row has, and the largest value
+    side: usize,
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    /// the column header of each node
+    column: Vec<usize>,
+    /// the candidate each node belongs to, as `side * (side * row + column) + value - 1`
+    candidate: Vec<usize>,
+    /// how many rows are left in each column, indexed by header
+    size: Vec<usize>,
+    /// the most candidates the search has had picked at once, which is as close as it got
+    deepest: Vec<usize>,
+}
+
+impl Dlx {
+    /// the matrix for the candidates left on the board
+    ///
+    /// a concrete cell only gets a row for its value, so givens are always picked
+    fn new<S: Shape>(board: &Board<S>) -> Self {
+        let headers = 4 * S::CELLS + 1;
+        let mut dlx = Dlx {
+            side: S::SIDE,
+            left: (0..headers).map(|i| (i + headers - 1) % headers).collect(),
+            right: (0..headers).map(|i| (i + 1) % headers).collect(),
+            up: (0..headers).collect(),
+            down: (0..headers).collect(),
+            column: (0..headers).collect(),
+            candidate: vec![usize::MAX; headers],
+            size: vec![0; headers],
+            deepest: vec![],
+        };
+        for (i, pos) in CellPos::all().enumerate() {
+            let mut vals: Vec<usize> = match board.cell(pos) {
+                Cell::Concrete(val) => vec![val.into_inner()],
+                Cell::Possibilities(set) => set.iter().map(|val| val.into_inner()).collect(),
+            };
+            // sorted so the same board always gives the same solution
+            vals.sort();
+            let (row, column) = (i / S::SIDE, i % S::SIDE);
+            for val in vals {
+                dlx.add_row(row, column, S::house_of(row, column), val);
+            }
+        }
+        dlx
+    }
+    fn add_row(&mut self, row: usize, column: usize, house: usize, val: usize) {
+        let (side, cells) = (self.side, self.side * self.side);
+        let headers = [
+            row * side + column,
+            cells + row * side + val - 1,
+            2 * cells + column * side + val - 1,
+            3 * cells + house * side + val - 1,
+        ];
+        let first = self.left.len();
+        for (k, header) in headers.map(|header| header + 1).into_iter().enumerate() {
+            let node = first + k;
+            self.left.push(if k == 0 { first + 3 } else { node - 1 });
+            self.right.push(if k == 3 { first } else { node + 1 });
+            self.up.push(self.up[header]);
+            self.down.push(header);
+            let above = self.up[header];
+            self.down[above] = node;
+            self.up[header] = node;
+            self.column.push(header);
+            self.candidate.push((row * side + column) * side + val - 1);
+            self.size[header] += 1;
+        }
+    }
+    fn cover(&mut self, header: usize) {
+        let (left, right) = (self.left[header], self.right[header]);
+        self.right[left] = right;
+        self.left[right] = left;
+        let mut i = self.down[header];
+        while i != header {
+            let mut j = self.right[i];
+            while j != i {
+                let (up, down) = (self.up[j], self.down[j]);
+                self.down[up] = down;
+                self.up[down] = up;
+                self.size[self.column[j]] -= 1;
+                j = self.right[j];
+            }
+            i = self.down[i];
+        }
+    }
+    fn uncover(&mut self, header: usize) {
+        let mut i = self.up[header];
+        while i != header {
+            let mut j = self.left[i];
+            while j != i {
+                self.size[self.column[j]] += 1;
+                let (up, down) = (self.up[j], self.down[j]);
+                self.down[up] = j;
+                self.up[down] = j;
+                j = self.left[j];
+            }
+            i = self.up[i];
+        }
+        let (left, right) = (self.left[header], self.right[header]);
+        self.right[left] = header;
+        self.left[right] = header;
+    }
+    /// the column with the fewest rows left, which keeps the branching down
+    fn smallest_column(&self) -> usize {
+        let mut best = self.right[ROOT];
+        let mut header = best;
+        while header != ROOT {
+            if self.size[header] < self.size[best] {
+                best = header;
+            }
+            header = self.right[header];
+        }
+        best
+    }
+    /// picks the row `node` is in, covering every other column it fills
+    fn choose(&mut self, node: usize) {
+        let mut j = self.right[node];
+        while j != node {
+            self.cover(self.column[j]);
+            j = self.right[j];
+        }
+    }
+    /// undoes `choose`, uncovering in the reverse order
+    fn unchoose(&mut self, node: usize) {
+        let mut j = self.left[node];
+        while j != node {
+            self.uncover(self.column[j]);
+            j = self.left[j];
+        }
+    }
+    /// Algorithm X, calling `found` with the candidates of each solution until it breaks
+    ///
+    /// every column with more than one row left is a guess, which counts toward the depth. each
+    /// covered column is a frame on a heap allocated stack rather than a call, and the matrix is
+    /// restored however the search ends
+    fn search<S: Shape>(
+        &mut self,
+        options: &SolveOptions<S>,
+        start: Duration,
+        stats: &mut SolveStats,
+        found: &mut impl FnMut(&[usize]) -> ControlFlow<()>,
+    ) -> Result<ControlFlow<()>, UpdateError> {
+        let mut chosen = vec![];
+        let mut frames: Vec<Frame> = vec![];
+        let result = loop {
+            if let Some(why) = options.stop_reason(start) {
+                break Err(why);
+            }
+            if chosen.len() > self.deepest.len() {
+                self.deepest.clone_from(&chosen);
+            }
+            // every frame has a row picked, so go a level deeper
+            if self.right[ROOT] == ROOT {
+                if found(&chosen).is_break() {
+                    break Ok(ControlFlow::Break(()));
+                }
+            } else {
+                let header = self.smallest_column();
+                let guessing = self.size[header] > 1;
+                let depth = frames.last().map_or(0, |frame| frame.depth) + usize::from(guessing);
+                if guessing {
+                    if options.max_depth.is_some_and(|max| depth > max) {
+                        break Err(UpdateError::DepthLimit);
+                    }
+                    stats.max_depth = stats.max_depth.max(depth);
+                }
+                self.cover(header);
+                frames.push(Frame {
+                    header,
+                    node: header,
+                    depth,
+                    guessing,
+                });
+            }
+            // move the deepest frame on to its next row, dropping frames that run out
+            if let Some(result) = self.advance(&mut frames, &mut chosen, stats) {
+                break Ok(result);
+            }
+        };
+        while let Some(frame) = frames.pop() {
+            if frame.node != frame.header {
+                self.unchoose(frame.node);
+            }
+            self.uncover(frame.header);
+        }
+        result
+    }
+    /// picks the next row of the deepest frame with one left, or gives `Continue` once every
+    /// frame has run out
+    fn advance(
+        &mut self,
+        frames: &mut Vec<Frame>,
+        chosen: &mut Vec<usize>,
+        stats: &mut SolveStats,
+    ) -> Option<ControlFlow<()>> {
+        while let Some(frame) = frames.last_mut() {
+            if frame.node != frame.header {
+                self.unchoose(frame.node);
+                chosen.pop();
+            }
+            frame.node = self.down[frame.node];
+            if frame.node == frame.header {
+                self.uncover(frame.header);
+                frames.pop();
+                continue;
+            }
+            stats.guesses += usize::from(frame.guessing);
+            chosen.push(self.candidate[frame.node]);
+            self.choose(frame.node);
+            return None;
+        }
+        Some(ControlFlow::Continue(()))
+    }
+}
+
+/// a covered column and the row of it that is picked
+struct Frame {
+    header: usize,
+    /// the picked row's node in this column, or `header` before the first one is picked
+    node: usize,
+    /// the guesses stacked up to and including this one
+    depth: usize,
+    guessing: bool,
+}
+
+impl<S: Shape> Board<S> {
+    /// solves the board with dancing links instead of guessing cell by cell
+    ///
+    /// the strategies and value order aren't used, but the depth limit, timeout, and cancel token
+    /// are
+    pub(crate) fn solve_dlx(
+        self,
+        options: &SolveOptions<S>,
+    ) -> Result<(Board<S>, SolveStats), SolveError<S>> {
+        let start = options.clock.now();
+        let (solution, mut stats, deepest) = catch_internal("solving with dancing links", || {
+            let mut stats = SolveStats::default();
+            let mut solution = None;
+            let mut dlx = Dlx::new(&self);
+            let flow = dlx.search(options, start, &mut stats, &mut |rows| {
+                solution = Some(rows.to_vec());
+                ControlFlow::Break(())
+            });
+            (flow.map(|_| solution), stats, dlx.deepest)
+        })?;
+        stats.elapsed = options.clock.now().saturating_sub(start);
+        let mut candidates = match solution {
+            Err(why @ (UpdateError::Timeout | UpdateError::Cancelled)) => {
+                let best = self.with_candidates(&deepest);
+                return Err(SolveError::Stopped { why, best });
+            }
+            solution => solution?.ok_or(UpdateError::Impossible)?,
+        };
+        // one candidate per cell, so in order they line up with the cells
+        candidates.sort();
+        let board = CellPos::all()
+            .zip(candidates)
+            .map(|(pos, candidate)| {
+                let val = CellVal::new(candidate % S::SIDE + 1).expect("a candidate has a value");
+                (pos, Cell::Concrete(val))
+            })
+            .collect();
+        Ok((board, stats))
+    }
+    /// how many solutions the board has, counting no further than `limit`
+    ///
+    /// unlike `solve` this keeps going after the first solution, and stops as soon as it has
+    /// found `limit`. a `limit` of 2 is enough to tell whether a puzzle's solution is unique
+    ///
+    /// only fails with `UpdateError::Internal`, on a bug in this crate
+    pub fn count_solutions(&self, limit: usize) -> Result<usize, UpdateError> {
+        let mut count = 0;
+        if limit == 0 {
+            return Ok(count);
+        }
+        catch_internal(
+            "counting solutions",
+            AssertUnwindSafe(|| {
+                // without a depth limit, timeout, or cancel token the search can't fail
+                let _ = Dlx::new(self).search(
+                    &SolveOptions::<S>::default(),
+                    Duration::ZERO,
+                    &mut SolveStats::default(),
+                    &mut |_| {
+                        count += 1;
+                        if count < limit {
+                            ControlFlow::Continue(())
+                        } else {
+                            ControlFlow::Break(())
+                        }
+                    },
+                );
+            }),
+        )?;
+        Ok(count)
+    }
+    /// whether the board is a proper puzzle, with exactly one solution
+    ///
+    /// `Ok(false)` means it has more than one, and an error means it has none, either because
+    /// the givens break a rule or because every way of filling it in does
+    pub fn has_unique_solution(&self) -> Result<bool, UpdateError> {
+        if let Some(why) = self.clone().propagate().contradiction {
+            Err(why)?
+        }
+        match self.count_solutions(2)? {
+            0 => Err(UpdateError::Impossible),
+            count => Ok(count == 1),
+        }
+    }
+    /// the board with the cells of `candidates` filled in
+    fn with_candidates(&self, candidates: &[usize]) -> Board<S> {
+        CellPos::all()
+            .enumerate()
+            .map(
+                |(i, pos)| match candidates.iter().find(|&&c| c / S::SIDE == i) {
+                    Some(candidate) => {
+                        let val =
+                            CellVal::new(candidate % S::SIDE + 1).expect("a candidate has a value");
+                        (pos, Cell::Concrete(val))
+                    }
+                    None => (pos, self.cell(pos).clone()),
+                },
+            )
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        board::{macros::*, units},
+        Classic, Size,
+    };
+
+    fn assert_solved(board: &Board) {
+        for unit in units() {
+            let mut vals: Vec<_> = unit
+                .iter()
+                .map(|&pos| match board.cell(pos) {
+                    Cell::Concrete(val) => val.into_inner(),
+                    Cell::Possibilities(_) => panic!("{pos:?} isn't solved"),
+                })
+                .collect();
+            vals.sort();
+            assert_eq!(vals, (1..=9).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn dlx_solves_an_empty_board() {
+        let (board, stats) = board!([]).solve_dlx(&SolveOptions::default()).unwrap();
+
+        assert_solved(&board);
+        assert!(stats.guesses > 0);
+    }
+    #[test]
+    fn dlx_keeps_the_givens() {
+        let (board, _) = board!([[5, 3, ?, ?, 7] [6, ?, ?, 1, 9, 5]])
+            .solve_dlx(&SolveOptions::default())
+            .unwrap();
+
+        assert_solved(&board);
+        assert_eq!(board.cell(pos!(0, 0)), &cell!(5));
+        assert_eq!(board.cell(pos!(1, 5)), &cell!(5));
+    }
+    #[test]
+    fn dlx_fails_on_clashing_givens() {
+        assert_eq!(
+            board!([[5, 5]]).solve_dlx(&SolveOptions::default()),
+            Err(SolveError::Update(UpdateError::Impossible))
+        );
+    }
+    #[test]
+    fn dlx_search_restores_the_matrix() {
+        let board = board!([[5, 3, ?, ?, 7]]);
+        let mut dlx = Dlx::new(&board);
+        let mut solutions = 0;
+
+        let flow = dlx.search(
+            &SolveOptions::<Classic>::default(),
+            Duration::ZERO,
+            &mut SolveStats::default(),
+            &mut |_| {
+                solutions += 1;
+                if solutions < 3 {
+                    ControlFlow::Continue(())
+                } else {
+                    ControlFlow::Break(())
+                }
+            },
+        );
+
+        assert_eq!(flow, Ok(ControlFlow::Break(())));
+        let fresh = Dlx::new(&board);
+        assert_eq!((dlx.left, dlx.right), (fresh.left, fresh.right));
+        assert_eq!((dlx.up, dlx.down), (fresh.up, fresh.down));
+        assert_eq!(dlx.size, fresh.size);
+    }
+    #[test]
+    fn dlx_stops_when_cancelled() {
+        let token = crate::CancelToken::new();
+        token.cancel();
+        let options = SolveOptions {
+            cancel_token: Some(token),
+            ..Default::default()
+        };
+
+        match board!([[5, 3]]).solve_dlx(&options) {
+            Err(SolveError::Stopped { why, best }) => {
+                assert_eq!(why, UpdateError::Cancelled);
+                assert_eq!(best, board!([[5, 3]]));
+            }
+            other => panic!("expected a cancellation, got {other:?}"),
+        }
+    }
+    #[test]
+    fn count_solutions_stops_at_the_limit() {
+        assert_eq!(board!([]).count_solutions(2), Ok(2));
+        assert_eq!(board!([]).count_solutions(0), Ok(0));
+        assert_eq!(board!([[5, 5]]).count_solutions(2), Ok(0));
+        let (solution, _) = board!([]).solve_dlx(&SolveOptions::default()).unwrap();
+        assert_eq!(solution.count_solutions(2), Ok(1));
+    }
+    #[test]
+    fn count_solutions_works_on_every_size() {
+        // there are 12 latin squares of side 3, and 288 4x4 sudoku
+        assert_eq!(Board::<Size<1, 3>>::default().count_solutions(20), Ok(12));
+        assert_eq!(Board::<Size<2, 2>>::default().count_solutions(300), Ok(288));
+        let square = Board::<Size<1, 3>>::from_givens(vec![
+            vec![Some(1), Some(3), None],
+            vec![None, Some(1), None],
+            vec![None, None, None],
+        ])
+        .unwrap();
+        assert_eq!(square.count_solutions(2), Ok(1));
+    }
+    #[test]
+    fn has_unique_solution_tells_puzzles_apart() {
+        let (solution, _) = board!([]).solve_dlx(&SolveOptions::default()).unwrap();
+
+        assert_eq!(solution.has_unique_solution(), Ok(true));
+        assert_eq!(board!([]).has_unique_solution(), Ok(false));
+        assert_eq!(
+            board!([[5, 5]]).has_unique_solution(),
+            Err(UpdateError::InvalidConcrete)
+        );
+    }
+    #[test]
+    fn dlx_stops_at_depth_limit() {
+        let options = SolveOptions {
+            max_depth: Some(1),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            board!([]).solve_dlx(&options),
+            Err(SolveError::Update(UpdateError::DepthLimit))
+        );
+    }
+}
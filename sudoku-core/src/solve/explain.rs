@@ -0,0 +1,131 @@
+//! the steps of a solve written out as sentences, for teaching material
+use super::SolveStep;
+use crate::{board::Shape, Board, CellPos, Classic, UpdateError};
+use std::collections::BTreeMap;
+
+/// a cell the way puzzle books write it, counting from 1
+fn name(row: usize, column: usize) -> String {
+    format!("R{}C{}", row + 1, column + 1)
+}
+
+/// the row, column, or box all of `cells` are in, counting from 1
+fn unit_name(cells: &[CellPos]) -> String {
+    let same = |key: fn(&CellPos) -> usize| cells.windows(2).all(|w| key(&w[0]) == key(&w[1]));
+    match cells.first() {
+        Some(first) if same(|pos| pos.row()) => format!("row {}", first.row() + 1),
+        Some(first) if same(|pos| pos.column()) => format!("column {}", first.column() + 1),
+        Some(first) => format!("box {}", Classic::house_of(first.row(), first.column()) + 1),
+        None => "the board".to_string(),
+    }
+}
+
+/// the cells each value was ruled out of, smallest value first
+fn by_value(eliminations: &[(usize, usize, usize)]) -> BTreeMap<usize, Vec<String>> {
+    let mut values: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    for &(row, column, val) in eliminations {
+        values.entry(val).or_default().push(name(row, column));
+    }
+    values
+}
+
+/// the sentences for one step
+fn explain_step(step: &SolveStep) -> Vec<String> {
+    let mut sentences = vec![];
+    if step.technique == "singles" {
+        let unit = unit_name(&step.cells);
+        for &(row, column, val) in &step.placements {
+            let cell = name(row, column);
+            sentences.push(format!(
+                "{cell} is {val}, the only value {unit} leaves it — naked single"
+            ));
+        }
+        for (val, cells) in by_value(&step.eliminations) {
+            sentences.push(format!(
+                "{unit} already has a {val}, so {} can't be {val}",
+                cells.join(", ")
+            ));
+        }
+        return sentences;
+    }
+    let pattern: Vec<_> = step
+        .cells
+        .iter()
+        .map(|pos| name(pos.row(), pos.column()))
+        .collect();
+    let mut results: Vec<_> = by_value(&step.eliminations)
+        .into_iter()
+        .map(|(val, cells)| format!("{val} from {}", cells.join(", ")))
+        .collect();
+    if !results.is_empty() {
+        results[0] = format!("removes {}", results[0]);
+    }
+    results.extend(
+        step.placements
+            .iter()
+            .map(|&(row, column, val)| format!("places {val} in {}", name(row, column))),
+    );
+    sentences.push(format!(
+        "{} form a pattern that {} — {}",
+        pattern.join(", "),
+        results.join(" and "),
+        step.technique
+    ));
+    sentences
+}
+
+impl Board {
+    /// every deduction `solve_steps` makes, as sentences in the order they were made
+    ///
+    /// such as "R4C7 is 5, the only value box 6 leaves it — naked single". if the steps can't
+    /// finish the puzzle, the last sentence says why
+    pub fn explain(&self) -> Vec<String> {
+        let mut steps = self.clone().solve_steps();
+        let mut sentences: Vec<_> = steps
+            .by_ref()
+            .flat_map(|step| explain_step(&step))
+            .collect();
+        match steps.stopped() {
+            None => {}
+            Some(UpdateError::Incomplete) => {
+                sentences.push("nothing more can be deduced without guessing".to_string())
+            }
+            Some(why) => sentences.push(format!("the puzzle can't be solved: {why}")),
+        }
+        sentences
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn explain_names_the_cell_and_unit() {
+        let board = board!([[1, 2, 3, 4, 5, 6, 7, 8, ?]]);
+        let sentences = board.explain();
+
+        assert_eq!(
+            sentences[0],
+            "R1C9 is 9, the only value row 1 leaves it — naked single"
+        );
+        assert_eq!(
+            sentences.last().unwrap(),
+            "nothing more can be deduced without guessing"
+        );
+    }
+    #[test]
+    fn explain_groups_eliminations_by_value() {
+        assert_eq!(unit_name(&[pos!(3, 6), pos!(4, 7)]), "box 6");
+        assert_eq!(
+            explain_step(&SolveStep {
+                technique: "x-wing",
+                cells: vec![pos!(0, 0), pos!(0, 4), pos!(3, 0), pos!(3, 4)],
+                placements: vec![],
+                eliminations: vec![(5, 0, 7), (6, 4, 7)],
+                board_after: board!([]),
+            }),
+            ["R1C1, R1C5, R4C1, R4C5 form a pattern that removes 7 from R6C1, R7C5 — x-wing"]
+        );
+    }
+}
@@ -0,0 +1,289 @@
+//! new puzzles, dug out of a solved grid one cell at a time
+//!
+//! a cell is only dug out if the puzzle still has a single solution, so which cells are tried
+//! first decides how few givens the puzzle ends up with and how hard it is
+use crate::{
+    board::{CellPos, Shape},
+    errors::catch_internal,
+    Board, Classic, SolveError, SolvedBoard,
+};
+use rand::{seq::SliceRandom, Rng};
+use std::{cmp::Ordering, panic::AssertUnwindSafe};
+
+/// the order `Board::generate` tries to dig cells out in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DigStrategy {
+    /// every cell in a random order, which usually digs the furthest
+    #[default]
+    Random,
+    /// a cell and its mirror through the center together, so the givens have half turn symmetry
+    SymmetricPairs,
+    /// the outer ring first and the center last, which leaves the givens bunched in the middle
+    BorderFirst,
+    /// the center first and the outer ring last, which leaves the givens around the edges
+    CenterOut,
+}
+
+impl DigStrategy {
+    pub const ALL: [DigStrategy; 4] = [
+        DigStrategy::Random,
+        DigStrategy::SymmetricPairs,
+        DigStrategy::BorderFirst,
+        DigStrategy::CenterOut,
+    ];
+    pub fn name(self) -> &'static str {
+        match self {
+            DigStrategy::Random => "random",
+            DigStrategy::SymmetricPairs => "symmetric-pairs",
+            DigStrategy::BorderFirst => "border-first",
+            DigStrategy::CenterOut => "center-out",
+        }
+    }
+    /// the groups of cells to dig out together, in the order to try them
+    fn order<S: Shape, R: Rng + ?Sized>(self, rng: &mut R) -> Vec<Vec<CellPos<S>>> {
+        let last = S::SIDE - 1;
+        // how many rings in from the edge, 0 for the border up to 4 for the center of the classic
+        // board
+        let ring = |pos: &CellPos<S>| {
+            let (row, column) = (pos.row(), pos.column());
+            row.min(column).min(last - row).min(last - column)
+        };
+        let mut cells: Vec<_> = CellPos::all().collect();
+        cells.shuffle(rng);
+        // the sorts are stable, so cells in the same ring stay shuffled
+        match self {
+            DigStrategy::Random => {}
+            DigStrategy::SymmetricPairs => {
+                // each pair is kept once, from its cell that comes first, so a board with an
+                // even number of cells doesn't dig its middle pair twice
+                return cells
+                    .into_iter()
+                    .filter_map(|pos| {
+                        let mirror = CellPos::at(last - pos.row(), last - pos.column())
+                            .expect("the mirror of a cell is on the board");
+                        match pos.index().cmp(&mirror.index()) {
+                            Ordering::Less => Some(vec![pos, mirror]),
+                            Ordering::Equal => Some(vec![pos]),
+                            Ordering::Greater => None,
+                        }
+                    })
+                    .collect();
+            }
+            DigStrategy::BorderFirst => cells.sort_by_key(ring),
+            DigStrategy::CenterOut => cells.sort_by_key(|pos| last / 2 - ring(pos)),
+        }
+        cells.into_iter().map(|pos| vec![pos]).collect()
+    }
+}
+
+/// settings for `Board::generate`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GeneratorOptions {
+    pub dig: DigStrategy,
+    /// stop digging once the puzzle is down to this many givens
+    ///
+    /// 0 digs as far as the strategy can, which is rarely below the low 20s
+    pub target_givens: usize,
+}
+
+/// a puzzle from `Board::generate`, with how it was made
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated<S: Shape = Classic> {
+    pub puzzle: Board<S>,
+    /// the puzzle's only solution
+    pub solution: SolvedBoard<S>,
+    /// the strategy that dug it
+    pub dig: DigStrategy,
+    pub givens: usize,
+}
+
+impl Board {
+    /// a new puzzle with a single solution, dug out of a random solved grid
+    ///
+    /// only fails with an internal error, on a bug in this crate
+    pub fn generate<R: Rng + ?Sized>(
+        options: &GeneratorOptions,
+        rng: &mut R,
+    ) -> Result<Generated, SolveError> {
+        Board::generate_sized(options, rng)
+    }
+}
+
+impl<S: Shape> Board<S> {
+    /// a new puzzle for a board of any size, see `Board::generate`
+    pub fn generate_sized<R: Rng + ?Sized>(
+        options: &GeneratorOptions,
+        rng: &mut R,
+    ) -> Result<Generated<S>, SolveError<S>> {
+        let solution = Board::<S>::default().sample_solution(rng)?;
+        Board::from_solution(SolvedBoard::try_from(solution)?, options, rng)
+    }
+    /// a puzzle whose only solution is `solution`, for when the answer has to be a particular
+    /// grid, such as one that spells something out
+    ///
+    /// the cells are dug the same way `Board::generate` digs a random grid, with `rng` only
+    /// picking the order. a grid that breaks a rule has no solutions to keep unique, so nothing
+    /// gets dug out of it
+    pub fn from_solution<R: Rng + ?Sized>(
+        solution: SolvedBoard<S>,
+        options: &GeneratorOptions,
+        rng: &mut R,
+    ) -> Result<Generated<S>, SolveError<S>> {
+        catch_internal(
+            "generating a puzzle",
+            AssertUnwindSafe(|| Board::dig(solution, options, rng)),
+        )?
+    }
+    fn dig<R: Rng + ?Sized>(
+        solution: SolvedBoard<S>,
+        options: &GeneratorOptions,
+        rng: &mut R,
+    ) -> Result<Generated<S>, SolveError<S>> {
+        let values = solution.values();
+        let mut kept = vec![vec![true; S::SIDE]; S::SIDE];
+        let mut givens = S::CELLS;
+        let puzzle = |kept: &[Vec<bool>]| {
+            let lines = values
+                .iter()
+                .zip(kept)
+                .map(|(row, kept)| {
+                    row.iter()
+                        .zip(kept)
+                        .map(|(&val, &kept)| kept.then_some(val as i64))
+                        .collect()
+                })
+                .collect();
+            Board::from_givens(lines).expect("a dug grid only has values from the grid")
+        };
+        for group in options.dig.order::<S, _>(rng) {
+            if givens < options.target_givens + group.len() {
+                continue;
+            }
+            for pos in &group {
+                kept[pos.row()][pos.column()] = false;
+            }
+            if puzzle(&kept).count_solutions(2)? == 1 {
+                givens -= group.len();
+            } else {
+                for pos in &group {
+                    kept[pos.row()][pos.column()] = true;
+                }
+            }
+        }
+        Ok(Generated {
+            puzzle: puzzle(&kept),
+            solution,
+            dig: options.dig,
+            givens,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Size;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn mini_sudoku_generate_too() {
+        let options = GeneratorOptions {
+            target_givens: 12,
+            ..Default::default()
+        };
+        let generated =
+            Board::<Size<2, 3>>::generate_sized(&options, &mut StdRng::seed_from_u64(2)).unwrap();
+
+        assert!(generated.givens >= 12);
+        assert_eq!(generated.puzzle.has_unique_solution(), Ok(true));
+    }
+    #[test]
+    fn generated_puzzles_have_one_solution() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for dig in DigStrategy::ALL {
+            let options = GeneratorOptions {
+                dig,
+                target_givens: 30,
+            };
+            let generated = Board::generate(&options, &mut rng).unwrap();
+            let givens = generated.puzzle.givens();
+
+            assert_eq!(generated.dig, dig);
+            assert!(generated.givens >= 30);
+            assert_eq!(
+                givens.iter().flatten().filter(|&&given| given).count(),
+                generated.givens
+            );
+            assert_eq!(generated.puzzle.count_solutions(2), Ok(1));
+        }
+    }
+    #[test]
+    fn puzzles_can_be_dug_from_a_chosen_solution() {
+        let solution: SolvedBoard = Board::build(
+            (0..9)
+                .map(|row| {
+                    (0..9)
+                        .map(|column| Some(((row * 3 + row / 3 + column) % 9 + 1) as u8))
+                        .collect()
+                })
+                .collect(),
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+        let options = GeneratorOptions {
+            dig: DigStrategy::SymmetricPairs,
+            target_givens: 0,
+        };
+        let generated =
+            Board::from_solution(solution.clone(), &options, &mut StdRng::seed_from_u64(3))
+                .unwrap();
+
+        assert_eq!(generated.solution, solution);
+        assert!(generated.givens < 81);
+        assert_eq!(generated.puzzle.count_solutions(2), Ok(1));
+        let (solved, _) = generated.puzzle.solve_with(&Default::default()).unwrap();
+        assert_eq!(SolvedBoard::try_from(solved).unwrap(), solution);
+    }
+    #[test]
+    fn symmetric_pairs_keep_half_turn_symmetry() {
+        let options = GeneratorOptions {
+            dig: DigStrategy::SymmetricPairs,
+            target_givens: 0,
+        };
+        let generated = Board::generate(&options, &mut StdRng::seed_from_u64(1)).unwrap();
+        let givens = generated.puzzle.givens();
+
+        assert!((0..81).all(|i| givens[i / 9][i % 9] == givens[8 - i / 9][8 - i % 9]));
+    }
+    #[test]
+    fn symmetric_pairs_count_the_givens_on_even_boards() {
+        let options = GeneratorOptions {
+            dig: DigStrategy::SymmetricPairs,
+            target_givens: 0,
+        };
+        let order =
+            DigStrategy::SymmetricPairs.order::<Size<2, 2>, _>(&mut StdRng::seed_from_u64(1));
+        assert_eq!(order.len(), 8);
+        assert_eq!(order.iter().flatten().count(), 16);
+        for seed in 0..8 {
+            let generated =
+                Board::<Size<2, 2>>::generate_sized(&options, &mut StdRng::seed_from_u64(seed))
+                    .unwrap();
+            let givens = generated.puzzle.givens().into_iter().flatten();
+
+            assert_eq!(generated.givens, givens.filter(|&given| given).count());
+        }
+    }
+    #[test]
+    fn border_first_digs_the_border_first() {
+        let order = DigStrategy::BorderFirst.order::<Classic, _>(&mut StdRng::seed_from_u64(1));
+        let order: Vec<_> = order.into_iter().flatten().collect();
+
+        assert_eq!(order.len(), 81);
+        assert!(order[..32]
+            .iter()
+            .all(|pos| [0, 8].contains(&pos.row()) || [0, 8].contains(&pos.column())));
+        assert_eq!(order[80], CellPos::at(4, 4).unwrap());
+    }
+}
@@ -0,0 +1,99 @@
+//! greater-than signs between cells next to each other, as in futoshiki
+//!
+//! a sign only bounds its two cells: the larger has to be above the smallest candidate of the
+//! smaller, and the smaller below the largest candidate of the larger. a chain of signs narrows
+//! one link per pass, since each pass starts from what the last one left
+use super::sums;
+use crate::{
+    board::{Cell, CellPos, Shape},
+    Board, BuildError, Classic, UpdateError,
+};
+
+/// the value in `greater` is larger than the value in `less`, two cells next to each other in a
+/// row or column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Inequality<S: Shape = Classic> {
+    greater: CellPos<S>,
+    less: CellPos<S>,
+}
+
+impl<S: Shape> Inequality<S> {
+    /// a sign between two cells, failing if they aren't next to each other
+    pub fn new(greater: CellPos<S>, less: CellPos<S>) -> Result<Self, BuildError> {
+        let apart = greater.row().abs_diff(less.row()) + greater.column().abs_diff(less.column());
+        if apart != 1 {
+            Err(BuildError::Inequality {
+                greater: (greater.row(), greater.column()),
+                less: (less.row(), less.column()),
+            })?
+        }
+        Ok(Inequality { greater, less })
+    }
+    pub fn greater(&self) -> CellPos<S> {
+        self.greater
+    }
+    pub fn less(&self) -> CellPos<S> {
+        self.less
+    }
+    /// removes the candidates outside each cell's bounds and fills in cells left with one,
+    /// failing if the sign is broken
+    pub(crate) fn check_and_update(&self, board: &mut Board<S>) -> Result<(), UpdateError> {
+        let greater = sums::mask(board.cell(self.greater));
+        let less = sums::mask(board.cell(self.less));
+        if greater == 0 || less == 0 {
+            Err(UpdateError::Impossible)?
+        }
+        // values are bits 1 to SIDE, so the lowest bit set is the smallest value
+        let least = less.trailing_zeros();
+        let most = u64::BITS - 1 - greater.leading_zeros();
+        let kept = [
+            (self.greater, greater & !((2 << least) - 1)),
+            (self.less, less & ((1 << most) - 1)),
+        ];
+        for (pos, kept) in kept {
+            if let &Cell::Concrete(val) = board.cell(pos) {
+                if kept & (1 << val.into_inner()) == 0 {
+                    Err(UpdateError::InvalidConcrete)?
+                }
+            }
+        }
+        sums::narrow(board, kept)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn signs_go_between_neighbours() {
+        assert!(Inequality::new(pos!(0, 0), pos!(1, 0)).is_ok());
+        assert_eq!(
+            Inequality::new(pos!(0, 0), pos!(1, 1)),
+            Err(BuildError::Inequality {
+                greater: (0, 0),
+                less: (1, 1)
+            })
+        );
+        assert!(Inequality::new(pos!(0, 0), pos!(0, 0)).is_err());
+    }
+    #[test]
+    fn signs_narrow_both_cells_to_their_bounds() {
+        let mut board = board!([[{ 2, 5, 7 }, { 3, 6, 9 }]]);
+        Inequality::new(pos!(0, 0), pos!(0, 1))
+            .unwrap()
+            .check_and_update(&mut board)
+            .unwrap();
+
+        // the larger has to beat 3, and the smaller has to stay under 7
+        assert_eq!(board.cell(pos!(0, 0)), &cell!(? 5, 7));
+        assert_eq!(board.cell(pos!(0, 1)), &cell!(? 3, 6));
+        assert_eq!(
+            Inequality::new(pos!(0, 0), pos!(0, 1))
+                .unwrap()
+                .check_and_update(&mut board!([[4, 5]])),
+            Err(UpdateError::InvalidConcrete)
+        );
+    }
+}
@@ -0,0 +1,582 @@
+mod anneal;
+mod arrow;
+mod builder;
+mod cage;
+mod cancel;
+#[cfg(feature = "difficulty-model")]
+mod difficulty;
+mod dlx;
+mod explain;
+mod generate;
+mod inequality;
+mod order;
+mod parity;
+mod rating;
+mod rules;
+mod sample;
+mod sandwich;
+mod setter;
+mod state;
+mod steps;
+mod sums;
+mod variant;
+
+use crate::{
+    board::{self, Column, House, Index, Row, Shape},
+    clock::{Clock, SharedClock},
+    errors::catch_internal,
+    Board, Classic, SolveError, UpdateError,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::ControlFlow,
+    panic::AssertUnwindSafe,
+    time::Duration,
+};
+
+pub use arrow::Arrow;
+pub use builder::{NoBackend, Solver, SolverBackend, SolverBuilder};
+pub use cage::Cage;
+pub use cancel::CancelToken;
+#[cfg(feature = "difficulty-model")]
+pub use difficulty::{Difficulty, Model};
+pub use generate::{DigStrategy, Generated, GeneratorOptions};
+pub use inequality::Inequality;
+pub use order::ValueOrder;
+pub use parity::Parity;
+pub(crate) use rating::GUESSING;
+pub use rating::{Rating, Tier};
+pub use rules::RuleSet;
+pub use sample::HeatMap;
+pub use sandwich::Sandwich;
+pub use setter::{Analysis, GivenDiff, Reanalysis, Setter};
+pub use state::SolverState;
+pub use steps::{SolveStep, SolveSteps};
+pub use variant::Variant;
+
+type ControlSolution<S> = ControlFlow<Board<S>, Result<Board<S>, UpdateError>>;
+
+/// settings for `Board::solve_with`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SolveOptions<S: Shape = Classic> {
+    /// the most guesses the search may stack up before giving up with `UpdateError::DepthLimit`
+    ///
+    /// `None` means there is no limit
+    pub max_depth: Option<usize>,
+    /// the most values a forcing chain may place before it gives up on a candidate
+    ///
+    /// `None` uses the default of 8
+    pub max_chain_length: Option<usize>,
+    /// what the solve is timed with
+    pub clock: SharedClock,
+    /// the names of strategies the solver shouldn't use, from `strategy_names`
+    ///
+    /// names that aren't a strategy are ignored
+    pub disabled_strategies: BTreeSet<String>,
+    /// the order a guessed cell's candidates are tried in
+    ///
+    /// only the backtracking search guesses one cell at a time, so the dlx backend ignores this
+    pub value_order: ValueOrder,
+    /// breaks ties in which cell to guess and which value to try first with an rng seeded by
+    /// this, so adversarial puzzles can't count on the search always taking the same path
+    ///
+    /// like `value_order`, the dlx backend ignores this, and the anneal backend seeds its rng
+    /// with it instead. `None` takes the first cell in row-major order and the smallest value
+    pub tie_break_seed: Option<u64>,
+    /// how long the solve may take by `clock` before it stops with `UpdateError::Timeout`
+    ///
+    /// `None` means it can take as long as it needs
+    pub timeout: Option<Duration>,
+    /// stops the solve with `UpdateError::Cancelled` once it is cancelled
+    pub cancel_token: Option<CancelToken>,
+    /// rules on top of the classic ones that the solution has to follow
+    ///
+    /// only the backtracking backend follows them, and the others fail with
+    /// `SolveError::Unsupported`
+    pub variants: Vec<Variant<S>>,
+    /// times each kind of unit and each strategy by `clock` into `SolveStats::profile`
+    pub profile: bool,
+    /// the puzzle is known to have exactly one solution, which lets the strategies that count on
+    /// that, like bug+1, run
+    ///
+    /// on a puzzle with more than one solution they can rule out every solution but one, so only
+    /// set it once `Board::has_unique_solution` says so
+    pub unique_solution: bool,
+}
+
+impl<S: Shape> SolveOptions<S> {
+    /// runs `f`, adding how long it took to `profile[name]` if `self.profile` is on
+    pub(crate) fn timed<T>(
+        &self,
+        profile: &mut BTreeMap<&'static str, Duration>,
+        name: &'static str,
+        f: impl FnOnce() -> T,
+    ) -> T {
+        if !self.profile {
+            return f();
+        }
+        let start = self.clock.now();
+        let out = f();
+        *profile.entry(name).or_default() += self.clock.now().saturating_sub(start);
+        out
+    }
+    /// why a solve that started at `start` has to stop now, if it does
+    pub(crate) fn stop_reason(&self, start: Duration) -> Option<UpdateError> {
+        if self
+            .cancel_token
+            .as_ref()
+            .is_some_and(CancelToken::is_cancelled)
+        {
+            return Some(UpdateError::Cancelled);
+        }
+        let timed_out = self
+            .timeout
+            .is_some_and(|timeout| self.clock.now().saturating_sub(start) >= timeout);
+        timed_out.then_some(UpdateError::Timeout)
+    }
+}
+
+/// what the search did to find a solution
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SolveStats {
+    /// the most guesses that were stacked on top of each other at once
+    pub max_depth: usize,
+    /// how many boards with a guessed value were tried
+    pub guesses: usize,
+    /// how long the solve took, by `SolveOptions::clock`
+    pub elapsed: Duration,
+    /// how many candidates each technique ruled out, by its name from `strategy_names`, or
+    /// `"singles"` for the checks on rows, columns, houses, and variant regions
+    ///
+    /// filling in a cell counts as ruling out the other candidates it had. only the backtracking
+    /// backend counts them, including on guesses that turned out wrong
+    pub eliminations: BTreeMap<&'static str, usize>,
+    /// with `SolveOptions::profile` on, how long was spent in each of `PROFILED_UNITS` and each
+    /// strategy, by name
+    ///
+    /// only the backtracking backend is profiled
+    pub profile: BTreeMap<&'static str, Duration>,
+    /// the `SolveOptions::tie_break_seed` the search ran with, so a run can be repeated
+    pub seed: Option<u64>,
+}
+
+/// the names `SolveStats::profile` times the checks of each kind of unit under, in the order
+/// they run
+pub const PROFILED_UNITS: [&str; 4] = ["rows", "houses", "columns", "variants"];
+
+/// what `Board::propagate` reduced a board to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Propagation<S: Shape = Classic> {
+    /// the board with every candidate the units rule out removed
+    ///
+    /// if there was a contradiction, this is the board as it was when it was found
+    pub board: Board<S>,
+    /// why the board can't be solved, if propagating showed that it can't
+    pub contradiction: Option<UpdateError>,
+}
+
+impl<S: Shape> Board<S> {
+    /// Attempt to solve the given board
+    pub fn solve(self) -> Result<Board<S>, SolveError<S>> {
+        Ok(self.solve_with(&SolveOptions::default())?.0)
+    }
+    /// Attempt to solve the given board, reporting how the search went
+    ///
+    /// if it has to stop early, the board with the most cells filled in so far comes back in
+    /// `SolveError::Stopped`. use a `SolverState` to be able to pick the search up again
+    pub fn solve_with(
+        self,
+        options: &SolveOptions<S>,
+    ) -> Result<(Board<S>, SolveStats), SolveError<S>> {
+        let mut state = SolverState::new(self);
+        let board = state.resume(options)?;
+        Ok((board, state.stats().clone()))
+    }
+    /// removes the candidates each row, house, and column rules out, then fills in the cells
+    /// left with one, until a pass over every unit changes nothing
+    ///
+    /// unlike solving, nothing is guessed and no strategies are tried
+    pub fn propagate(self) -> Propagation<S> {
+        let board = self.clone();
+        catch_internal("propagating", AssertUnwindSafe(|| self.propagate_fully())).unwrap_or_else(
+            |panicked| Propagation {
+                board,
+                contradiction: Some(panicked.into()),
+            },
+        )
+    }
+    fn propagate_fully(mut self) -> Propagation<S> {
+        loop {
+            let before = self.clone();
+            if let Err(err) = self.propagate_pass() {
+                return Propagation {
+                    board: self,
+                    contradiction: Some(err),
+                };
+            }
+            if self == before {
+                return Propagation {
+                    board: self,
+                    contradiction: None,
+                };
+            }
+        }
+    }
+    /// one pass over the rows, then the houses, then the columns, in the order `validate` uses
+    fn propagate_pass(&mut self) -> Result<(), UpdateError> {
+        self.check_units::<Row>()?;
+        self.check_units::<House>()?;
+        self.check_units::<Column>()
+    }
+    fn check_units<C: board::ToSet>(&mut self) -> Result<(), UpdateError> {
+        Index::<S>::indexes().try_for_each(|i| self.get_set::<C>(i).check_and_update())
+    }
+    fn check_variants(&mut self, options: &SolveOptions<S>) -> Result<(), UpdateError> {
+        options
+            .variants
+            .iter()
+            .try_for_each(|variant| variant.check_and_update(self))
+    }
+    /// verifies that all of the rows, columns, and houses are valid, then the regions and cages of
+    /// any variants
+    /// ## Rules
+    ///
+    /// - for each row, column, and house:
+    ///   - there can only be one concrete instance of each cell value 1-9
+    ///   - for each value:
+    ///     - if it can only exist in one cell, that cell has that concrete value
+    ///     - it must be able to exist
+    ///   - for each cell
+    ///     - if it can only have one value, it has that value
+    ///     - it must be able to exist
+    ///
+    /// what each technique ruled out is added to `stats.eliminations`, and with
+    /// `options.profile` on, the time spent on each to `stats.profile`
+    pub(crate) fn validate(
+        self,
+        options: &SolveOptions<S>,
+        stats: &mut SolveStats,
+    ) -> BoardState<S> {
+        let mut init = BoardState::Valid(self);
+        let [rows, houses, columns, variants] = PROFILED_UNITS;
+
+        loop {
+            let before = init.candidate_count();
+            let profile = &mut stats.profile;
+            let mut board = options.timed(profile, rows, || init.validate_cell_lists::<Row>());
+            let mut board = options.timed(profile, houses, || board.validate_cell_lists::<House>());
+            let mut board =
+                options.timed(profile, columns, || board.validate_cell_lists::<Column>());
+            if !options.variants.is_empty() {
+                board = options.timed(profile, variants, || {
+                    board.validate_with(|board| board.check_variants(options))
+                });
+            }
+            if let (Some(before), Some(after)) = (before, board.candidate_count()) {
+                if before > after {
+                    *stats.eliminations.entry("singles").or_default() += before - after;
+                }
+            }
+            break match board {
+                board @ (BoardState::Finished(_) | BoardState::Err(_)) => board,
+                BoardState::Valid(board) | BoardState::PartiallyValid(board)
+                    if board.is_finished() =>
+                {
+                    BoardState::Finished(board)
+                }
+                // nothing changed in the last pass, so try the strategies before giving up
+                BoardState::Valid(mut board) => {
+                    match board.apply_strategies(options, &mut stats.profile) {
+                        Ok(Some(deduction)) => {
+                            *stats.eliminations.entry(deduction.strategy).or_default() +=
+                                deduction.eliminations.len();
+                            init = BoardState::Valid(board);
+                            continue;
+                        }
+                        Ok(None) => BoardState::Valid(board),
+                        Err(err) => BoardState::Err(err),
+                    }
+                }
+                BoardState::PartiallyValid(board) => {
+                    init = BoardState::Valid(board);
+                    continue;
+                }
+            };
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum BoardState<S: Shape = Classic> {
+    Finished(Board<S>),
+    Valid(Board<S>),
+    PartiallyValid(Board<S>),
+    Err(UpdateError),
+}
+impl<S: Shape> BoardState<S> {
+    /// the candidates left on the board, or `None` after an error
+    fn candidate_count(&self) -> Option<usize> {
+        match self {
+            Self::Finished(board) | Self::Valid(board) | Self::PartiallyValid(board) => {
+                Some(board.candidate_count())
+            }
+            Self::Err(_) => None,
+        }
+    }
+    fn validate_cell_lists<C: board::ToSet>(&mut self) -> BoardState<S> {
+        self.validate_with(|board| board.check_units::<C>())
+    }
+    fn validate_with(
+        &mut self,
+        validate: impl Fn(&mut Board<S>) -> Result<(), UpdateError>,
+    ) -> BoardState<S> {
+        match self {
+            board @ (Self::Finished(_) | Self::Err(_)) => board.clone(),
+            Self::Valid(board) => {
+                let old = board.clone();
+                let out = validate(board);
+                let new = board.clone();
+                match out {
+                    Ok(()) if new == old => Self::Valid(new),
+                    Ok(()) => Self::PartiallyValid(new),
+                    Err(err) => Self::Err(err),
+                }
+            }
+            Self::PartiallyValid(board) => match validate(board) {
+                Ok(()) => Self::PartiallyValid(board.clone()),
+                Err(err) => Self::Err(err),
+            },
+        }
+    }
+}
+
+impl<S: Shape> From<BoardState<S>> for ControlSolution<S> {
+    fn from(value: BoardState<S>) -> Self {
+        match value {
+            BoardState::Finished(board) => ControlFlow::Break(board),
+            BoardState::Valid(board) => ControlFlow::Break(board),
+            BoardState::PartiallyValid(board) => ControlFlow::Continue(Ok(board)),
+            BoardState::Err(why) => ControlFlow::Continue(Err(why)),
+        }
+    }
+}
+impl<S: Shape> From<ControlSolution<S>> for BoardState<S> {
+    fn from(value: ControlSolution<S>) -> Self {
+        match value {
+            ControlFlow::Break(board) => BoardState::Finished(board),
+            ControlFlow::Continue(Ok(board)) => BoardState::Valid(board),
+            ControlFlow::Continue(Err(why)) => BoardState::Err(why),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+    use crate::clock::MockClock;
+    use crate::{strategy_names, CellPos, Size, Variant};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn other_sizes_solve_with_the_same_search() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let solution = Board::<Size<4, 4>>::default()
+            .sample_solution(&mut rng)
+            .unwrap();
+        let mut puzzle = solution.clone();
+        let givens = vec![vec![false; 16]; 16];
+        for cell in rand::seq::index::sample(&mut rng, 256, 120) {
+            puzzle.clear(CellPos::at(cell / 16, cell % 16).unwrap(), &givens);
+        }
+
+        assert_eq!(solution.count_solutions(2), Ok(1));
+        let (solved, stats) = puzzle.solve_with(&SolveOptions::default()).unwrap();
+        assert!(solved.is_finished());
+        assert_eq!(solved.count_solutions(2), Ok(1));
+        assert!(stats.eliminations.contains_key("singles"));
+        // a latin square with both diagonals, which leaves 48 of the 576 of side 4
+        let options = SolveOptions {
+            variants: vec![Variant::Diagonal],
+            ..Default::default()
+        };
+        let (square, _) = Board::<Size<1, 4>>::default().solve_with(&options).unwrap();
+        let values = square.values();
+        for diagonal in [
+            [(0, 0), (1, 1), (2, 2), (3, 3)],
+            [(0, 3), (1, 2), (2, 1), (3, 0)],
+        ] {
+            let mut vals: Vec<_> = diagonal
+                .iter()
+                .map(|&(row, column)| values[row][column])
+                .collect();
+            vals.sort();
+            assert_eq!(vals, [Some(1), Some(2), Some(3), Some(4)]);
+        }
+    }
+    #[test]
+    fn solve_with_reports_guesses() {
+        let (board, stats) = board!([]).solve_with(&SolveOptions::default()).unwrap();
+
+        assert!(board.is_finished());
+        assert!(stats.guesses > 0);
+        assert!(stats.max_depth > 0);
+    }
+    #[test]
+    fn solve_with_needs_no_guesses_for_finished_board() {
+        let board = board!([
+            [1, 2, 3, 4, 5, 6, 7, 8, 9]
+            [4, 5, 6, 7, 8, 9, 1, 2, 3]
+            [7, 8, 9, 1, 2, 3, 4, 5, 6]
+            [2, 3, 4, 5, 6, 7, 8, 9, 1]
+            [5, 6, 7, 8, 9, 1, 2, 3, 4]
+            [8, 9, 1, 2, 3, 4, 5, 6, 7]
+            [3, 4, 5, 6, 7, 8, 9, 1, 2]
+            [6, 7, 8, 9, 1, 2, 3, 4, 5]
+            [9, 1, 2, 3, 4, 5, 6, 7, 8]
+        ]);
+
+        let options = SolveOptions {
+            clock: SharedClock::new(MockClock::new()),
+            ..Default::default()
+        };
+
+        assert_eq!(board.solve_with(&options).unwrap().1, SolveStats::default());
+    }
+    #[test]
+    fn solve_with_counts_eliminations() {
+        let board = board!([
+            [1, 2, 3, 4, 5, 6, 7, 8, 9]
+            [4, 5, 6, 7, 8, 9, 1, 2, 3]
+            [7, 8, 9, 1, 2, 3, 4, 5, 6]
+            [2, 3, 4, 5, 6, 7, 8, 9, 1]
+            [5, 6, 7, 8, 9, 1, 2, 3, 4]
+            [8, 9, 1, 2, 3, 4, 5, 6, 7]
+            [3, 4, 5, 6, 7, 8, 9, 1, 2]
+            [6, 7, 8, 9, 1, 2, 3, 4, 5]
+            [9, 1, 2, 3, 4, 5, 6, 7, ?]
+        ]);
+        let (_, stats) = board.solve_with(&SolveOptions::default()).unwrap();
+
+        // the last cell loses its other 8 candidates to its row, column, and house
+        assert_eq!(stats.eliminations, BTreeMap::from([("singles", 8)]));
+        assert!(
+            board!([])
+                .solve_with(&SolveOptions::default())
+                .unwrap()
+                .1
+                .eliminations["singles"]
+                > 0
+        );
+    }
+    #[test]
+    fn solve_with_profiles_when_asked() {
+        // the strategies would take most of the test looking over every guess on an empty board
+        let options = SolveOptions {
+            profile: true,
+            disabled_strategies: strategy_names().into_iter().map(String::from).collect(),
+            ..Default::default()
+        };
+        let (_, stats) = board!([]).solve_with(&options).unwrap();
+
+        // the variants are only timed if there are some
+        assert!(PROFILED_UNITS[..3]
+            .iter()
+            .all(|unit| stats.profile.contains_key(unit)));
+        assert!(!stats.profile.contains_key("variants"));
+        let options = SolveOptions {
+            profile: false,
+            ..options
+        };
+        assert!(board!([])
+            .solve_with(&options)
+            .unwrap()
+            .1
+            .profile
+            .is_empty());
+    }
+    #[test]
+    fn solve_with_records_the_tie_break_seed() {
+        let options = SolveOptions {
+            tie_break_seed: Some(11),
+            disabled_strategies: strategy_names().into_iter().map(String::from).collect(),
+            ..Default::default()
+        };
+        let (board, stats) = board!([]).solve_with(&options).unwrap();
+
+        assert!(board.is_finished());
+        assert_eq!(stats.seed, Some(11));
+        assert_eq!(board!([]).solve_with(&options).unwrap().0, board);
+    }
+    #[test]
+    fn propagate_carries_singles_across_units() {
+        let propagation = board!([[1, 2, 3, 4, 5, 6, 7, 8, ?]]).propagate();
+        let board = &propagation.board;
+
+        assert_eq!(propagation.contradiction, None);
+        assert_eq!(board.cell(pos!(0, 8)), &cell!(9));
+        assert!(!board.cell(pos!(1, 6)).is_possible(cell_val!(9)));
+        assert!(!board.cell(pos!(5, 8)).is_possible(cell_val!(9)));
+    }
+    #[test]
+    fn propagate_leaves_an_empty_board_alone() {
+        assert_eq!(
+            board!([]).propagate(),
+            Propagation {
+                board: board!([]),
+                contradiction: None
+            }
+        );
+    }
+    #[test]
+    fn propagate_reports_contradictions() {
+        assert_eq!(
+            board!([[1, 1]]).propagate().contradiction,
+            Some(UpdateError::InvalidConcrete)
+        );
+    }
+    #[test]
+    fn solve_with_stops_at_the_timeout() {
+        let options = SolveOptions {
+            timeout: Some(Duration::ZERO),
+            ..Default::default()
+        };
+
+        match board!([[1, 2, 3]]).solve_with(&options) {
+            Err(SolveError::Stopped { why, best }) => {
+                assert_eq!(why, UpdateError::Timeout);
+                assert_eq!(best.cell(pos!(0, 2)), &cell!(3));
+            }
+            other => panic!("expected a timeout, got {other:?}"),
+        }
+    }
+    #[test]
+    fn solve_with_stops_when_cancelled() {
+        let token = CancelToken::new();
+        token.cancel();
+        let options = SolveOptions {
+            cancel_token: Some(token),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            board!([]).solve_with(&options),
+            Err(SolveError::Stopped {
+                why: UpdateError::Cancelled,
+                ..
+            })
+        ));
+    }
+    #[test]
+    fn solve_with_stops_at_depth_limit() {
+        let options = SolveOptions {
+            max_depth: Some(1),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            board!([]).solve_with(&options),
+            Err(SolveError::Update(UpdateError::DepthLimit))
+        );
+    }
+}
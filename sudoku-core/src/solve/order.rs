@@ -0,0 +1,120 @@
+use crate::{
+    board::{CellPos, CellVal, Shape},
+    Board,
+};
+use rand::{seq::SliceRandom, Rng};
+use std::cmp::Reverse;
+
+/// the order the backtracking search tries a cell's candidates in when it has to guess
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ValueOrder {
+    /// smallest value first
+    #[default]
+    Ascending,
+    /// the value that the fewest peers could also be first, so a guess rules out as little as
+    /// possible and is more likely to be right
+    LeastConstraining,
+    /// the value that the most peers could also be first, so a wrong guess fails sooner
+    PeerFrequency,
+    /// a shuffled order, the same every time for the same seed
+    Random { seed: u64 },
+}
+
+impl ValueOrder {
+    /// sorts the candidates of the cell at `pos`
+    ///
+    /// ties are broken by value, so only `Random` depends on `rng`, unless `shuffle_ties` breaks
+    /// them with `rng` instead
+    pub(crate) fn sort<S: Shape, R: Rng + ?Sized>(
+        self,
+        board: &Board<S>,
+        pos: CellPos<S>,
+        vals: &mut [CellVal<S>],
+        shuffle_ties: bool,
+        rng: &mut R,
+    ) {
+        let peers_with = |val: CellVal<S>| {
+            board
+                .peers(pos)
+                .filter(|&peer| board.cell(peer).is_possible(val))
+                .count()
+        };
+        // the sorts below are stable, so values that tie stay in this order
+        if shuffle_ties {
+            vals.shuffle(rng);
+        } else {
+            vals.sort();
+        }
+        match self {
+            ValueOrder::Ascending => vals.sort(),
+            ValueOrder::LeastConstraining => vals.sort_by_cached_key(|&val| peers_with(val)),
+            ValueOrder::PeerFrequency => vals.sort_by_cached_key(|&val| Reverse(peers_with(val))),
+            ValueOrder::Random { .. } => vals.shuffle(rng),
+        }
+    }
+    /// what the search seeds its rng with
+    pub(crate) fn seed(self) -> u64 {
+        match self {
+            ValueOrder::Random { seed } => seed,
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::collections::BTreeSet;
+
+    fn sorted(order: ValueOrder, board: &Board) -> Vec<usize> {
+        let mut vals = vec![cell_val!(3), cell_val!(1), cell_val!(2)];
+        order.sort(
+            board,
+            pos!(0, 0),
+            &mut vals,
+            false,
+            &mut StdRng::seed_from_u64(order.seed()),
+        );
+        vals.into_iter().map(CellVal::into_inner).collect()
+    }
+
+    #[test]
+    fn value_orders_count_the_peers() {
+        // 1 is still a candidate in 12 peers, 2 in 16, and 3 in 14
+        let board = board!([[{ 1, 2, 3 }, { 2, 3 }, { 2, 3 }, 1, 1, 1, { 2 }, { 2 }] [1]]);
+
+        assert_eq!(sorted(ValueOrder::Ascending, &board), [1, 2, 3]);
+        assert_eq!(sorted(ValueOrder::LeastConstraining, &board), [1, 3, 2]);
+        assert_eq!(sorted(ValueOrder::PeerFrequency, &board), [2, 3, 1]);
+    }
+    #[test]
+    fn random_order_depends_only_on_the_seed() {
+        let board = board!([]);
+        let order = ValueOrder::Random { seed: 7 };
+
+        assert_eq!(sorted(order, &board), sorted(order, &board));
+        let mut vals = sorted(order, &board);
+        vals.sort();
+        assert_eq!(vals, [1, 2, 3]);
+    }
+    #[test]
+    fn shuffled_ties_keep_the_order() {
+        // 2 and 3 are each still a candidate in 16 peers, 1 in 14
+        let board = board!([[{ 1, 2, 3 }, { 2, 3 }, { 2, 3 }, 1, 1, 1] [1]]);
+        let orders: BTreeSet<_> = (0..20)
+            .map(|seed| {
+                let mut vals = vec![cell_val!(1), cell_val!(2), cell_val!(3)];
+                let mut rng = StdRng::seed_from_u64(seed);
+                let order = ValueOrder::LeastConstraining;
+                order.sort(&board, pos!(0, 0), &mut vals, true, &mut rng);
+                vals.into_iter()
+                    .map(CellVal::into_inner)
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        assert_eq!(orders, [vec![1, 2, 3], vec![1, 3, 2]].into());
+    }
+}
@@ -0,0 +1,79 @@
+//! even and odd cells, shaded in the grid to say which kind of value they hold
+//!
+//! a mark only has to take the other kind out of its cell, which the first pass does before any
+//! guessing, and every pass after checks that a filled in cell kept to it
+use super::sums;
+use crate::{
+    board::{Cell, CellPos, Shape},
+    Board, UpdateError,
+};
+
+/// which kind of value a marked cell holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Parity {
+    Even,
+    Odd,
+}
+
+impl Parity {
+    /// the values of this kind up to `side`, as bits of a mask
+    fn mask(self, side: usize) -> u64 {
+        let first = match self {
+            Parity::Even => 2,
+            Parity::Odd => 1,
+        };
+        (first..=side)
+            .step_by(2)
+            .fold(0, |mask, val| mask | 1 << val)
+    }
+    /// removes the other kind of value from the cell at `pos`, filling it in if one is left and
+    /// failing if it already holds the other kind
+    pub(crate) fn check_and_update<S: Shape>(
+        self,
+        board: &mut Board<S>,
+        pos: CellPos<S>,
+    ) -> Result<(), UpdateError> {
+        let kept = sums::mask(board.cell(pos)) & self.mask(S::SIDE);
+        if let Cell::Concrete(_) = board.cell(pos) {
+            if kept == 0 {
+                Err(UpdateError::InvalidConcrete)?
+            }
+        }
+        sums::narrow(board, [(pos, kept)])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn marks_keep_their_kind_of_value() {
+        let mut board = board!([[?, { 1, 2, 3 }, { 3, 4 }]]);
+        for column in 0..3 {
+            let parity = [Parity::Even, Parity::Odd, Parity::Even][column];
+            parity
+                .check_and_update(&mut board, pos!(0, column))
+                .unwrap();
+        }
+
+        assert_eq!(board.cell(pos!(0, 0)), &cell!(? 2, 4, 6, 8));
+        assert_eq!(board.cell(pos!(0, 1)), &cell!(? 1, 3));
+        assert_eq!(board.cell(pos!(0, 2)), &cell!(4));
+    }
+    #[test]
+    fn filled_in_cells_of_the_other_kind_fail() {
+        let mut board = board!([[3, { 1, 5 }]]);
+
+        assert_eq!(
+            Parity::Even.check_and_update(&mut board, pos!(0, 0)),
+            Err(UpdateError::InvalidConcrete)
+        );
+        assert_eq!(
+            Parity::Even.check_and_update(&mut board, pos!(0, 1)),
+            Err(UpdateError::Impossible)
+        );
+        assert!(Parity::Odd.check_and_update(&mut board, pos!(0, 0)).is_ok());
+    }
+}
@@ -0,0 +1,209 @@
+//! how hard a puzzle is for a person, from the techniques solving it takes
+use super::{SolveOptions, SolveStep};
+use crate::{strategy_names, Board, UpdateError};
+use std::fmt;
+
+/// what `rate` calls the technique of a solve that has to guess
+pub(crate) const GUESSING: &str = "guessing";
+/// what a solve that has to guess adds to the score, on top of its steps
+const GUESSING_WEIGHT: usize = 50;
+
+/// a rough difficulty band, from the hardest technique a puzzle needs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Tier {
+    /// singles alone solve it
+    Easy,
+    /// it needs intersections or naked and hidden pairs or triples
+    Medium,
+    /// it needs fish, wings, single digit patterns, coloring, or quads
+    Hard,
+    /// it needs almost locked sets, bug+1, forcing chains, or guessing
+    Expert,
+}
+
+impl Tier {
+    pub(crate) fn of(technique: &str) -> Self {
+        match technique {
+            "singles" => Tier::Easy,
+            "pointing" | "claiming" | "naked pair" | "hidden pair" | "naked triple"
+            | "hidden triple" => Tier::Medium,
+            "als-xz" | "bug+1" | "forcing chain" | GUESSING => Tier::Expert,
+            _ => Tier::Hard,
+        }
+    }
+}
+impl fmt::Display for Tier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Tier::Easy => "easy",
+            Tier::Medium => "medium",
+            Tier::Hard => "hard",
+            Tier::Expert => "expert",
+        })
+    }
+}
+
+/// how hard `Board::rate` found a puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rating {
+    /// the sum of the weights of every step. singles weigh 1, the strategies 2 and up in the
+    /// order of `strategy_names`, and having to guess adds 50
+    pub score: usize,
+    pub tier: Tier,
+    /// the technique that sets the tier, `"guessing"` if the techniques can't finish the puzzle
+    pub hardest: &'static str,
+    /// how many deductions were made before the puzzle was solved or a guess was needed
+    pub steps: usize,
+}
+impl fmt::Display for Rating {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (score {}, needs {})",
+            self.tier, self.score, self.hardest
+        )
+    }
+}
+
+impl Board {
+    /// solves the board with the strategies, one step at a time, and rates how hard that was
+    ///
+    /// fails if a step finds the puzzle can't be solved. bug+1 is only used if the puzzle has one
+    /// solution, so a puzzle with more that it would have finished is rated as guessing
+    pub fn rate(&self) -> Result<Rating, UpdateError> {
+        let names = strategy_names();
+        let weight = |technique: &str| match names.iter().position(|name| *name == technique) {
+            Some(rank) => rank + 2,
+            None => 1,
+        };
+        let mut steps = self.clone().solve_steps_with(&self.logic_options());
+        let mut rating = Rating {
+            score: 0,
+            tier: Tier::Easy,
+            hardest: "singles",
+            steps: 0,
+        };
+        for step in steps.by_ref() {
+            rating.score += weight(step.technique);
+            rating.steps += 1;
+            if weight(step.technique) > weight(rating.hardest) {
+                rating.hardest = step.technique;
+            }
+        }
+        match steps.stopped() {
+            None => {}
+            Some(UpdateError::Incomplete) => {
+                rating.score += GUESSING_WEIGHT;
+                rating.hardest = GUESSING;
+            }
+            Some(why) => Err(why)?,
+        }
+        rating.tier = Tier::of(rating.hardest);
+        Ok(rating)
+    }
+    /// the deductions that solve the board without guessing or any technique harder than
+    /// `level`, in the order they are made, or `None` if it can't be finished that way or has
+    /// more than one solution
+    ///
+    /// the steps are the certificate: each follows from the board the one before it left, so a
+    /// puzzle that gets them can be advertised as needing no guessing. the puzzle is checked with
+    /// `has_unique_solution` first, since no deductions can pick one of several solutions, and it
+    /// fails if that or a step finds the puzzle can't be solved
+    pub fn is_logic_solvable(&self, level: Tier) -> Result<Option<Vec<SolveStep>>, UpdateError> {
+        if !self.has_unique_solution()? {
+            return Ok(None);
+        }
+        let options = SolveOptions {
+            disabled_strategies: strategy_names()
+                .into_iter()
+                .filter(|name| Tier::of(name) > level)
+                .map(String::from)
+                .collect(),
+            unique_solution: true,
+            ..Default::default()
+        };
+        let mut steps = self.clone().solve_steps_with(&options);
+        let certificate: Vec<_> = steps.by_ref().collect();
+        match steps.stopped() {
+            None => Ok(Some(certificate)),
+            Some(UpdateError::Incomplete) => Ok(None),
+            Some(why) => Err(why),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn singles_are_easy() {
+        let board = board!([
+            [?, 2, 3, 4, 5, 6, 7, 8, 9]
+            [4, 5, 6, 7, 8, 9, 1, 2, 3]
+            [7, 8, 9, 1, 2, 3, 4, 5, 6]
+            [2, 3, 4, 5, 6, 7, 8, 9, 1]
+            [5, 6, 7, 8, 9, 1, 2, 3, 4]
+            [8, 9, 1, 2, 3, 4, 5, 6, 7]
+            [3, 4, 5, 6, 7, 8, 9, 1, 2]
+            [6, 7, 8, 9, 1, 2, 3, 4, 5]
+            [9, 1, 2, 3, 4, 5, 6, 7, 8]
+        ]);
+        let rating = board.rate().unwrap();
+
+        assert_eq!(rating.tier, Tier::Easy);
+        assert_eq!(rating.hardest, "singles");
+        assert_eq!(rating.score, rating.steps);
+    }
+    #[test]
+    fn guessing_is_expert() {
+        let rating = board!([]).rate().unwrap();
+
+        assert_eq!(rating.tier, Tier::Expert);
+        assert_eq!(rating.hardest, "guessing");
+        assert_eq!(rating.score, GUESSING_WEIGHT);
+        assert_eq!(Tier::of("naked pair"), Tier::Medium);
+        assert_eq!(Tier::of("x-wing"), Tier::Hard);
+    }
+    #[test]
+    fn rate_fails_on_broken_puzzles() {
+        assert_eq!(board!([[1, 1]]).rate(), Err(UpdateError::InvalidConcrete));
+    }
+    #[test]
+    fn certificates_solve_the_puzzle_without_guessing() {
+        let board = board!([
+            [5, 3, ?, ?, 7, ?, ?, ?, ?]
+            [6, ?, ?, 1, 9, 5, ?, ?, ?]
+            [?, 9, 8, ?, ?, ?, ?, 6, ?]
+            [8, ?, ?, ?, 6, ?, ?, ?, 3]
+            [4, ?, ?, 8, ?, 3, ?, ?, 1]
+            [7, ?, ?, ?, 2, ?, ?, ?, 6]
+            [?, 6, ?, ?, ?, ?, 2, 8, ?]
+            [?, ?, ?, 4, 1, 9, ?, ?, 5]
+            [?, ?, ?, ?, 8, ?, ?, 7, 9]
+        ]);
+        let certificate = board.is_logic_solvable(Tier::Easy).unwrap().unwrap();
+
+        assert!(certificate.iter().all(|step| step.technique == "singles"));
+        assert_eq!(
+            certificate.last().map(|step| &step.board_after),
+            Some(&board.solve().unwrap())
+        );
+        assert_eq!(board!([]).is_logic_solvable(Tier::Expert), Ok(None));
+        assert_eq!(
+            board!([[1, 1]]).is_logic_solvable(Tier::Easy),
+            Err(UpdateError::InvalidConcrete)
+        );
+    }
+    #[test]
+    fn puzzles_with_more_than_one_solution_have_no_certificate() {
+        let board: Board =
+            "8.32.5..95298.74.31769348257913865424587.1.96362549..893467.251687152934215493687"
+                .parse()
+                .unwrap();
+
+        assert_eq!(board.count_solutions(10), Ok(3));
+        assert_eq!(board.is_logic_solvable(Tier::Expert), Ok(None));
+    }
+}
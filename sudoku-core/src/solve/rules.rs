@@ -0,0 +1,110 @@
+//! named sets of variants for the common kinds of puzzle, so they don't have to be put together
+//! by hand
+//!
+//! a preset only knows the rules, not the puzzle, so `killer` comes without any cages and
+//! `RuleSet::with_cages` adds the puzzle's own
+use super::{Cage, Variant};
+use crate::SolveError;
+
+/// the variants of a kind of puzzle, see `RuleSet::preset`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RuleSet {
+    name: &'static str,
+    variants: Vec<Variant>,
+}
+
+impl RuleSet {
+    /// the name of every preset
+    pub const PRESETS: [&'static str; 5] =
+        ["classic", "x", "windoku", "antiknight-classic", "killer"];
+
+    /// the preset named `name`, one of `PRESETS`
+    pub fn preset(name: &str) -> Result<RuleSet, SolveError> {
+        let Some(name) = Self::PRESETS.into_iter().find(|&preset| preset == name) else {
+            return Err(SolveError::UnknownPreset(name.to_string()));
+        };
+        let variants = match name {
+            "classic" => vec![],
+            "x" => vec![Variant::Diagonal],
+            "windoku" => vec![Variant::Windoku],
+            "antiknight-classic" => vec![Variant::AntiKnight],
+            "killer" => vec![Variant::Killer(vec![])],
+            _ => unreachable!("every preset has its variants"),
+        };
+        Ok(RuleSet { name, variants })
+    }
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+    /// the variants on top of the classic rules, see `SolveOptions::variants`
+    pub fn variants(&self) -> &[Variant] {
+        &self.variants
+    }
+    /// true if the rules have killer cages but no cages have been added yet
+    pub fn needs_cages(&self) -> bool {
+        self.variants
+            .iter()
+            .any(|variant| matches!(variant, Variant::Killer(cages) if cages.is_empty()))
+    }
+    /// adds `cages` to the killer variant, adding one if the rules don't have it yet
+    pub fn with_cages(mut self, cages: Vec<Cage>) -> Self {
+        match self.variants.iter_mut().find_map(|variant| match variant {
+            Variant::Killer(cages) => Some(cages),
+            _ => None,
+        }) {
+            Some(existing) => existing.extend(cages),
+            None => self.variants.push(Variant::Killer(cages)),
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{board::macros::*, Classic, Solver, SolverBackend};
+
+    #[test]
+    fn every_preset_can_be_built_by_name() {
+        for name in RuleSet::PRESETS {
+            assert_eq!(RuleSet::preset(name).unwrap().name(), name);
+        }
+        let why = RuleSet::preset("jigsaw").unwrap_err();
+
+        assert_eq!(why, SolveError::UnknownPreset("jigsaw".to_string()));
+        assert_eq!(
+            why.to_string(),
+            "there is no \"jigsaw\" preset, the presets are classic, x, windoku, \
+             antiknight-classic, killer"
+        );
+    }
+    #[test]
+    fn killer_waits_for_its_cages() {
+        let rules = RuleSet::preset("killer").unwrap();
+        assert!(rules.needs_cages());
+
+        let cage = Cage::new(vec![pos!(0, 0), pos!(0, 1)], 3).unwrap();
+        let rules = rules.with_cages(vec![cage.clone()]);
+
+        assert!(!rules.needs_cages());
+        assert_eq!(rules.variants(), [Variant::Killer(vec![cage])]);
+        assert!(!RuleSet::preset("x").unwrap().needs_cages());
+    }
+    #[test]
+    fn x_solutions_fill_both_diagonals() {
+        let solver = Solver::builder()
+            .backend(SolverBackend::Backtracking)
+            .rules(RuleSet::preset("x").unwrap())
+            .build();
+        let solution: [[Option<usize>; 9]; 9] = solver.solve(board!([])).unwrap().0.into();
+
+        for diagonal in Variant::<Classic>::Diagonal.regions() {
+            let mut vals: Vec<_> = diagonal
+                .into_iter()
+                .map(|pos| solution[pos.row()][pos.column()])
+                .collect();
+            vals.sort();
+            assert_eq!(vals, (1..=9).map(Some).collect::<Vec<_>>());
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use super::{BoardState, SolveOptions, SolveStats};
+use crate::{
+    board::{Cell, CellPos, CellVal, Shape},
+    errors::catch_internal,
+    strategy::strategy_names,
+    Board, SolveError, UpdateError,
+};
+use rand::{seq::SliceRandom, Rng};
+use std::panic::AssertUnwindSafe;
+
+/// how often each cell took each value across a sample of solutions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeatMap {
+    /// how many solutions were sampled
+    pub samples: usize,
+    /// `counts[row][column][value - 1]` is how many of the samples had `value` in that cell
+    pub counts: [[[usize; 9]; 9]; 9],
+}
+
+impl HeatMap {
+    /// the share of the samples with `value` in the cell, from 0 to 1
+    pub fn probability(&self, row: usize, column: usize, value: usize) -> f64 {
+        if self.samples == 0 {
+            return 0.0;
+        }
+        self.counts[row][column][value - 1] as f64 / self.samples as f64
+    }
+    /// how uncertain the cell's value is in bits
+    ///
+    /// 0 if every sample agreed, up to `log2(9)` if every value was equally likely
+    pub fn entropy(&self, row: usize, column: usize) -> f64 {
+        (1..=9)
+            .map(|value| self.probability(row, column, value))
+            .filter(|&p| p > 0.0)
+            .map(|p| -p * p.log2())
+            .sum()
+    }
+    /// the cell with the most uncertain value, as `(row, column)`
+    ///
+    /// a good place for the next clue when setting a puzzle
+    pub fn most_uncertain(&self) -> (usize, usize) {
+        (0..81)
+            .map(|i| (i / 9, i % 9))
+            .max_by(|&(r1, c1), &(r2, c2)| self.entropy(r1, c1).total_cmp(&self.entropy(r2, c2)))
+            .unwrap_or_default()
+    }
+}
+
+impl<S: Shape> Board<S> {
+    /// a random solution of the board
+    ///
+    /// each guess goes in the unsolved cell with the fewest candidates, trying its values in a
+    /// random order. that doesn't pick every solution equally often, but it is close enough to see
+    /// which values a cell tends to take
+    pub fn sample_solution<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Board<S>, SolveError<S>> {
+        catch_internal("sampling a solution", AssertUnwindSafe(|| self.sample(rng)))?
+    }
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Board<S>, SolveError<S>> {
+        // only the unit checks run, since any solution will do
+        let options = SolveOptions {
+            disabled_strategies: strategy_names().into_iter().map(String::from).collect(),
+            ..Default::default()
+        };
+        let mut stack = vec![vec![self.clone()].into_iter()];
+        let mut err = UpdateError::InitError;
+        while let Some(frame) = stack.last_mut() {
+            let Some(board) = frame.next() else {
+                stack.pop();
+                continue;
+            };
+            match board.validate(&options, &mut SolveStats::default()) {
+                BoardState::Finished(board) => return Ok(board),
+                BoardState::Err(why) => err = why,
+                BoardState::Valid(board) | BoardState::PartiallyValid(board) => {
+                    stack.push(board.random_guesses(rng).into_iter())
+                }
+            }
+        }
+        Err(err.into())
+    }
+    /// a board for each value of the unsolved cell with the fewest candidates, in a random order
+    fn random_guesses<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<Board<S>> {
+        let Some((pos, set)) = CellPos::all()
+            .filter_map(|pos| match self.cell(pos) {
+                Cell::Possibilities(set) => Some((pos, set)),
+                Cell::Concrete(_) => None,
+            })
+            .min_by_key(|(_, set)| set.len())
+        else {
+            return vec![];
+        };
+        let mut vals: Vec<CellVal<S>> = set.iter().collect();
+        // sorted first so the order only depends on the rng
+        vals.sort();
+        vals.shuffle(rng);
+        vals.into_iter()
+            .map(|val| {
+                let mut board = self.clone();
+                *board.mut_cell(pos) = Cell::Concrete(val);
+                board
+            })
+            .collect()
+    }
+}
+
+impl Board {
+    /// how often each cell takes each value over `samples` random solutions
+    pub fn heat_map<R: Rng + ?Sized>(
+        &self,
+        samples: usize,
+        rng: &mut R,
+    ) -> Result<HeatMap, SolveError> {
+        let mut map = HeatMap {
+            samples,
+            counts: [[[0; 9]; 9]; 9],
+        };
+        for _ in 0..samples {
+            let solution: [[Option<usize>; 9]; 9] = self.sample_solution(rng)?.into();
+            for (r, row) in solution.iter().enumerate() {
+                for (c, val) in row.iter().enumerate() {
+                    if let Some(val) = val {
+                        map.counts[r][c][val - 1] += 1;
+                    }
+                }
+            }
+        }
+        Ok(map)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::board::macros::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn sample_solution_finishes_an_empty_board() {
+        let solution = board!([])
+            .sample_solution(&mut StdRng::seed_from_u64(3))
+            .unwrap();
+
+        assert!(solution.is_finished());
+    }
+    #[test]
+    fn sample_solution_fails_on_impossible_boards() {
+        let board = board!([[1, 1]]);
+
+        assert!(board
+            .sample_solution(&mut StdRng::seed_from_u64(3))
+            .is_err());
+    }
+    #[test]
+    fn heat_map_counts_every_sample() {
+        let map = board!([[1]])
+            .heat_map(5, &mut StdRng::seed_from_u64(4))
+            .unwrap();
+
+        assert_eq!(map.counts[0][0], [5, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(map.entropy(0, 0), 0.0);
+        assert!(map
+            .counts
+            .iter()
+            .flatten()
+            .all(|cell| cell.iter().sum::<usize>() == 5));
+    }
+}
@@ -0,0 +1,157 @@
+//! sandwich clues, the sum of the values between the 1 and the largest value of a row or
+//! column, the 9 on the classic board
+//!
+//! a clue tries every place the 1 and the 9 could go, keeping the candidates of the cells between
+//! them that some way of adding up to the clue still uses, see `sums`. a place the 1 or the 9
+//! can't go because nothing fits between them is ruled out of that cell
+use super::sums;
+use crate::{
+    board::{Cell, CellPos, Shape, Unit},
+    Board, BuildError, CellVal, Classic, UpdateError,
+};
+use std::marker::PhantomData;
+
+/// the values between the 1 and the 9 of a row or column add up to `sum`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Sandwich<S: Shape = Classic> {
+    unit: Unit,
+    sum: usize,
+    shape: PhantomData<S>,
+}
+
+impl<S: Shape> Sandwich<S> {
+    /// the largest sandwich, 2 + 3 + ... + 8 on the classic board
+    const MOST: usize = S::SIDE * (S::SIDE - 1) / 2 - 1;
+    /// the 1 and the 9 as bits of a mask
+    const CRUSTS: u64 = (1 << 1) | (1 << S::SIDE);
+
+    /// a clue on a row or column, failing for a house or a sum over `MOST`
+    pub fn new(unit: Unit, sum: usize) -> Result<Self, BuildError> {
+        match unit {
+            Unit::Row(i) | Unit::Column(i) if i < S::SIDE && sum <= Self::MOST => Ok(Sandwich {
+                unit,
+                sum,
+                shape: PhantomData,
+            }),
+            _ => Err(BuildError::Sandwich { unit, sum }),
+        }
+    }
+    pub fn unit(&self) -> Unit {
+        self.unit
+    }
+    pub fn sum(&self) -> usize {
+        self.sum
+    }
+    /// the cells of the clue's row or column, in order
+    pub fn cells(&self) -> Vec<CellPos<S>> {
+        self.unit.cells_of().collect()
+    }
+    /// removes the candidates no place for the 1 and the 9 uses and fills in cells left with
+    /// one, failing if nothing can add up to the clue
+    pub(crate) fn check_and_update(&self, board: &mut Board<S>) -> Result<(), UpdateError> {
+        let cells = self.cells();
+        let masks: Vec<u64> = cells
+            .iter()
+            .map(|&pos| sums::mask(board.cell(pos)))
+            .collect();
+        let [one, nine] = [1, S::SIDE].map(|val| CellVal::new(val).expect("1 and SIDE are values"));
+        let mut kept = vec![0; cells.len()];
+        let mut fits = false;
+        for (i, j) in (0..cells.len()).flat_map(|i| (0..cells.len()).map(move |j| (i, j))) {
+            // a cell that can only be the 1 or the 9 has to be one of the two placed here
+            let crust_elsewhere = masks
+                .iter()
+                .enumerate()
+                .any(|(k, &mask)| k != i && k != j && mask & Self::CRUSTS == mask);
+            if i == j
+                || masks[i] & (1 << 1) == 0
+                || masks[j] & (1 << S::SIDE) == 0
+                || crust_elsewhere
+            {
+                continue;
+            }
+            let (low, high) = (i.min(j), i.max(j));
+            let mut placed = board.clone();
+            *placed.mut_cell(cells[i]) = Cell::Concrete(one);
+            *placed.mut_cell(cells[j]) = Cell::Concrete(nine);
+            // counting the 1 and the 9 keeps them out of the middle
+            let crusts = 1 + S::SIDE;
+            let Some(middle) = sums::kept(&placed, &cells[low..=high], self.sum + crusts, true)?
+            else {
+                continue;
+            };
+            fits = true;
+            kept[i] |= 1 << 1;
+            kept[j] |= 1 << S::SIDE;
+            for (k, &mask) in masks.iter().enumerate() {
+                if k < low || k > high {
+                    kept[k] |= mask & !Self::CRUSTS;
+                }
+            }
+            for (pos, mask) in middle {
+                if let Some(k) = cells.iter().position(|&cell| cell == pos) {
+                    kept[k] |= mask;
+                }
+            }
+        }
+        if !fits {
+            Err(UpdateError::Impossible)?
+        }
+        sums::narrow(board, cells.into_iter().zip(kept))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn sandwiches_are_on_rows_and_columns() {
+        assert!(Sandwich::<Classic>::new(Unit::Column(8), 35).is_ok());
+        assert_eq!(
+            Sandwich::<Classic>::new(Unit::House(0), 10),
+            Err(BuildError::Sandwich {
+                unit: Unit::House(0),
+                sum: 10
+            })
+        );
+        assert_eq!(
+            Sandwich::<Classic>::new(Unit::Row(0), 36),
+            Err(BuildError::Sandwich {
+                unit: Unit::Row(0),
+                sum: 36
+            })
+        );
+    }
+    #[test]
+    fn a_sum_of_35_puts_the_crusts_at_the_ends() {
+        let mut board = board!([]);
+        Sandwich::new(Unit::Row(0), 35)
+            .unwrap()
+            .check_and_update(&mut board)
+            .unwrap();
+
+        assert_eq!(board.cell(pos!(0, 0)), &cell!(? 1, 9));
+        assert_eq!(board.cell(pos!(0, 8)), &cell!(? 1, 9));
+        assert_eq!(board.cell(pos!(0, 4)), &cell!(? 2, 3, 4, 5, 6, 7, 8));
+    }
+    #[test]
+    fn crusts_too_close_for_the_sum_are_ruled_out() {
+        // one value can't add up to 14, so the 9 has to be at least 3 cells from the 1
+        let mut board = board!([[?, ?, ?, ?, 1]]);
+        Sandwich::new(Unit::Row(0), 14)
+            .unwrap()
+            .check_and_update(&mut board)
+            .unwrap();
+
+        assert_eq!(board.cell(pos!(0, 3)), &cell!(? 2, 3, 4, 5, 6, 7, 8));
+        assert_eq!(board.cell(pos!(0, 0)), &cell!(? 2, 3, 4, 5, 6, 7, 8, 9));
+        assert_eq!(
+            Sandwich::new(Unit::Row(0), 0)
+                .unwrap()
+                .check_and_update(&mut board!([[1, 2, 9]])),
+            Err(UpdateError::Impossible)
+        );
+    }
+}
@@ -0,0 +1,219 @@
+//! the analysis a setter sees while editing a puzzle, kept up to date one edit at a time
+//!
+//! counting solutions and rating a puzzle after every keystroke is slow, but most edits only
+//! add a clue or two. a clue added to a puzzle with one solution either agrees with it, and the
+//! solution stands, or doesn't, and there are none left. a puzzle with no solutions has none
+//! however many clues are added. anything else is analysed again from scratch
+use super::Rating;
+use crate::{Board, SolvedBoard};
+
+/// the most clues an edit can add and still be analysed from the one before it
+const MAX_INCREMENTAL: usize = 3;
+
+/// the givens one version of a puzzle has that another doesn't
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GivenDiff {
+    /// givens that are new or have a new value, as `(row, column, value)` with rows and columns
+    /// from 0
+    pub added: Vec<(usize, usize, usize)>,
+    /// givens that are gone or had their value changed, with the old value
+    pub removed: Vec<(usize, usize, usize)>,
+}
+
+impl GivenDiff {
+    pub fn between(before: &Board, after: &Board) -> Self {
+        let before: [[Option<usize>; 9]; 9] = before.clone().into();
+        let after: [[Option<usize>; 9]; 9] = after.clone().into();
+        let mut diff = GivenDiff::default();
+        for (row, (before, after)) in before.iter().zip(&after).enumerate() {
+            for (column, (&before, &after)) in before.iter().zip(after).enumerate() {
+                if before == after {
+                    continue;
+                }
+                diff.removed.extend(before.map(|val| (row, column, val)));
+                diff.added.extend(after.map(|val| (row, column, val)));
+            }
+        }
+        diff
+    }
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// what a setter is shown about a puzzle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Analysis {
+    /// how many solutions, counting no further than 2
+    pub solutions: usize,
+    /// the solution, if there is only one
+    pub solution: Option<SolvedBoard>,
+    /// `None` if the puzzle has no solution
+    pub rating: Option<Rating>,
+}
+
+impl Analysis {
+    fn of(puzzle: &Board) -> Self {
+        // a bug while counting leaves the puzzle looking unsolvable rather than taking down the
+        // setter
+        let solutions = puzzle.count_solutions(2).unwrap_or(0);
+        let solution = match solutions {
+            1 => puzzle
+                .clone()
+                .solve()
+                .ok()
+                .and_then(|board| SolvedBoard::try_from(board).ok()),
+            _ => None,
+        };
+        Analysis {
+            solutions,
+            solution,
+            rating: if solutions > 0 {
+                puzzle.rate().ok()
+            } else {
+                None
+            },
+        }
+    }
+}
+
+/// how `Setter::update` got the new analysis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Reanalysis {
+    /// the givens didn't change, so neither did the analysis
+    Cached,
+    /// the solutions were worked out from the last analysis, and only the rating was redone
+    Incremental,
+    /// everything was analysed again
+    Full,
+}
+
+impl Reanalysis {
+    pub fn name(self) -> &'static str {
+        match self {
+            Reanalysis::Cached => "cached",
+            Reanalysis::Incremental => "incremental",
+            Reanalysis::Full => "full",
+        }
+    }
+}
+
+/// a puzzle being set and its analysis
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Setter {
+    puzzle: Board,
+    analysis: Analysis,
+}
+
+impl Setter {
+    pub fn new(puzzle: Board) -> Self {
+        Setter {
+            analysis: Analysis::of(&puzzle),
+            puzzle,
+        }
+    }
+    pub fn puzzle(&self) -> &Board {
+        &self.puzzle
+    }
+    pub fn analysis(&self) -> &Analysis {
+        &self.analysis
+    }
+    /// moves on to the next version of the puzzle, reusing as much of the last analysis as the
+    /// edit allows
+    pub fn update(&mut self, puzzle: Board) -> Reanalysis {
+        let diff = GivenDiff::between(&self.puzzle, &puzzle);
+        let how = if diff.is_empty() {
+            Reanalysis::Cached
+        } else if !diff.removed.is_empty() || diff.added.len() > MAX_INCREMENTAL {
+            self.analysis = Analysis::of(&puzzle);
+            Reanalysis::Full
+        } else {
+            match (self.analysis.solutions, &self.analysis.solution) {
+                (0, _) => Reanalysis::Incremental,
+                (1, Some(solution)) => {
+                    let values: [[usize; 9]; 9] = solution.clone().into();
+                    if diff
+                        .added
+                        .iter()
+                        .all(|&(row, column, val)| values[row][column] == val)
+                    {
+                        self.analysis.rating = puzzle.rate().ok();
+                    } else {
+                        self.analysis = Analysis {
+                            solutions: 0,
+                            solution: None,
+                            rating: None,
+                        };
+                    }
+                    Reanalysis::Incremental
+                }
+                _ => {
+                    self.analysis = Analysis::of(&puzzle);
+                    Reanalysis::Full
+                }
+            }
+        };
+        self.puzzle = puzzle;
+        how
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    fn puzzle() -> Board {
+        board!([
+            [5, 3, ?, ?, 7, ?, ?, ?, ?]
+            [6, ?, ?, 1, 9, 5, ?, ?, ?]
+            [?, 9, 8, ?, ?, ?, ?, 6, ?]
+            [8, ?, ?, ?, 6, ?, ?, ?, 3]
+            [4, ?, ?, 8, ?, 3, ?, ?, 1]
+            [7, ?, ?, ?, 2, ?, ?, ?, 6]
+            [?, 6, ?, ?, ?, ?, 2, 8, ?]
+            [?, ?, ?, 4, 1, 9, ?, ?, 5]
+            [?, ?, ?, ?, 8, ?, ?, 7, 9]
+        ])
+    }
+    /// the puzzle with `val` given at `row` and `column`
+    fn with_given(puzzle: &Board, row: usize, column: usize, val: Option<u8>) -> Board {
+        let values: [[Option<usize>; 9]; 9] = puzzle.clone().into();
+        let mut lines: Vec<Vec<_>> = values
+            .iter()
+            .map(|row| row.iter().map(|val| val.map(|val| val as u8)).collect())
+            .collect();
+        lines[row][column] = val;
+        Board::build(lines).unwrap()
+    }
+
+    #[test]
+    fn added_clues_are_checked_against_the_solution() {
+        let mut setter = Setter::new(puzzle());
+        // the solution's first row goes 5, 3, 4, 6, and 2 is still a candidate for the 6
+        let agrees = with_given(&puzzle(), 0, 2, Some(4));
+        let disagrees = with_given(&agrees, 0, 3, Some(2));
+
+        assert_eq!(setter.update(puzzle()), Reanalysis::Cached);
+        assert_eq!(setter.update(agrees.clone()), Reanalysis::Incremental);
+        assert_eq!(setter.analysis(), Setter::new(agrees).analysis());
+        assert_eq!(setter.update(disagrees.clone()), Reanalysis::Incremental);
+        assert_eq!(setter.analysis(), Setter::new(disagrees).analysis());
+        assert_eq!(setter.analysis().solutions, 0);
+    }
+    #[test]
+    fn removed_clues_are_analysed_again() {
+        let mut setter = Setter::new(puzzle());
+        let removed = with_given(&puzzle(), 0, 0, None);
+
+        assert_eq!(
+            GivenDiff::between(&puzzle(), &removed),
+            GivenDiff {
+                added: vec![],
+                removed: vec![(0, 0, 5)],
+            }
+        );
+        assert_eq!(setter.update(removed.clone()), Reanalysis::Full);
+        assert_eq!(setter.analysis(), Setter::new(removed).analysis());
+    }
+}
@@ -0,0 +1,476 @@
+//! a backtracking search that can be stopped, saved, and picked up again
+//!
+//! a saved state is plain text, one line per item:
+//!
+//! ```text
+//! solver-state 1
+//! stats <guesses> <max depth> <elapsed nanoseconds> <tie break seed, or ->
+//! eliminations <technique>=<count> ...  (only if any, with dashes for spaces in names)
+//! profile <unit or technique>=<nanoseconds> ...  (only if profiled, named the same way)
+//! error <the last dead end, such as Impossible, or Internal and what it was doing, with dashes>
+//! pending <board>                       (only before the first step)
+//! best <board>
+//! frame <cell 0-80> <values left> <board>   (one per guess, outermost first)
+//! ```
+//!
+//! a board is 81 cells separated by spaces, each a digit for a value or `?` followed by the
+//! candidates left
+use super::{BoardState, SolveOptions, SolveStats, PROFILED_UNITS};
+use crate::{
+    board::{Candidates, Cell, CellPos, Shape},
+    clock::Clock,
+    errors::{catch_internal, CONTEXTS},
+    strategy_names, Board, CellVal, Classic, ParseError, SolveError, UpdateError,
+};
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+use std::{fmt, panic::AssertUnwindSafe, str::FromStr, time::Duration};
+
+const HEADER: &str = "solver-state 1";
+
+/// where a backtracking search is, so it can be saved and resumed later
+///
+/// `Board::solve_with` is `SolverState::new` followed by one `resume`. if a timeout or cancel
+/// token stops it, the state keeps every guess still to be tried, and writing it with `Display`
+/// and reading it back with `FromStr` moves the search to another time or process
+///
+/// the text form is only written and read for 9x9 boards. a search on another size can still
+/// be stopped and resumed in the same process
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolverState<S: Shape = Classic> {
+    /// the board the search starts from, until the first `resume` checks it
+    pending: Option<Board<S>>,
+    stack: Vec<Frame<S>>,
+    best: Board<S>,
+    err: UpdateError,
+    stats: SolveStats,
+}
+
+/// a guessed cell and the values it has left to try
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Frame<S: Shape> {
+    board: Board<S>,
+    pos: CellPos<S>,
+    vals: Vec<CellVal<S>>,
+}
+
+impl<S: Shape> Frame<S> {
+    fn new(board: Board<S>, options: &SolveOptions<S>, rng: &mut StdRng) -> Option<Self> {
+        let shuffle_ties = options.tie_break_seed.is_some();
+        let ties: Option<&mut dyn RngCore> = if shuffle_ties { Some(&mut *rng) } else { None };
+        let (pos, mut vals) = board.branch(ties)?;
+        let order = options.value_order;
+        order.sort(&board, pos, &mut vals, shuffle_ties, rng);
+        Some(Frame { board, pos, vals })
+    }
+}
+impl<S: Shape> Iterator for Frame<S> {
+    type Item = Board<S>;
+
+    fn next(&mut self) -> Option<Board<S>> {
+        if self.vals.is_empty() {
+            return None;
+        }
+        let val = self.vals.remove(0);
+        Some(self.pos.make_concrete_board(&self.board, val))
+    }
+}
+
+impl<S: Shape> SolverState<S> {
+    /// a search of `board` that hasn't started yet
+    pub fn new(board: Board<S>) -> Self {
+        SolverState {
+            pending: Some(board.clone()),
+            stack: vec![],
+            best: board,
+            err: UpdateError::InitError,
+            stats: SolveStats::default(),
+        }
+    }
+    /// how the search has gone over every `resume` so far
+    pub fn stats(&self) -> &SolveStats {
+        &self.stats
+    }
+    /// the board with the most cells filled in that the search has got to
+    pub fn best(&self) -> &Board<S> {
+        &self.best
+    }
+    /// carries on the depth first search until it finds a solution, runs out, or is stopped
+    ///
+    /// `options` should be the same each time. a timeout counts from the start of this call,
+    /// and after a solution, resuming again looks for the next one. `Random` value orders and
+    /// tie breaks are reseeded from their seeds and the number of guesses so far, so they stay
+    /// repeatable
+    pub fn resume(&mut self, options: &SolveOptions<S>) -> Result<Board<S>, SolveError<S>> {
+        let start = options.clock.now();
+        let found = catch_internal("solving", AssertUnwindSafe(|| self.search(options, start)))?;
+        self.stats.elapsed += options.clock.now().saturating_sub(start);
+        found
+    }
+    /// each guess pushes a frame on a heap allocated stack instead of recurring, so deep
+    /// searches can't overflow the call stack
+    fn search(
+        &mut self,
+        options: &SolveOptions<S>,
+        start: Duration,
+    ) -> Result<Board<S>, SolveError<S>> {
+        let seed = options.value_order.seed() ^ options.tie_break_seed.unwrap_or(0);
+        let mut rng = StdRng::seed_from_u64(seed ^ self.stats.guesses as u64);
+        self.stats.seed = options.tie_break_seed;
+        if let Some(board) = self.pending.take() {
+            match board.validate(options, &mut self.stats) {
+                BoardState::Finished(board) => return Ok(board),
+                BoardState::Err(err) => return Err(err.into()),
+                BoardState::Valid(board) | BoardState::PartiallyValid(board) => {
+                    self.best = board.clone();
+                    self.stack.extend(Frame::new(board, options, &mut rng));
+                }
+            }
+        }
+        while let Some(frame) = self.stack.last() {
+            if let Some(why) = options.stop_reason(start) {
+                let best = self.best.clone();
+                return Err(SolveError::Stopped { why, best });
+            }
+            if frame.vals.is_empty() {
+                self.stack.pop();
+                continue;
+            }
+            // checked before the guess is taken off its frame, so a search stopped at the limit
+            // still has it to try when resumed
+            let depth = self.stack.len();
+            if options.max_depth.is_some_and(|max| depth > max) {
+                return Err(UpdateError::DepthLimit.into());
+            }
+            let board = self
+                .stack
+                .last_mut()
+                .and_then(Frame::next)
+                .expect("the frame has values left");
+            self.stats.guesses += 1;
+            self.stats.max_depth = self.stats.max_depth.max(depth);
+            match board.validate(options, &mut self.stats) {
+                BoardState::Finished(board) => return Ok(board),
+                BoardState::Err(why) => self.err = why,
+                BoardState::Valid(board) | BoardState::PartiallyValid(board) => {
+                    if board.solved_cells() > self.best.solved_cells() {
+                        self.best = board.clone();
+                    }
+                    self.stack.extend(Frame::new(board, options, &mut rng));
+                }
+            }
+        }
+        Err(self.err.into())
+    }
+}
+
+/// every `UpdateError` but `Internal`, so one can be read back from its name
+///
+/// `Internal` is written with its context after it, which is read back from `CONTEXTS`
+const ERRORS: [UpdateError; 8] = [
+    UpdateError::InvalidConcrete,
+    UpdateError::MultipleConcrete,
+    UpdateError::InitError,
+    UpdateError::Incomplete,
+    UpdateError::Impossible,
+    UpdateError::DepthLimit,
+    UpdateError::Timeout,
+    UpdateError::Cancelled,
+];
+
+fn write_board(f: &mut fmt::Formatter<'_>, board: &Board) -> fmt::Result {
+    for pos in CellPos::all() {
+        match board.cell(pos) {
+            Cell::Concrete(val) => write!(f, " {}", val.into_inner())?,
+            Cell::Possibilities(set) => {
+                let mut vals: Vec<_> = set.iter().map(CellVal::into_inner).collect();
+                vals.sort();
+                write!(f, " ?")?;
+                for val in vals {
+                    write!(f, "{val}")?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+impl fmt::Display for SolverState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let SolveStats {
+            max_depth,
+            guesses,
+            elapsed,
+            seed,
+            eliminations,
+            profile,
+        } = &self.stats;
+        writeln!(f, "{HEADER}")?;
+        let seed = seed.map_or("-".to_string(), |seed| seed.to_string());
+        writeln!(
+            f,
+            "stats {guesses} {max_depth} {} {seed}",
+            elapsed.as_nanos()
+        )?;
+        if !eliminations.is_empty() {
+            write!(f, "eliminations")?;
+            for (technique, count) in eliminations {
+                write!(f, " {}={count}", technique.replace(' ', "-"))?;
+            }
+            writeln!(f)?;
+        }
+        if !profile.is_empty() {
+            write!(f, "profile")?;
+            for (name, time) in profile {
+                write!(f, " {}={}", name.replace(' ', "-"), time.as_nanos())?;
+            }
+            writeln!(f)?;
+        }
+        match self.err {
+            UpdateError::Internal(context) => {
+                writeln!(f, "error Internal {}", context.replace(' ', "-"))?
+            }
+            err => writeln!(f, "error {err:?}")?,
+        }
+        if let Some(board) = &self.pending {
+            write!(f, "pending")?;
+            write_board(f, board)?;
+            writeln!(f)?;
+        }
+        write!(f, "best")?;
+        write_board(f, &self.best)?;
+        writeln!(f)?;
+        for Frame { board, pos, vals } in &self.stack {
+            let cell = pos.index();
+            let vals: String = vals
+                .iter()
+                .map(|val| val.into_inner().to_string())
+                .collect();
+            // a frame with nothing left to try is popped before it is saved again
+            write!(
+                f,
+                "frame {cell} {}",
+                if vals.is_empty() { "-" } else { &vals[..] }
+            )?;
+            write_board(f, board)?;
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+fn digits(text: &str) -> Result<Vec<CellVal>, ParseError> {
+    text.chars()
+        .map(|c| {
+            c.to_digit(10)
+                .and_then(|d| CellVal::new(d as usize).ok())
+                .ok_or(ParseError::Character(c))
+        })
+        .collect()
+}
+
+fn read_board<'a>(cells: impl Iterator<Item = &'a str>) -> Result<Board, ParseError> {
+    let cells = cells
+        .map(|cell| match cell.strip_prefix('?') {
+            Some(vals) => Ok(Cell::Possibilities(
+                digits(vals)?.into_iter().collect::<Candidates>(),
+            )),
+            None => {
+                let vals = digits(cell)?;
+                match vals[..] {
+                    [val] => Ok(Cell::Concrete(val)),
+                    _ => Err(ParseError::SolverState),
+                }
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if cells.len() != 81 {
+        Err(ParseError::CellCount(cells.len()))?
+    }
+    Ok(CellPos::all().zip(cells).collect())
+}
+
+impl FromStr for SolverState {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, ParseError> {
+        let mut lines = crate::io::lines(text).filter(|line| !line.trim().is_empty());
+        if lines.next().map(str::trim) != Some(HEADER) {
+            Err(ParseError::SolverState)?
+        }
+        let number = |text: Option<&str>| -> Result<u128, ParseError> {
+            text.and_then(|text| text.parse().ok())
+                .ok_or(ParseError::SolverState)
+        };
+        // a `name=number` pair, where the name is one of `names` or a strategy with dashes for
+        // spaces
+        let named = |word: &str, names: &[&'static str]| {
+            let (name, count) = word.split_once('=').ok_or(ParseError::SolverState)?;
+            let name = names
+                .iter()
+                .copied()
+                .chain(strategy_names())
+                .find(|known| known.replace(' ', "-") == name)
+                .ok_or(ParseError::SolverState)?;
+            Ok::<_, ParseError>((name, number(Some(count))?))
+        };
+        let (mut pending, mut best, mut stack) = (None, None, vec![]);
+        let (mut err, mut stats) = (UpdateError::InitError, SolveStats::default());
+        for line in lines {
+            let mut words = line.split_whitespace();
+            match words.next() {
+                Some("stats") => {
+                    stats.guesses = number(words.next())? as usize;
+                    stats.max_depth = number(words.next())? as usize;
+                    stats.elapsed = Duration::from_nanos(number(words.next())? as u64);
+                    stats.seed = match words.next() {
+                        Some("-") => None,
+                        seed => Some(number(seed)? as u64),
+                    };
+                }
+                Some("eliminations") => {
+                    for word in words {
+                        let (technique, count) = named(word, &["singles"])?;
+                        stats.eliminations.insert(technique, count as usize);
+                    }
+                }
+                Some("profile") => {
+                    for word in words {
+                        let (name, time) = named(word, &PROFILED_UNITS)?;
+                        stats
+                            .profile
+                            .insert(name, Duration::from_nanos(time as u64));
+                    }
+                }
+                Some("error") => {
+                    let name = words.next().ok_or(ParseError::SolverState)?;
+                    err = match name {
+                        "Internal" => {
+                            let context = words.next().ok_or(ParseError::SolverState)?;
+                            let context = CONTEXTS
+                                .into_iter()
+                                .find(|known| known.replace(' ', "-") == context)
+                                .ok_or(ParseError::SolverState)?;
+                            UpdateError::Internal(context)
+                        }
+                        name => *ERRORS
+                            .iter()
+                            .find(|err| format!("{err:?}") == name)
+                            .ok_or(ParseError::SolverState)?,
+                    };
+                }
+                Some("pending") => pending = Some(read_board(words)?),
+                Some("best") => best = Some(read_board(words)?),
+                Some("frame") => {
+                    let cell = number(words.next())? as usize;
+                    let pos = CellPos::all().nth(cell).ok_or(ParseError::SolverState)?;
+                    let vals = match words.next() {
+                        Some("-") => vec![],
+                        Some(vals) => digits(vals)?,
+                        None => Err(ParseError::SolverState)?,
+                    };
+                    let board = read_board(words)?;
+                    stack.push(Frame { board, pos, vals });
+                }
+                _ => Err(ParseError::SolverState)?,
+            }
+        }
+        Ok(SolverState {
+            pending,
+            stack,
+            best: best.ok_or(ParseError::SolverState)?,
+            err,
+            stats,
+        })
+    }
+}
+
+/// saved as the same text as `Display`
+#[cfg(feature = "serde")]
+impl serde::Serialize for SolverState {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SolverState {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn a_stopped_search_resumes_where_it_left_off() {
+        let mut state = SolverState::new(board!([[1, 2, 3]]));
+        let stop_at_once = SolveOptions {
+            timeout: Some(Duration::ZERO),
+            profile: true,
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            state.resume(&stop_at_once),
+            Err(SolveError::Stopped {
+                why: UpdateError::Timeout,
+                ..
+            })
+        ));
+        let saved: SolverState = state.to_string().parse().unwrap();
+        assert_eq!(saved, state);
+        let solution = state.clone().resume(&SolveOptions::default()).unwrap();
+        assert!(solution.is_finished());
+        assert_eq!(saved.clone().resume(&SolveOptions::default()), Ok(solution));
+    }
+    #[test]
+    fn the_depth_limit_keeps_the_guess_it_stopped_at() {
+        let mut state = SolverState::new(board!([]));
+        let shallow = SolveOptions {
+            max_depth: Some(1),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            state.resume(&shallow),
+            Err(SolveError::Update(UpdateError::DepthLimit))
+        );
+        let stopped = state.clone();
+        assert_eq!(
+            state.resume(&shallow),
+            Err(SolveError::Update(UpdateError::DepthLimit))
+        );
+        assert_eq!(state.stack, stopped.stack);
+        assert_eq!(state.stats().guesses, stopped.stats().guesses);
+        assert!(state
+            .resume(&SolveOptions::default())
+            .unwrap()
+            .is_finished());
+    }
+    #[test]
+    fn every_error_reads_back() {
+        let internal = CONTEXTS.map(UpdateError::Internal);
+        for err in ERRORS.into_iter().chain(internal) {
+            let state = SolverState {
+                err,
+                ..SolverState::new(board!([]))
+            };
+
+            assert_eq!(state.to_string().parse(), Ok(state), "{err:?}");
+        }
+    }
+    #[test]
+    fn solver_state_rejects_other_text() {
+        assert_eq!(
+            "stats 1 2 3".parse::<SolverState>(),
+            Err(ParseError::SolverState)
+        );
+        let text = SolverState::new(board!([]))
+            .to_string()
+            .replace("best", "worst");
+        assert_eq!(text.parse::<SolverState>(), Err(ParseError::SolverState));
+    }
+}
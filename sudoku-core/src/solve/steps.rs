@@ -0,0 +1,246 @@
+use super::SolveOptions;
+use crate::{
+    board::{Cell, CellPos, Column, House, Index, Row, ToSet},
+    errors::catch_internal,
+    Board, UpdateError,
+};
+use std::{collections::BTreeMap, panic::AssertUnwindSafe};
+
+/// one deduction on the way to a solution, see `Board::solve_steps`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveStep {
+    /// the strategy's name from `strategy_names`, or `"singles"` for the checks on one row,
+    /// column, or house that every solve starts with
+    pub technique: &'static str,
+    /// the cells the deduction was made from, the unit's cells for `"singles"` or the pattern
+    /// the strategy found, in order
+    pub cells: Vec<CellPos>,
+    /// the values filled in, as `(row, column, value)` with rows and columns from 0
+    pub placements: Vec<(usize, usize, usize)>,
+    /// the candidates ruled out of cells that are still open, in the same form
+    pub eliminations: Vec<(usize, usize, usize)>,
+    pub board_after: Board,
+}
+
+/// the steps of a solve, one deduction at a time
+///
+/// it ends once the board is solved, nothing else can be deduced without guessing, or a step
+/// finds a contradiction. `stopped` tells those apart
+#[derive(Debug, Clone)]
+pub struct SolveSteps {
+    board: Board,
+    options: SolveOptions,
+    stopped: Option<UpdateError>,
+}
+
+impl SolveSteps {
+    /// why the steps ended early, or `None` if they haven't or the board is solved
+    ///
+    /// `UpdateError::Incomplete` means a guess is needed to go on
+    pub fn stopped(&self) -> Option<UpdateError> {
+        self.stopped
+    }
+    /// the board after the last step
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+    /// makes the next deduction, returning the technique that made it and the cells it used
+    fn step(&mut self) -> Result<(&'static str, Vec<CellPos>), UpdateError> {
+        if let Some(unit) = self.singles()? {
+            return Ok(("singles", unit));
+        }
+        match self
+            .board
+            .apply_strategies(&self.options, &mut BTreeMap::new())?
+        {
+            Some(deduction) => Ok((deduction.strategy, deduction.cells)),
+            None => Err(UpdateError::Incomplete),
+        }
+    }
+    /// checks one unit at a time, in the order `validate` does, then the regions and cages of any
+    /// variants, until one changes the board
+    fn singles(&mut self) -> Result<Option<Vec<CellPos>>, UpdateError> {
+        let kinds: [UnitCheck; 3] = [
+            Board::check_unit::<Row>,
+            Board::check_unit::<House>,
+            Board::check_unit::<Column>,
+        ];
+        for check in kinds {
+            for i in Index::indexes() {
+                if let Some(unit) = check(&mut self.board, i)? {
+                    return Ok(Some(unit));
+                }
+            }
+        }
+        for variant in &self.options.variants {
+            if let Some(cells) = variant.step(&mut self.board)? {
+                return Ok(Some(cells));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl Iterator for SolveSteps {
+    type Item = SolveStep;
+
+    fn next(&mut self) -> Option<SolveStep> {
+        if self.stopped.is_some() || self.board.is_finished() {
+            return None;
+        }
+        let before = self.board.clone();
+        // a panic is a bug, which ends the steps like a contradiction instead of the caller
+        let step = catch_internal(
+            "solving one step at a time",
+            AssertUnwindSafe(|| self.step()),
+        );
+        let (technique, cells) = match step.map_err(UpdateError::from).and_then(|step| step) {
+            Ok(step) => step,
+            Err(err) => {
+                self.stopped = Some(err);
+                return None;
+            }
+        };
+        let (placements, eliminations) = changes(&before, &self.board);
+        Some(SolveStep {
+            technique,
+            cells,
+            placements,
+            eliminations,
+            board_after: self.board.clone(),
+        })
+    }
+}
+
+impl Board {
+    /// solves the board as far as it can without guessing, one deduction at a time
+    ///
+    /// the steps end where `solve` would start guessing
+    pub fn solve_steps(self) -> SolveSteps {
+        self.solve_steps_with(&SolveOptions::default())
+    }
+    /// `solve_steps` with only the strategies `options` allows
+    pub fn solve_steps_with(self, options: &SolveOptions) -> SolveSteps {
+        SolveSteps {
+            board: self,
+            options: options.clone(),
+            stopped: None,
+        }
+    }
+    /// the next deduction `solve_steps` would make, without going on to the ones after it
+    ///
+    /// `None` if the board is solved or can't go on without a guess, and an error if the
+    /// deduction shows the board can't be solved. bug+1 is only used if the puzzle has one
+    /// solution
+    pub fn hint(&self) -> Result<Option<SolveStep>, UpdateError> {
+        let mut steps = self.clone().solve_steps_with(&self.logic_options());
+        match (steps.next(), steps.stopped()) {
+            (Some(step), _) => Ok(Some(step)),
+            (None, None | Some(UpdateError::Incomplete)) => Ok(None),
+            (None, Some(why)) => Err(why),
+        }
+    }
+    /// the default options, with `unique_solution` set if the board has exactly one solution
+    pub(crate) fn logic_options(&self) -> SolveOptions {
+        SolveOptions {
+            unique_solution: matches!(self.has_unique_solution(), Ok(true)),
+            ..Default::default()
+        }
+    }
+    /// runs the checks on one unit, returning its cells if they changed anything
+    fn check_unit<C: ToSet>(&mut self, i: Index) -> Result<Option<Vec<CellPos>>, UpdateError> {
+        let before = self.clone();
+        self.get_set::<C>(i).check_and_update()?;
+        Ok((*self != before).then(|| C::cells(i).collect()))
+    }
+}
+
+/// the values placed and the candidates eliminated between two boards
+/// checks unit `i` of one kind, giving its cells if that changed the board
+type UnitCheck = fn(&mut Board, Index) -> Result<Option<Vec<CellPos>>, UpdateError>;
+
+type Changes = (Vec<(usize, usize, usize)>, Vec<(usize, usize, usize)>);
+
+fn changes(before: &Board, after: &Board) -> Changes {
+    let (mut placements, mut eliminations) = (vec![], vec![]);
+    for (i, pos) in CellPos::all().enumerate() {
+        let (row, column) = (i / 9, i % 9);
+        match (before.cell(pos), after.cell(pos)) {
+            (Cell::Possibilities(_), &Cell::Concrete(val)) => {
+                placements.push((row, column, val.into_inner()))
+            }
+            (Cell::Possibilities(was), Cell::Possibilities(now)) => {
+                let mut gone: Vec<_> = was.difference(now).iter().collect();
+                gone.sort();
+                eliminations.extend(gone.into_iter().map(|val| (row, column, val.into_inner())));
+            }
+            _ => {}
+        }
+    }
+    (placements, eliminations)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn solve_steps_fill_in_singles_first() {
+        let mut steps = board!([[1, 2, 3, 4, 5, 6, 7, 8, ?]]).solve_steps();
+        let step = steps.next().unwrap();
+
+        assert_eq!(step.technique, "singles");
+        assert_eq!(step.placements, [(0, 8, 9)]);
+        assert_eq!(step.board_after.cell(pos!(0, 8)), &cell!(9));
+        assert_eq!(step.cells, CellPos::all().take(9).collect::<Vec<_>>());
+    }
+    #[test]
+    fn hint_is_the_first_step() {
+        let board = board!([[1, 2, 3, 4, 5, 6, 7, 8, ?]]);
+        let hint = board.hint().unwrap().unwrap();
+
+        assert_eq!(Some(hint), board.solve_steps().next());
+        assert_eq!(board!([]).hint(), Ok(None));
+        assert_eq!(board!([[1, 1]]).hint(), Err(UpdateError::InvalidConcrete));
+    }
+    #[test]
+    fn solve_steps_end_where_solve_does() {
+        let board = board!([
+            [5, 3, ?, ?, 7, ?, ?, ?, ?]
+            [6, ?, ?, 1, 9, 5, ?, ?, ?]
+            [?, 9, 8, ?, ?, ?, ?, 6, ?]
+            [8, ?, ?, ?, 6, ?, ?, ?, 3]
+            [4, ?, ?, 8, ?, 3, ?, ?, 1]
+            [7, ?, ?, ?, 2, ?, ?, ?, 6]
+            [?, 6, ?, ?, ?, ?, 2, 8, ?]
+            [?, ?, ?, 4, 1, 9, ?, ?, 5]
+            [?, ?, ?, ?, 8, ?, ?, 7, 9]
+        ]);
+        let mut steps = board.clone().solve_steps();
+
+        assert!(steps
+            .by_ref()
+            .all(|step| !step.placements.is_empty() || !step.eliminations.is_empty()));
+        assert_eq!(steps.stopped(), None);
+        assert_eq!(steps.board(), &board.solve().unwrap());
+    }
+    #[test]
+    fn bug_plus_one_waits_for_a_unique_solution() {
+        // three solutions, which bug+1 would wrongly narrow down to one
+        let board: Board =
+            "8.32.5..95298.74.31769348257913865424587.1.96362549..893467.251687152934215493687"
+                .parse()
+                .unwrap();
+        assert_eq!(board.count_solutions(10), Ok(3));
+        let mut steps = board.clone().solve_steps();
+
+        assert!(steps.by_ref().all(|step| step.technique != "bug+1"));
+        assert_eq!(steps.stopped(), Some(UpdateError::Incomplete));
+        assert_eq!(board.rate().unwrap().hardest, "guessing");
+        assert!(board
+            .hint()
+            .unwrap()
+            .is_none_or(|step| step.technique != "bug+1"));
+    }
+}
@@ -0,0 +1,176 @@
+//! the arithmetic killer cages, arrows, and sandwiches share, which candidates can still add up
+//! to a sum
+//!
+//! the ways of filling the cells are searched over which values have been used so far, or over
+//! the total so far when values can repeat, so even nine empty cells only have a few thousand
+//! states to look at
+use crate::{
+    board::{Candidates, Cell, CellPos, Shape},
+    Board, CellVal, UpdateError,
+};
+use std::collections::{HashMap, HashSet};
+
+/// the values a cell can still be, as bits 1 to `SIDE` of a mask
+pub(crate) fn mask<S: Shape>(cell: &Cell<S>) -> u64 {
+    match cell {
+        Cell::Concrete(val) => 1 << val.into_inner(),
+        Cell::Possibilities(set) => set.iter().fold(0, |mask, val| mask | 1 << val.into_inner()),
+    }
+}
+
+/// each open cell with the mask of the candidates it keeps
+type Kept<S> = Vec<(CellPos<S>, u64)>;
+
+/// the candidates each open cell of `cells` keeps when they add up to `sum`, or `None` if no way
+/// of filling them does
+///
+/// with `distinct`, no value can be used twice, and failing if two filled in cells already do
+pub(crate) fn kept<S: Shape>(
+    board: &Board<S>,
+    cells: &[CellPos<S>],
+    sum: usize,
+    distinct: bool,
+) -> Result<Option<Kept<S>>, UpdateError> {
+    let (mut used, mut total) = (0, 0);
+    let mut open = vec![];
+    for &pos in cells {
+        match board.cell(pos) {
+            &Cell::Concrete(val) => {
+                let bit = 1 << val.into_inner();
+                if distinct && used & bit != 0 {
+                    Err(UpdateError::InvalidConcrete)?
+                }
+                if distinct {
+                    used |= bit;
+                }
+                total += val.into_inner();
+            }
+            cell @ Cell::Possibilities(_) => open.push((pos, mask(cell))),
+        }
+    }
+    let mut sums = Sums {
+        masks: open.iter().map(|&(_, mask)| mask).collect(),
+        side: S::SIDE,
+        sum,
+        distinct,
+        memo: HashMap::new(),
+    };
+    if !sums.completes(0, used, total) {
+        return Ok(None);
+    }
+    let mut kept = vec![0; open.len()];
+    sums.keep(0, used, total, &mut kept, &mut HashSet::new());
+    Ok(Some(open.iter().map(|&(pos, _)| pos).zip(kept).collect()))
+}
+
+/// narrows each open cell down to the values of its mask, filling in cells left with one and
+/// failing if one is left with none
+pub(crate) fn narrow<S: Shape>(
+    board: &mut Board<S>,
+    kept: impl IntoIterator<Item = (CellPos<S>, u64)>,
+) -> Result<(), UpdateError> {
+    for (pos, kept) in kept {
+        if let Cell::Concrete(_) = board.cell(pos) {
+            continue;
+        }
+        let vals: Candidates<S> = CellVal::cell_vals()
+            .filter(|val| kept & 1 << val.into_inner() != 0)
+            .collect();
+        *board.mut_cell(pos) = match vals.single() {
+            Some(val) => Cell::Concrete(val),
+            None if vals.is_empty() => Err(UpdateError::Impossible)?,
+            None => Cell::Possibilities(vals),
+        };
+    }
+    Ok(())
+}
+
+/// the ways of filling the open cells, with values as bits 1 to `side` of a mask
+struct Sums {
+    /// the candidates of each open cell
+    masks: Vec<u64>,
+    /// the largest value
+    side: usize,
+    sum: usize,
+    /// whether a value can only be used once, in which case `used` has the values used so far
+    distinct: bool,
+    /// whether the cells from an index on can finish given the values used and the total so far
+    memo: HashMap<(usize, u64, usize), bool>,
+}
+
+impl Sums {
+    /// the values cell `i` can take next, and what `used` and `total` become with each
+    fn next(&self, i: usize, used: u64, total: usize) -> impl Iterator<Item = (u64, u64, usize)> {
+        let (mask, distinct) = (self.masks[i], self.distinct);
+        (1..=self.side)
+            .map(|val| (val, 1 << val))
+            .filter(move |&(_, bit)| mask & bit != 0 && !(distinct && used & bit != 0))
+            .map(move |(val, bit)| {
+                let used = if distinct { used | bit } else { used };
+                (bit, used, total + val)
+            })
+    }
+    /// whether the cells from `i` on can take values that bring `total` up to the sum
+    fn completes(&mut self, i: usize, used: u64, total: usize) -> bool {
+        if i == self.masks.len() {
+            return total == self.sum;
+        }
+        // every open cell adds at least 1
+        if total >= self.sum {
+            return false;
+        }
+        if let Some(&completes) = self.memo.get(&(i, used, total)) {
+            return completes;
+        }
+        let ways: Vec<_> = self.next(i, used, total).collect();
+        let completes = ways
+            .into_iter()
+            .any(|(_, used, total)| self.completes(i + 1, used, total));
+        self.memo.insert((i, used, total), completes);
+        completes
+    }
+    /// adds each value that some way of finishing from `i`, `used`, and `total` gives a cell to
+    /// `kept`
+    fn keep(
+        &mut self,
+        i: usize,
+        used: u64,
+        total: usize,
+        kept: &mut [u64],
+        seen: &mut HashSet<(usize, u64, usize)>,
+    ) {
+        if i == self.masks.len() || !seen.insert((i, used, total)) {
+            return;
+        }
+        let ways: Vec<_> = self.next(i, used, total).collect();
+        for (bit, used, total) in ways {
+            if self.completes(i + 1, used, total) {
+                kept[i] |= bit;
+                self.keep(i + 1, used, total, kept, seen);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn repeats_are_only_kept_when_allowed() {
+        let board = board!([[?, ?]]);
+        let cells = [pos!(0, 0), pos!(0, 1)];
+
+        // 2 is only 1 + 1
+        assert_eq!(kept(&board, &cells, 2, true), Ok(None));
+        assert_eq!(
+            kept(&board, &cells, 2, false),
+            Ok(Some(vec![(pos!(0, 0), 1 << 1), (pos!(0, 1), 1 << 1)]))
+        );
+        assert_eq!(
+            kept(&board!([[1, 1]]), &cells, 2, true),
+            Err(UpdateError::InvalidConcrete)
+        );
+    }
+}
@@ -0,0 +1,552 @@
+//! rules some puzzles add on top of the classic ones
+//!
+//! a variant's regions are checked the same way rows, columns, and houses are, after them in
+//! every pass, and so are killer cages, arrows, sandwiches, greater-than signs, even and odd
+//! marks, and the cells a filled in cell rules out. the strategies only know the classic units,
+//! so what they find is still true but misses what the variants would add
+use super::{arrow::Arrow, cage::Cage, inequality::Inequality, parity::Parity, sandwich::Sandwich};
+use crate::{
+    board::{
+        geometry::{diagonal_cells, window_cells},
+        Cell, CellPos, Shape,
+    },
+    Board, CellVal, Classic, UpdateError,
+};
+
+/// the `(row, column)` steps of a chess knight
+const KNIGHT_MOVES: [(isize, isize); 8] = [
+    (-2, -1),
+    (-2, 1),
+    (-1, -2),
+    (-1, 2),
+    (1, -2),
+    (1, 2),
+    (2, -1),
+    (2, 1),
+];
+/// the `(row, column)` steps to the cells next to a cell, but not diagonally
+const NEIGHBOURS: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// a set of extra rules, see `SolveOptions::variants`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Variant<S: Shape = Classic> {
+    /// hyper sudoku, where the four 3x3 windows one cell in from each corner also need every
+    /// value once, or as many house-sized windows as fit one cell apart on other sizes
+    Windoku,
+    /// x sudoku, where the two long diagonals also need every value once
+    Diagonal,
+    /// killer sudoku, where the values in each cage add up to its sum without repeating
+    Killer(Vec<Cage<S>>),
+    /// cells a chess knight's move apart can't hold the same value
+    AntiKnight,
+    /// cells next to each other in a row or column can't hold consecutive values
+    NonConsecutive,
+    /// the value in each arrow's circle is the sum of the values along it
+    Arrows(Vec<Arrow<S>>),
+    /// the values between the 1 and the largest value of each clue's row or column add up to its
+    /// sum
+    Sandwiches(Vec<Sandwich<S>>),
+    /// the cell on the larger side of each sign holds the larger value
+    GreaterThan(Vec<Inequality<S>>),
+    /// each marked cell holds an even or an odd value, as its mark says
+    EvenOdd(Vec<(CellPos<S>, Parity)>),
+}
+
+impl<S: Shape> Variant<S> {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Variant::Windoku => "windoku",
+            Variant::Diagonal => "diagonal",
+            Variant::Killer(_) => "killer",
+            Variant::AntiKnight => "anti-knight",
+            Variant::NonConsecutive => "non-consecutive",
+            Variant::Arrows(_) => "arrow",
+            Variant::Sandwiches(_) => "sandwich",
+            Variant::GreaterThan(_) => "greater-than",
+            Variant::EvenOdd(_) => "even-odd",
+        }
+    }
+    /// the extra groups of `SIDE` cells that each need every value once, each in row-major order
+    pub fn regions(&self) -> Vec<Vec<CellPos<S>>> {
+        let table = match self {
+            Variant::Windoku => window_cells::<S>(),
+            Variant::Diagonal => diagonal_cells::<S>(),
+            Variant::Killer(_)
+            | Variant::AntiKnight
+            | Variant::NonConsecutive
+            | Variant::Arrows(_)
+            | Variant::Sandwiches(_)
+            | Variant::GreaterThan(_)
+            | Variant::EvenOdd(_) => vec![],
+        };
+        table
+            .iter()
+            .map(|cells| {
+                cells
+                    .iter()
+                    .map(|&(row, column)| {
+                        CellPos::at(row, column).expect("the tables only have cells on the board")
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+    /// the cells a cell at `pos` holding `val` stops from holding a value, with that value,
+    /// beyond its row, column, and house
+    fn ruled_out(&self, pos: CellPos<S>, val: CellVal<S>) -> Vec<(CellPos<S>, CellVal<S>)> {
+        let step = |&(down, across): &(isize, isize)| {
+            CellPos::at(
+                pos.row().checked_add_signed(down)?,
+                pos.column().checked_add_signed(across)?,
+            )
+        };
+        match self {
+            Variant::AntiKnight => KNIGHT_MOVES
+                .iter()
+                .filter_map(step)
+                .map(|other| (other, val))
+                .collect(),
+            Variant::NonConsecutive => {
+                let val = val.into_inner();
+                let consecutive: Vec<_> = [val - 1, val + 1]
+                    .into_iter()
+                    .filter_map(|val| CellVal::new(val).ok())
+                    .collect();
+                NEIGHBOURS
+                    .iter()
+                    .filter_map(step)
+                    .flat_map(|other| consecutive.iter().map(move |&val| (other, val)))
+                    .collect()
+            }
+            Variant::Windoku
+            | Variant::Diagonal
+            | Variant::Killer(_)
+            | Variant::Arrows(_)
+            | Variant::Sandwiches(_)
+            | Variant::GreaterThan(_)
+            | Variant::EvenOdd(_) => vec![],
+        }
+    }
+    /// removes what the value at `pos` rules out, returning whether anything changed
+    fn rule_out_from(&self, board: &mut Board<S>, pos: CellPos<S>) -> Result<bool, UpdateError> {
+        let &Cell::Concrete(val) = board.cell(pos) else {
+            return Ok(false);
+        };
+        let mut changed = false;
+        for (other, ruled_out) in self.ruled_out(pos, val) {
+            match board.cell(other) {
+                &Cell::Concrete(val) if val == ruled_out => Err(UpdateError::InvalidConcrete)?,
+                Cell::Possibilities(set) if set.contains(ruled_out) => {
+                    let set = set.without(ruled_out);
+                    *board.mut_cell(other) = match set.single() {
+                        Some(val) => Cell::Concrete(val),
+                        None if set.is_empty() => Err(UpdateError::Impossible)?,
+                        None => Cell::Possibilities(set),
+                    };
+                    changed = true;
+                }
+                _ => {}
+            }
+        }
+        Ok(changed)
+    }
+    /// removes the candidates the variant rules out and fills in cells left with one, failing if
+    /// a rule is broken
+    pub(crate) fn check_and_update(&self, board: &mut Board<S>) -> Result<(), UpdateError> {
+        self.regions().into_iter().try_for_each(|region| {
+            board
+                .get_cells(region.into_iter().collect())
+                .check_and_update()
+        })?;
+        self.cages()
+            .iter()
+            .try_for_each(|cage| cage.check_and_update(board))?;
+        self.arrows()
+            .iter()
+            .try_for_each(|arrow| arrow.check_and_update(board))?;
+        self.sandwiches()
+            .iter()
+            .try_for_each(|sandwich| sandwich.check_and_update(board))?;
+        self.inequalities()
+            .iter()
+            .try_for_each(|inequality| inequality.check_and_update(board))?;
+        self.parities()
+            .iter()
+            .try_for_each(|&(pos, parity)| parity.check_and_update(board, pos))?;
+        CellPos::all().try_for_each(|pos| self.rule_out_from(board, pos).map(|_| ()))
+    }
+    /// checks one region, cage, arrow, sandwich, sign, mark, or filled in cell at a time until one
+    /// changes the board, returning its cells
+    pub(crate) fn step(
+        &self,
+        board: &mut Board<S>,
+    ) -> Result<Option<Vec<CellPos<S>>>, UpdateError> {
+        for region in self.regions() {
+            let before = board.clone();
+            board
+                .get_cells(region.iter().copied().collect())
+                .check_and_update()?;
+            if *board != before {
+                return Ok(Some(region));
+            }
+        }
+        for cage in self.cages() {
+            let before = board.clone();
+            cage.check_and_update(board)?;
+            if *board != before {
+                return Ok(Some(cage.cells().to_vec()));
+            }
+        }
+        for arrow in self.arrows() {
+            let before = board.clone();
+            arrow.check_and_update(board)?;
+            if *board != before {
+                let mut cells = vec![arrow.circle()];
+                cells.extend(arrow.cells());
+                return Ok(Some(cells));
+            }
+        }
+        for sandwich in self.sandwiches() {
+            let before = board.clone();
+            sandwich.check_and_update(board)?;
+            if *board != before {
+                return Ok(Some(sandwich.cells()));
+            }
+        }
+        for inequality in self.inequalities() {
+            let before = board.clone();
+            inequality.check_and_update(board)?;
+            if *board != before {
+                return Ok(Some(vec![inequality.greater(), inequality.less()]));
+            }
+        }
+        for &(pos, parity) in self.parities() {
+            let before = board.clone();
+            parity.check_and_update(board, pos)?;
+            if *board != before {
+                return Ok(Some(vec![pos]));
+            }
+        }
+        for pos in CellPos::all() {
+            if self.rule_out_from(board, pos)? {
+                return Ok(Some(vec![pos]));
+            }
+        }
+        Ok(None)
+    }
+    fn cages(&self) -> &[Cage<S>] {
+        match self {
+            Variant::Killer(cages) => cages,
+            Variant::Windoku
+            | Variant::Diagonal
+            | Variant::AntiKnight
+            | Variant::NonConsecutive
+            | Variant::Arrows(_)
+            | Variant::Sandwiches(_)
+            | Variant::GreaterThan(_)
+            | Variant::EvenOdd(_) => &[],
+        }
+    }
+    fn arrows(&self) -> &[Arrow<S>] {
+        match self {
+            Variant::Arrows(arrows) => arrows,
+            Variant::Windoku
+            | Variant::Diagonal
+            | Variant::Killer(_)
+            | Variant::AntiKnight
+            | Variant::NonConsecutive
+            | Variant::Sandwiches(_)
+            | Variant::GreaterThan(_)
+            | Variant::EvenOdd(_) => &[],
+        }
+    }
+    fn sandwiches(&self) -> &[Sandwich<S>] {
+        match self {
+            Variant::Sandwiches(sandwiches) => sandwiches,
+            Variant::Windoku
+            | Variant::Diagonal
+            | Variant::Killer(_)
+            | Variant::AntiKnight
+            | Variant::NonConsecutive
+            | Variant::Arrows(_)
+            | Variant::GreaterThan(_)
+            | Variant::EvenOdd(_) => &[],
+        }
+    }
+    fn inequalities(&self) -> &[Inequality<S>] {
+        match self {
+            Variant::GreaterThan(inequalities) => inequalities,
+            Variant::Windoku
+            | Variant::Diagonal
+            | Variant::Killer(_)
+            | Variant::AntiKnight
+            | Variant::NonConsecutive
+            | Variant::Arrows(_)
+            | Variant::Sandwiches(_)
+            | Variant::EvenOdd(_) => &[],
+        }
+    }
+    fn parities(&self) -> &[(CellPos<S>, Parity)] {
+        match self {
+            Variant::EvenOdd(parities) => parities,
+            Variant::Windoku
+            | Variant::Diagonal
+            | Variant::Killer(_)
+            | Variant::AntiKnight
+            | Variant::NonConsecutive
+            | Variant::Arrows(_)
+            | Variant::Sandwiches(_)
+            | Variant::GreaterThan(_) => &[],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        board::{macros::*, Cell},
+        SolveError, SolveOptions, Solver, SolverBackend, Unit,
+    };
+
+    #[test]
+    fn windows_fill_in_their_last_cell() {
+        // nothing classic forces the 9, since row 4, column 4, and the center house have room
+        let mut board = board!([
+            []
+            [?, 1, 2, 3]
+            [?, 4, 5, 6]
+            [?, 7, 8, ?]
+        ]);
+        Variant::Windoku.check_and_update(&mut board).unwrap();
+
+        assert_eq!(board.cell(pos!(3, 3)), &cell!(9));
+        assert_eq!(Variant::Windoku.regions()[3].last(), Some(&pos!(7, 7)));
+    }
+    #[test]
+    fn windoku_solutions_fill_every_window() {
+        let options = SolveOptions {
+            variants: vec![Variant::Windoku],
+            ..Default::default()
+        };
+        let solution = board!([]).solve_with(&options).unwrap().0;
+
+        for window in Variant::Windoku.regions() {
+            let mut vals: Vec<_> = window
+                .into_iter()
+                .map(|pos| match solution.cell(pos) {
+                    Cell::Concrete(val) => val.into_inner(),
+                    Cell::Possibilities(_) => 0,
+                })
+                .collect();
+            vals.sort();
+            assert_eq!(vals, [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        }
+        let dlx = Solver::builder()
+            .backend(SolverBackend::Dlx)
+            .variant(Variant::Windoku)
+            .build();
+        assert_eq!(
+            dlx.solve(board!([])),
+            Err(SolveError::Unsupported {
+                backend: "dlx",
+                variant: "windoku"
+            })
+        );
+    }
+    #[test]
+    fn killer_solutions_add_up_in_every_cage() {
+        let cages = vec![
+            Cage::new(vec![pos!(0, 0)], 2).unwrap(),
+            Cage::new(vec![pos!(0, 0), pos!(0, 1)], 3).unwrap(),
+            Cage::new(vec![pos!(4, 4), pos!(4, 5), pos!(5, 4)], 24).unwrap(),
+        ];
+        let options = SolveOptions {
+            variants: vec![Variant::Killer(cages.clone())],
+            ..Default::default()
+        };
+        let solution = board!([]).solve_with(&options).unwrap().0;
+        let val = |pos| match solution.cell(pos) {
+            Cell::Concrete(val) => val.into_inner(),
+            Cell::Possibilities(_) => 0,
+        };
+
+        assert_eq!((val(pos!(0, 0)), val(pos!(0, 1))), (2, 1));
+        for cage in &cages {
+            assert_eq!(
+                cage.cells().iter().map(|&pos| val(pos)).sum::<usize>(),
+                cage.sum()
+            );
+        }
+    }
+    #[test]
+    fn arrow_solutions_add_up_to_their_circle() {
+        let arrows = vec![
+            Arrow::new(pos!(0, 0), vec![pos!(1, 1), pos!(2, 2)]).unwrap(),
+            Arrow::new(pos!(4, 4), vec![pos!(4, 5), pos!(4, 6), pos!(5, 6)]).unwrap(),
+        ];
+        let options = SolveOptions {
+            variants: vec![Variant::Arrows(arrows.clone())],
+            ..Default::default()
+        };
+        let solution = board!([]).solve_with(&options).unwrap().0;
+        let val = |pos| match solution.cell(pos) {
+            Cell::Concrete(val) => val.into_inner(),
+            Cell::Possibilities(_) => 0,
+        };
+
+        for arrow in &arrows {
+            assert_eq!(
+                arrow.cells().iter().map(|&pos| val(pos)).sum::<usize>(),
+                val(arrow.circle())
+            );
+        }
+    }
+    #[test]
+    fn sandwich_solutions_add_up_between_the_crusts() {
+        let sandwiches = vec![
+            Sandwich::new(Unit::Row(0), 35).unwrap(),
+            Sandwich::new(Unit::Column(4), 0).unwrap(),
+            Sandwich::new(Unit::Row(5), 12).unwrap(),
+        ];
+        let options = SolveOptions {
+            variants: vec![Variant::Sandwiches(sandwiches.clone())],
+            ..Default::default()
+        };
+        let solution = board!([]).solve_with(&options).unwrap().0;
+
+        for sandwich in &sandwiches {
+            let vals: Vec<_> = sandwich
+                .cells()
+                .into_iter()
+                .map(|pos| match solution.cell(pos) {
+                    Cell::Concrete(val) => val.into_inner(),
+                    Cell::Possibilities(_) => 0,
+                })
+                .collect();
+            let one = vals.iter().position(|&val| val == 1).unwrap();
+            let nine = vals.iter().position(|&val| val == 9).unwrap();
+            let between = &vals[one.min(nine) + 1..one.max(nine)];
+            assert_eq!(between.iter().sum::<usize>(), sandwich.sum());
+        }
+    }
+    #[test]
+    fn greater_than_solutions_follow_every_sign() {
+        // a chain across the top row leaves only 9 to 5 for its first five cells
+        let inequalities: Vec<_> = (0..4)
+            .map(|column| Inequality::new(pos!(0, column), pos!(0, column + 1)).unwrap())
+            .chain([Inequality::new(pos!(5, 5), pos!(4, 5)).unwrap()])
+            .collect();
+        let options = SolveOptions {
+            variants: vec![Variant::GreaterThan(inequalities.clone())],
+            ..Default::default()
+        };
+        let solution = board!([]).solve_with(&options).unwrap().0;
+        let val = |pos| match solution.cell(pos) {
+            Cell::Concrete(val) => val.into_inner(),
+            Cell::Possibilities(_) => 0,
+        };
+
+        for inequality in &inequalities {
+            assert!(val(inequality.greater()) > val(inequality.less()));
+        }
+    }
+    #[test]
+    fn even_odd_solutions_keep_to_their_marks() {
+        // the top row alternates, starting with an odd value, so it has its five odd values
+        // at the ends and in every other cell
+        let parities: Vec<_> = (0..9)
+            .map(|column| {
+                let parity = [Parity::Odd, Parity::Even][column % 2];
+                (pos!(0, column), parity)
+            })
+            .collect();
+        let options = SolveOptions {
+            variants: vec![Variant::EvenOdd(parities)],
+            ..Default::default()
+        };
+        let solution = board!([]).solve_with(&options).unwrap().0;
+
+        for column in 0..9 {
+            let Cell::Concrete(val) = solution.cell(pos!(0, column)) else {
+                panic!("the solution is filled in");
+            };
+            assert_eq!(val.into_inner() % 2 == 1, column % 2 == 0);
+        }
+    }
+    #[test]
+    fn anti_knight_rules_out_a_knights_move_away() {
+        let mut board = board!([[] [] [] [] [?, ?, ?, ?, 5]]);
+        Variant::AntiKnight.check_and_update(&mut board).unwrap();
+
+        assert_eq!(board.cell(pos!(2, 3)), &cell!(? 1, 2, 3, 4, 6, 7, 8, 9));
+        assert_eq!(board.cell(pos!(6, 5)), &cell!(? 1, 2, 3, 4, 6, 7, 8, 9));
+        // only the classic rules rule it out of its own row
+        assert_eq!(board.cell(pos!(4, 5)), &cell!(? 1, 2, 3, 4, 5, 6, 7, 8, 9));
+        assert_eq!(
+            Variant::AntiKnight.check_and_update(&mut board!([[1] [?, ?, 1]])),
+            Err(UpdateError::InvalidConcrete)
+        );
+    }
+    #[test]
+    fn anti_knight_solutions_have_no_knights_alike() {
+        let options = SolveOptions {
+            variants: vec![Variant::AntiKnight],
+            ..Default::default()
+        };
+        let solution = board!([]).solve_with(&options).unwrap().0;
+
+        for pos in CellPos::all() {
+            let val = match solution.cell(pos) {
+                &Cell::Concrete(val) => val,
+                Cell::Possibilities(_) => panic!("{pos:?} wasn't filled in"),
+            };
+            assert!(Variant::AntiKnight
+                .ruled_out(pos, val)
+                .into_iter()
+                .all(|(other, val)| solution.cell(other) != &Cell::Concrete(val)));
+        }
+    }
+    #[test]
+    fn non_consecutive_rules_out_the_values_either_side() {
+        let mut board = board!([[] [] [] [] [?, ?, ?, ?, 5]]);
+        Variant::NonConsecutive
+            .check_and_update(&mut board)
+            .unwrap();
+
+        assert_eq!(board.cell(pos!(3, 4)), &cell!(? 1, 2, 3, 5, 7, 8, 9));
+        assert_eq!(board.cell(pos!(4, 5)), &cell!(? 1, 2, 3, 5, 7, 8, 9));
+        // diagonal cells aren't next to it
+        assert_eq!(board.cell(pos!(3, 3)), &cell!(? 1, 2, 3, 4, 5, 6, 7, 8, 9));
+        assert_eq!(
+            Variant::NonConsecutive.check_and_update(&mut board!([[9, 8]])),
+            Err(UpdateError::InvalidConcrete)
+        );
+    }
+    #[test]
+    fn non_consecutive_solutions_have_no_neighbours_one_apart() {
+        let options = SolveOptions {
+            variants: vec![Variant::NonConsecutive],
+            ..Default::default()
+        };
+        // the top band of a solution, since searching from an empty board takes a long while
+        let board = board!([
+            [1, 3, 5, 2, 7, 9, 4, 6, 8]
+            [4, 6, 8, 5, 1, 3, 7, 9, 2]
+            [7, 9, 2, 8, 4, 6, 1, 3, 5]
+        ]);
+        let solution = board.solve_with(&options).unwrap().0;
+        let values: [[Option<usize>; 9]; 9] = solution.into();
+
+        for (row, next) in values.iter().zip(&values[1..]) {
+            for column in 0..9 {
+                assert_ne!(row[column].unwrap().abs_diff(next[column].unwrap()), 1);
+            }
+        }
+        for row in &values {
+            for pair in row.windows(2) {
+                assert_ne!(pair[0].unwrap().abs_diff(pair[1].unwrap()), 1);
+            }
+        }
+    }
+}
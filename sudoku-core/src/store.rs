@@ -0,0 +1,340 @@
+//! a compact in-memory index of solved and graded puzzles, so work over the same corpus isn't
+//! done twice
+//!
+//! puzzles are filed by their `CanonicalForm`, packed into 41 bytes, with the solution of the
+//! form's own puzzle (see `CanonicalForm::board`) as the 104 bit number behind its answer code,
+//! in 13 bytes. a million puzzles take about 54 MB before the hash map's own overhead. grades
+//! are filed the same way, as the score, the number of steps, and which technique was hardest,
+//! in 9 bytes. the technique is kept as its place in `TECHNIQUES`, which only ever grows, so
+//! adding or reordering strategies doesn't change what an old grade names.
+//!
+//! `save` writes the index out as a header line, the number of solutions, and then the records
+//! back to back, solutions first, and `load` reads one back, so a corpus only has to be solved
+//! and graded once. stores saved before they kept grades still load, with no grades
+use crate::{
+    io::{numbered_solution, solution_number},
+    solve::GUESSING,
+    CanonicalForm, ParseError, Rating, SolveError, SolvedBoard, Tier, UpdateError,
+};
+use std::{
+    collections::HashMap,
+    io::{self, Read, Write},
+};
+
+const HEADER: &[u8] = b"solution-store 3\n";
+/// the header of stores that kept a grade's technique as its place in the order the solver tried
+/// them, which was the order of `TECHNIQUES` when that was pinned, so they load the same way
+const BY_SOLVER_ORDER: &[u8] = b"solution-store 2\n";
+/// the header of stores saved before they kept grades, which are only solutions
+const SOLUTIONS_ONLY: &[u8] = b"solution-store 1\n";
+const KEY: usize = 41;
+const SOLUTION: usize = 13;
+const GRADE: usize = 9;
+
+/// solutions and grades by the canonical form of their puzzle
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SolutionStore {
+    solutions: HashMap<[u8; KEY], [u8; SOLUTION]>,
+    grades: HashMap<[u8; KEY], [u8; GRADE]>,
+}
+
+/// every technique a `Rating` can name as its hardest, so a grade can keep the technique as its
+/// index
+///
+/// a saved grade points into this, so new techniques go on the end and none are ever moved or
+/// taken out
+const TECHNIQUES: [&str; 25] = [
+    "singles",
+    "pointing",
+    "claiming",
+    "naked pair",
+    "hidden pair",
+    "naked triple",
+    "hidden triple",
+    "x-wing",
+    "finned x-wing",
+    "skyscraper",
+    "two-string kite",
+    "turbot fish",
+    "simple coloring",
+    "xy-wing",
+    "w-wing",
+    "naked quad",
+    "hidden quad",
+    "swordfish",
+    "finned swordfish",
+    "jellyfish",
+    "xyz-wing",
+    "als-xz",
+    "bug+1",
+    "forcing chain",
+    GUESSING,
+];
+
+impl SolutionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// how many puzzles have a solution filed
+    pub fn solutions_len(&self) -> usize {
+        self.solutions.len()
+    }
+    /// how many puzzles have a grade filed
+    pub fn grades_len(&self) -> usize {
+        self.grades.len()
+    }
+    /// whether nothing is filed, neither a solution nor a grade
+    pub fn is_empty(&self) -> bool {
+        self.solutions.is_empty() && self.grades.is_empty()
+    }
+    /// files `solution` as the solution of `form`'s puzzle, returning false if two cells that see
+    /// each other share a value, which no answer code can stand for
+    pub fn insert(&mut self, form: &CanonicalForm, solution: &SolvedBoard) -> bool {
+        let Some(number) = solution_number(solution) else {
+            return false;
+        };
+        let mut packed = [0; SOLUTION];
+        packed.copy_from_slice(&number.to_le_bytes()[..SOLUTION]);
+        self.solutions.insert(form.packed(), packed);
+        true
+    }
+    /// the solution filed for `form`'s puzzle
+    pub fn get(&self, form: &CanonicalForm) -> Option<SolvedBoard> {
+        let packed = self.solutions.get(&form.packed())?;
+        let mut bytes = [0; 16];
+        bytes[..SOLUTION].copy_from_slice(packed);
+        numbered_solution(u128::from_le_bytes(bytes)).ok()
+    }
+    /// the solution of `form`'s puzzle, solving it and filing the solution if there isn't one yet
+    pub fn solve(&mut self, form: &CanonicalForm) -> Result<SolvedBoard, SolveError> {
+        if let Some(solution) = self.get(form) {
+            return Ok(solution);
+        }
+        let solution = SolvedBoard::try_from(form.board().solve()?)?;
+        self.insert(form, &solution);
+        Ok(solution)
+    }
+    /// files `rating` as the grade of `form`'s puzzle, returning false if its score or steps
+    /// don't fit in 32 bits or its hardest technique isn't one `Board::rate` names
+    pub fn insert_grade(&mut self, form: &CanonicalForm, rating: &Rating) -> bool {
+        let Some(technique) = TECHNIQUES
+            .iter()
+            .position(|&technique| technique == rating.hardest)
+        else {
+            return false;
+        };
+        let (Ok(score), Ok(steps)) = (u32::try_from(rating.score), u32::try_from(rating.steps))
+        else {
+            return false;
+        };
+        let mut packed = [0; GRADE];
+        packed[..4].copy_from_slice(&score.to_le_bytes());
+        packed[4..8].copy_from_slice(&steps.to_le_bytes());
+        packed[8] = technique as u8;
+        self.grades.insert(form.packed(), packed);
+        true
+    }
+    /// the grade filed for `form`'s puzzle
+    pub fn get_grade(&self, form: &CanonicalForm) -> Option<Rating> {
+        let packed = self.grades.get(&form.packed())?;
+        let hardest = *TECHNIQUES.get(packed[8] as usize)?;
+        let number = |bytes: &[u8]| {
+            u32::from_le_bytes(bytes.try_into().expect("4 bytes make a u32")) as usize
+        };
+        Some(Rating {
+            score: number(&packed[..4]),
+            tier: Tier::of(hardest),
+            hardest,
+            steps: number(&packed[4..8]),
+        })
+    }
+    /// the grade of `form`'s puzzle, rating it and filing the grade if there isn't one yet
+    ///
+    /// the puzzle rated is the form's own, so every isomorph of a puzzle gets the same grade
+    /// whether or not it was in the store, even where rating the isomorph itself would have made
+    /// the deductions in another order
+    pub fn grade(&mut self, form: &CanonicalForm) -> Result<Rating, UpdateError> {
+        if let Some(rating) = self.get_grade(form) {
+            return Ok(rating);
+        }
+        let rating = form.board().rate()?;
+        self.insert_grade(form, &rating);
+        Ok(rating)
+    }
+    /// writes every record, in the order of their keys so the same store always saves the same
+    pub fn save(&self, mut output: impl Write) -> io::Result<()> {
+        let mut solutions: Vec<_> = self.solutions.iter().collect();
+        solutions.sort();
+        let mut grades: Vec<_> = self.grades.iter().collect();
+        grades.sort();
+        output.write_all(HEADER)?;
+        output.write_all(&(solutions.len() as u64).to_le_bytes())?;
+        for (key, solution) in solutions {
+            output.write_all(key)?;
+            output.write_all(solution)?;
+        }
+        for (key, grade) in grades {
+            output.write_all(key)?;
+            output.write_all(grade)?;
+        }
+        output.flush()
+    }
+    /// reads a store `save` wrote, failing with `ParseError::SolutionStore` as the invalid data
+    /// if it isn't one
+    ///
+    /// the records aren't checked until they are looked up, so loading stays quick
+    pub fn load(mut input: impl Read) -> io::Result<Self> {
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, ParseError::SolutionStore);
+        let mut bytes = vec![];
+        input.read_to_end(&mut bytes)?;
+        if let Some(records) = bytes.strip_prefix(SOLUTIONS_ONLY) {
+            return Ok(SolutionStore {
+                solutions: records_of(records).ok_or_else(invalid)?,
+                grades: HashMap::new(),
+            });
+        }
+        let records = bytes
+            .strip_prefix(HEADER)
+            .or_else(|| bytes.strip_prefix(BY_SOLVER_ORDER))
+            .ok_or_else(invalid)?;
+        let (count, records) = records.split_first_chunk::<8>().ok_or_else(invalid)?;
+        let split = usize::try_from(u64::from_le_bytes(*count))
+            .ok()
+            .and_then(|count| count.checked_mul(KEY + SOLUTION))
+            .filter(|&split| split <= records.len())
+            .ok_or_else(invalid)?;
+        let (solutions, grades) = records.split_at(split);
+        Ok(SolutionStore {
+            solutions: records_of(solutions).ok_or_else(invalid)?,
+            grades: records_of(grades).ok_or_else(invalid)?,
+        })
+    }
+}
+
+/// records of a key and then `N` bytes, back to back, or `None` if the last one is cut short
+fn records_of<const N: usize>(bytes: &[u8]) -> Option<HashMap<[u8; KEY], [u8; N]>> {
+    if !bytes.len().is_multiple_of(KEY + N) {
+        return None;
+    }
+    let records = bytes
+        .chunks_exact(KEY + N)
+        .map(|record| {
+            let (key, value) = record.split_at(KEY);
+            (
+                key.try_into().expect("the chunk starts with a key"),
+                value
+                    .try_into()
+                    .expect("the rest of the chunk is the value"),
+            )
+        })
+        .collect();
+    Some(records)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{board::macros::*, strategy_names};
+    use std::iter;
+
+    #[test]
+    fn solutions_are_solved_once() {
+        let form = board!([[5, 3, ?, ?, 7] [6, ?, ?, 1, 9, 5]]).canonical_form();
+        let mut store = SolutionStore::new();
+        let solution = store.solve(&form).unwrap();
+
+        assert_eq!(store.solutions_len(), 1);
+        assert_eq!(store.grades_len(), 0);
+        assert_eq!(store.get(&form), Some(solution.clone()));
+        assert_eq!(store.solve(&form), Ok(solution));
+        assert_eq!(store.get(&board!([[1]]).canonical_form()), None);
+    }
+    #[test]
+    fn stores_save_and_load() {
+        let mut store = SolutionStore::new();
+        for board in [board!([[1, 2]]), board!([[1] [?, ?, 2]]), board!([])] {
+            store.solve(&board.canonical_form()).unwrap();
+        }
+        let mut saved = vec![];
+        store.save(&mut saved).unwrap();
+
+        assert_eq!(saved.len(), HEADER.len() + 8 + 3 * (KEY + SOLUTION));
+        assert_eq!(SolutionStore::load(&saved[..]).unwrap(), store);
+        let error = SolutionStore::load(&saved[1..]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(SolutionStore::load(&saved[..saved.len() - 1]).is_err());
+    }
+    #[test]
+    fn grades_are_rated_once_for_every_isomorph() {
+        let board = board!([[5, 3, ?, ?, 7] [6, ?, ?, 1, 9, 5] [?, 9, 8, ?, ?, ?, ?, 6]]);
+        let mut store = SolutionStore::new();
+        let rating = store.grade(&board.canonical_form()).unwrap();
+
+        assert_eq!(rating, board.canonical_form().board().rate().unwrap());
+        assert_eq!(store.get_grade(&board.canonical_form()), Some(rating));
+        // a relabeling of the digits is the same puzzle to the store
+        let relabeled = board!([[6, 3, ?, ?, 7] [5, ?, ?, 1, 9, 6] [?, 9, 8, ?, ?, ?, ?, 5]]);
+        assert_eq!(store.grade(&relabeled.canonical_form()), Ok(rating));
+        assert_eq!((store.solutions_len(), store.grades_len()), (0, 1));
+        assert!(!store.is_empty());
+        assert!(SolutionStore::new().is_empty());
+    }
+    #[test]
+    fn grades_save_and_load_with_the_solutions() {
+        let mut store = SolutionStore::new();
+        store.solve(&board!([[1, 2]]).canonical_form()).unwrap();
+        for board in [board!([[1] [?, ?, 2]]), board!([])] {
+            store.grade(&board.canonical_form()).unwrap();
+        }
+        let mut saved = vec![];
+        store.save(&mut saved).unwrap();
+
+        assert_eq!(
+            saved.len(),
+            HEADER.len() + 8 + (KEY + SOLUTION) + 2 * (KEY + GRADE)
+        );
+        assert_eq!(SolutionStore::load(&saved[..]).unwrap(), store);
+        assert_eq!(
+            SolutionStore::load(&saved[..saved.len() - 1])
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+    #[test]
+    fn every_technique_a_rating_names_has_a_place() {
+        let techniques = iter::once("singles")
+            .chain(strategy_names())
+            .chain(iter::once(GUESSING));
+
+        assert!(techniques
+            .into_iter()
+            .all(|name| TECHNIQUES.contains(&name)));
+        let mut unique = TECHNIQUES.to_vec();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), TECHNIQUES.len());
+    }
+    #[test]
+    fn stores_from_before_the_technique_table_still_load() {
+        let form = board!([[1, 2]]).canonical_form();
+        let mut store = SolutionStore::new();
+        store.grade(&form).unwrap();
+        let mut saved = vec![];
+        store.save(&mut saved).unwrap();
+        saved.splice(..HEADER.len(), BY_SOLVER_ORDER.iter().copied());
+
+        assert_eq!(SolutionStore::load(&saved[..]).unwrap(), store);
+    }
+    #[test]
+    fn stores_from_before_grades_still_load() {
+        let form = board!([[1, 2]]).canonical_form();
+        let mut store = SolutionStore::new();
+        store.solve(&form).unwrap();
+        let mut saved = SOLUTIONS_ONLY.to_vec();
+        saved.extend(form.packed());
+        saved.extend(store.solutions[&form.packed()]);
+
+        assert_eq!(SolutionStore::load(&saved[..]).unwrap(), store);
+    }
+}
@@ -0,0 +1,220 @@
+use super::{combinations, Deduction, Elimination, Strategy};
+use crate::board::{units, Candidates, Cell, CellPos, CellVal, Shape};
+use crate::Board;
+use std::collections::HashSet;
+
+/// two almost locked sets that share a restricted common value `x`
+///
+/// `x` is restricted if every cell of one set that could be `x` sees every cell of the other
+/// that could, so only one of the sets can have it. the other set is then locked, so any other
+/// value `z` they share has to be in one of them, and `z` is removed from every cell that sees
+/// all of their `z` cells
+pub(crate) struct AlsXz;
+
+/// `n` unsolved cells in a unit with `n + 1` candidates between them
+///
+/// if any one of the values is taken out, the rest have to fill the cells
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AlmostLockedSet<S: Shape> {
+    /// in the order of the unit they were found in
+    pub(crate) cells: Vec<CellPos<S>>,
+    /// in order
+    pub(crate) values: Vec<CellVal<S>>,
+    /// `values` as a bit for each, bit `v - 1` for value `v`
+    pub(crate) mask: u64,
+}
+
+impl<S: Shape> AlmostLockedSet<S> {
+    /// the most cells in a set that are looked for, which keeps the number of sets manageable
+    pub(crate) const MAX_SIZE: usize = 4;
+
+    /// every almost locked set of up to `MAX_SIZE` cells
+    ///
+    /// a set that fits in a line and a house is only listed once
+    pub(crate) fn all(board: &Board<S>) -> Vec<Self> {
+        let mut sets: Vec<Self> = vec![];
+        let mut seen = HashSet::new();
+        for unit in units() {
+            // the candidates as bits, which are much quicker to take the union of than the sets.
+            // a cell with more candidates than the biggest set has values can't be in any set
+            let unsolved: Vec<(CellPos<S>, u64)> = unit
+                .iter()
+                .filter_map(|&pos| match board.cell(pos) {
+                    Cell::Possibilities(set) if set.len() <= Self::MAX_SIZE + 1 => {
+                        Some((pos, mask_of(set)))
+                    }
+                    _ => None,
+                })
+                .collect();
+            for size in 1..=Self::MAX_SIZE {
+                for subset in combinations(&unsolved, size) {
+                    let mask = subset.iter().fold(0, |mask, &(_, set)| mask | set);
+                    if mask.count_ones() as usize != size + 1 {
+                        continue;
+                    }
+                    let cells: Vec<_> = subset.iter().map(|&(pos, _)| pos).collect();
+                    if !seen.insert(cells.clone()) {
+                        continue;
+                    }
+                    let values = CellVal::cell_vals()
+                        .filter(|val| mask & bit(*val) != 0)
+                        .collect();
+                    sets.push(AlmostLockedSet {
+                        cells,
+                        values,
+                        mask,
+                    });
+                }
+            }
+        }
+        sets
+    }
+    /// the cells of the set that could be `val`
+    pub(crate) fn holding<'a>(
+        &'a self,
+        board: &'a Board<S>,
+        val: CellVal<S>,
+    ) -> impl Iterator<Item = CellPos<S>> + 'a {
+        self.cells
+            .iter()
+            .copied()
+            .filter(move |&pos| board.cell(pos).is_possible(val))
+    }
+}
+
+impl<S: Shape> Strategy<S> for AlsXz {
+    fn name(&self) -> &'static str {
+        "als-xz"
+    }
+    fn find(&self, board: &Board<S>) -> Option<Deduction<S>> {
+        let sets = AlmostLockedSet::all(board);
+        sets.iter().enumerate().find_map(|(i, a)| {
+            sets[i + 1..]
+                .iter()
+                // without a value `z` besides the restricted one there is nothing to remove
+                .filter(|b| (a.mask & b.mask).count_ones() >= 2)
+                .filter(|b| !b.cells.iter().any(|pos| a.cells.contains(pos)))
+                .find_map(|b| {
+                    let common: Vec<_> = a
+                        .values
+                        .iter()
+                        .copied()
+                        .filter(|val| b.values.contains(val))
+                        .collect();
+                    let restricted = |x| {
+                        a.holding(board, x).all(|first| {
+                            b.holding(board, x).all(|second| board.sees(first, second))
+                        })
+                    };
+                    common
+                        .iter()
+                        .copied()
+                        .filter(|&x| restricted(x))
+                        .find_map(|x| {
+                            let eliminations = common
+                                .iter()
+                                .filter(|&&z| z != x)
+                                .flat_map(|&z| z_eliminations(board, [a, b], z))
+                                .collect();
+                            let cells = a.cells.iter().chain(&b.cells).copied().collect();
+                            Deduction::new(Strategy::<S>::name(self), cells, eliminations)
+                        })
+                })
+        })
+    }
+}
+
+/// the bit for `val` in `AlmostLockedSet::mask`
+fn bit<S: Shape>(val: CellVal<S>) -> u64 {
+    1 << (val.into_inner() - 1)
+}
+/// a bit for each value in `set`
+fn mask_of<S: Shape>(set: &Candidates<S>) -> u64 {
+    set.iter().fold(0, |mask, val| mask | bit(val))
+}
+
+/// `z` for every cell that sees all of the cells in either set that could be `z`
+///
+/// a cell never sees itself, so the sets' own cells are never included
+fn z_eliminations<S: Shape>(
+    board: &Board<S>,
+    sets: [&AlmostLockedSet<S>; 2],
+    z: CellVal<S>,
+) -> Vec<Elimination<S>> {
+    let holding: Vec<_> = sets
+        .into_iter()
+        .flat_map(|set| set.holding(board, z))
+        .collect();
+    board
+        .peers(holding[0])
+        .filter(|&pos| board.cell(pos).is_possible(z))
+        .filter(|&pos| holding.iter().all(|&other| board.sees(pos, other)))
+        .map(|pos| Elimination { pos, val: z })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn almost_locked_sets_have_one_extra_value() {
+        let board = board!([[{ 1, 2 }, { 2, 3 }, { 1, 2, 3, 4 }, 5, 6, 7, 8, 9, 1]]);
+        let sets = AlmostLockedSet::all(&board);
+
+        assert!(sets.contains(&AlmostLockedSet {
+            cells: vec![pos!(0, 0), pos!(0, 1)],
+            values: vec![cell_val!(1), cell_val!(2), cell_val!(3)],
+            mask: 0b111,
+        }));
+        assert!(!sets
+            .iter()
+            .any(|set| set.cells == vec![pos!(0, 0), pos!(0, 2)]));
+    }
+    #[test]
+    fn almost_locked_sets_are_not_repeated() {
+        // (0, 0) and (0, 1) share a row and a house
+        let board = board!([[{ 1, 2 }, { 2, 3 }]]);
+        let count = AlmostLockedSet::all(&board)
+            .iter()
+            .filter(|set| set.cells == vec![pos!(0, 0), pos!(0, 1)])
+            .count();
+
+        assert_eq!(count, 1);
+    }
+    #[test]
+    fn als_xz_removes_z_from_cells_seeing_every_z() {
+        // {1, 2} at (0, 0) and {1, 3}, {2, 3} down column 4, with 1 restricted between them
+        let board = board!([
+            [{ 1, 2 }, ?, ?, ?, { 1, 3 }, ?, ?, ?, ?]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [{ 2, 5 }, ?, ?, ?, { 2, 3 }, ?, ?, ?, ?]
+        ]);
+        let deduction = AlsXz.find(&board).unwrap();
+
+        assert_eq!(deduction.cells, vec![pos!(0, 0), pos!(0, 4), pos!(4, 4)]);
+        assert_eq!(
+            deduction.eliminations,
+            vec![Elimination {
+                pos: pos!(4, 0),
+                val: cell_val!(2)
+            }]
+        );
+    }
+    #[test]
+    fn als_xz_needs_a_restricted_common() {
+        // the 1s of the two sets no longer see each other
+        let board = board!([
+            [{ 1, 2 }, ?, ?, ?, ?, ?, ?, ?, ?]
+            [?, ?, ?, ?, { 1, 3 }, ?, ?, ?, ?]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [{ 2, 5 }, ?, ?, ?, { 2, 3 }, ?, ?, ?, ?]
+        ]);
+
+        assert_eq!(AlsXz.find(&board), None);
+    }
+}
@@ -0,0 +1,101 @@
+use super::{Deduction, Elimination, Strategy};
+use crate::board::{Cell, CellPos, CellVal, Column, House, Row, Shape, ToSet};
+use crate::Board;
+
+/// every unsolved cell has two candidates except one with three (bivalue universal grave + 1)
+///
+/// with the extra candidate gone the puzzle would have two solutions, so the three candidate cell
+/// has to be the value that shows up three times in its units. that only holds if the puzzle has
+/// one solution to begin with, so it is left out until that is known
+pub(crate) struct BugPlusOne;
+
+impl<S: Shape> Strategy<S> for BugPlusOne {
+    fn name(&self) -> &'static str {
+        "bug+1"
+    }
+    fn needs_unique_solution(&self) -> bool {
+        true
+    }
+    fn find(&self, board: &Board<S>) -> Option<Deduction<S>> {
+        let mut extra = None;
+        for pos in CellPos::all() {
+            match board.cell(pos) {
+                Cell::Concrete(_) => {}
+                Cell::Possibilities(set) if set.len() == 2 => {}
+                Cell::Possibilities(set) if set.len() == 3 && extra.is_none() => {
+                    extra = Some((pos, set))
+                }
+                Cell::Possibilities(_) => return None,
+            }
+        }
+        let (pos, set) = extra?;
+        let val = set.iter().find(|&val| {
+            count_in::<Row, _>(board, pos, val) == 3
+                || count_in::<Column, _>(board, pos, val) == 3
+                || count_in::<House, _>(board, pos, val) == 3
+        })?;
+        let eliminations = set
+            .iter()
+            .filter(|&other| other != val)
+            .map(|val| Elimination { pos, val })
+            .collect();
+        Deduction::new(Strategy::<S>::name(self), vec![pos], eliminations)
+    }
+}
+
+/// how many cells in the `U` containing `pos` could be `val`
+fn count_in<U: ToSet, S: Shape>(board: &Board<S>, pos: CellPos<S>, val: CellVal<S>) -> usize {
+    U::cells(U::containing(pos))
+        .filter(|&other| board.cell(other).is_possible(val))
+        .count()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn bug_plus_one_keeps_the_extra_candidate() {
+        let board = board!([
+            [{ 1, 2 }, { 1, 2, 3 }, { 2, 3 }, 4, 5, 6, 7, 8, 9]
+            [4, 5, 6, 7, 8, 9, 1, 2, 3]
+            [7, 8, 9, 1, 2, 3, 4, 5, 6]
+            [2, 3, 4, 5, 6, 7, 8, 9, 1]
+            [5, 6, 7, 8, 9, 1, 2, 3, 4]
+            [8, 9, 1, 2, 3, 4, 5, 6, 7]
+            [3, 4, 5, 6, 7, 8, 9, 1, 2]
+            [6, 7, 8, 9, 1, 2, 3, 4, 5]
+            [9, 1, 2, 3, 4, 5, 6, 7, 8]
+        ]);
+        let deduction = BugPlusOne.find(&board).unwrap();
+
+        assert_eq!(deduction.cells, vec![pos!(0, 1)]);
+        assert_eq!(
+            deduction.eliminations,
+            [1, 3]
+                .into_iter()
+                .map(|val| Elimination {
+                    pos: pos!(0, 1),
+                    val: cell_val!(val)
+                })
+                .collect::<Vec<_>>()
+        );
+    }
+    #[test]
+    fn bug_plus_one_needs_a_single_extra_cell() {
+        let board = board!([
+            [{ 1, 2, 3 }, { 1, 2, 3 }, { 2, 3 }, 4, 5, 6, 7, 8, 9]
+            [4, 5, 6, 7, 8, 9, 1, 2, 3]
+            [7, 8, 9, 1, 2, 3, 4, 5, 6]
+            [2, 3, 4, 5, 6, 7, 8, 9, 1]
+            [5, 6, 7, 8, 9, 1, 2, 3, 4]
+            [8, 9, 1, 2, 3, 4, 5, 6, 7]
+            [3, 4, 5, 6, 7, 8, 9, 1, 2]
+            [6, 7, 8, 9, 1, 2, 3, 4, 5]
+            [9, 1, 2, 3, 4, 5, 6, 7, 8]
+        ]);
+
+        assert_eq!(BugPlusOne.find(&board), None);
+    }
+}
@@ -0,0 +1,127 @@
+use super::{Deduction, Elimination, Strategy, StrongLinks};
+use crate::board::{CellPos, CellVal, Shape};
+use crate::Board;
+
+/// two colors a chain of strong links on one value, since one color or the other has to be it
+///
+/// if two cells of one color see each other (a color wrap), that color is wrong and the value is
+/// removed from all of its cells. otherwise the value is removed from any other cell that sees
+/// both colors (a color trap)
+pub(crate) struct SimpleColoring;
+
+impl<S: Shape> Strategy<S> for SimpleColoring {
+    fn name(&self) -> &'static str {
+        "simple coloring"
+    }
+    fn find(&self, board: &Board<S>) -> Option<Deduction<S>> {
+        let links = StrongLinks::new(board);
+        CellVal::cell_vals().find_map(|val| {
+            links.colorings(val).into_iter().find_map(|chain| {
+                let cells: Vec<CellPos<S>> = chain.iter().map(|&(pos, _)| pos).collect();
+                let eliminations = color_wrap(board, &chain)
+                    .unwrap_or_else(|| color_trap(board, &chain, val))
+                    .into_iter()
+                    .map(|pos| Elimination { pos, val })
+                    .collect();
+                Deduction::new(Strategy::<S>::name(self), cells, eliminations)
+            })
+        })
+    }
+}
+
+/// the cells of a color that has two cells seeing each other
+fn color_wrap<S: Shape>(board: &Board<S>, chain: &[(CellPos<S>, bool)]) -> Option<Vec<CellPos<S>>> {
+    [true, false].into_iter().find_map(|color| {
+        let cells: Vec<CellPos<S>> = chain
+            .iter()
+            .filter(|&&(_, other)| other == color)
+            .map(|&(pos, _)| pos)
+            .collect();
+        let clash = cells
+            .iter()
+            .any(|&a| cells.iter().any(|&b| board.sees(a, b)));
+        clash.then_some(cells)
+    })
+}
+
+/// the cells outside the chain that could be `val` and see both colors
+fn color_trap<S: Shape>(
+    board: &Board<S>,
+    chain: &[(CellPos<S>, bool)],
+    val: CellVal<S>,
+) -> Vec<CellPos<S>> {
+    let sees_color = |pos: CellPos<S>, color: bool| {
+        chain
+            .iter()
+            .any(|&(other, other_color)| other_color == color && board.sees(pos, other))
+    };
+    CellPos::all()
+        .filter(|&pos| board.cell(pos).is_possible(val))
+        .filter(|&pos| !chain.iter().any(|&(other, _)| other == pos))
+        .filter(|&pos| sees_color(pos, true) && sees_color(pos, false))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    fn eliminate_one(cells: Vec<CellPos>) -> Vec<Elimination> {
+        cells
+            .into_iter()
+            .map(|pos| Elimination {
+                pos,
+                val: cell_val!(1),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn color_trap_removes_value_from_cells_seeing_both_colors() {
+        // the chain (0, 0) - (0, 6) - (6, 6) - (6, 2) through row 0, column 6, and row 6
+        let board = board!([
+            [{ 1, 2 }, 2, 3, 4, 5, 6, { 1, 7 }, 7, 8]
+            [?, ?, ?, ?, ?, ?, 2, ?, ?]
+            [?, ?, ?, ?, ?, ?, 3, ?, ?]
+            [?, ?, ?, ?, ?, ?, 4, ?, ?]
+            [?, ?, ?, ?, ?, ?, 5, ?, ?]
+            [?, ?, ?, ?, ?, ?, 6, ?, ?]
+            [2, 3, { 1, 4 }, 4, 5, 6, { 1, 7 }, 7, 8]
+            [?, ?, ?, ?, ?, ?, 8, ?, ?]
+            [?, ?, ?, ?, ?, ?, 9, ?, ?]
+        ]);
+        let deduction = SimpleColoring.find(&board).unwrap();
+
+        assert_eq!(
+            deduction.cells,
+            vec![pos!(0, 0), pos!(0, 6), pos!(6, 2), pos!(6, 6)]
+        );
+        assert_eq!(
+            deduction.eliminations,
+            eliminate_one(vec![pos!(1, 2), pos!(2, 2), pos!(7, 0), pos!(8, 0)])
+        );
+    }
+    #[test]
+    fn color_wrap_removes_value_from_the_clashing_color() {
+        // the chain (0, 0) - (0, 6) - (3, 6) - (3, 1) - (1, 1), where (0, 0) and (1, 1) share a
+        // house and a color
+        let board = board!([
+            [{ 1, 2 }, 2, 3, 4, 5, 6, { 1, 7 }, 7, 8]
+            [?, { 1, 5 }, ?, ?, ?, ?, 2, ?, ?]
+            [?, 4, ?, ?, ?, ?, 3, ?, ?]
+            [2, { 1, 3 }, 3, 4, 5, 6, { 1, 7 }, 7, 8]
+            [?, 5, ?, ?, ?, ?, 4, ?, ?]
+            [?, 6, ?, ?, ?, ?, 5, ?, ?]
+            [?, 7, ?, ?, ?, ?, 6, ?, ?]
+            [?, 8, ?, ?, ?, ?, 8, ?, ?]
+            [?, 9, ?, ?, ?, ?, 9, ?, ?]
+        ]);
+        let deduction = SimpleColoring.find(&board).unwrap();
+
+        assert_eq!(
+            deduction.eliminations,
+            eliminate_one(vec![pos!(0, 0), pos!(1, 1), pos!(3, 6)])
+        );
+    }
+}
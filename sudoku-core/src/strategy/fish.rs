@@ -0,0 +1,371 @@
+use super::{combinations, Deduction, Elimination, Strategy};
+use crate::board::{CellVal, Column, House, Index, Row, Shape, ToSet};
+use crate::Board;
+
+/// a value confined to the same two columns in two rows (or the same two rows in two columns)
+///
+/// the value has to take up those columns in those rows, so it is removed from the rest of them
+pub(crate) struct XWing;
+/// the same as an x-wing, with three rows and three columns
+pub(crate) struct Swordfish;
+/// the same as an x-wing, with four rows and four columns
+pub(crate) struct Jellyfish;
+/// an x-wing with extra cells (fins) in its base lines, all in one house
+///
+/// either the x-wing holds or one of the fins is the value, so the value is only removed from the
+/// cover cells that see every fin. this includes sashimi x-wings, where a base line is missing
+/// one of its cover cells
+pub(crate) struct FinnedXWing;
+/// a swordfish with fins, the same way as a finned x-wing
+pub(crate) struct FinnedSwordfish;
+
+impl<S: Shape> Strategy<S> for XWing {
+    fn name(&self) -> &'static str {
+        "x-wing"
+    }
+    fn find(&self, board: &Board<S>) -> Option<Deduction<S>> {
+        find_fish(board, 2, false, Strategy::<S>::name(self))
+    }
+}
+impl<S: Shape> Strategy<S> for Swordfish {
+    fn name(&self) -> &'static str {
+        "swordfish"
+    }
+    fn find(&self, board: &Board<S>) -> Option<Deduction<S>> {
+        find_fish(board, 3, false, Strategy::<S>::name(self))
+    }
+}
+impl<S: Shape> Strategy<S> for Jellyfish {
+    fn name(&self) -> &'static str {
+        "jellyfish"
+    }
+    fn find(&self, board: &Board<S>) -> Option<Deduction<S>> {
+        find_fish(board, 4, false, Strategy::<S>::name(self))
+    }
+}
+
+impl<S: Shape> Strategy<S> for FinnedXWing {
+    fn name(&self) -> &'static str {
+        "finned x-wing"
+    }
+    fn find(&self, board: &Board<S>) -> Option<Deduction<S>> {
+        find_fish(board, 2, true, Strategy::<S>::name(self))
+    }
+}
+impl<S: Shape> Strategy<S> for FinnedSwordfish {
+    fn name(&self) -> &'static str {
+        "finned swordfish"
+    }
+    fn find(&self, board: &Board<S>) -> Option<Deduction<S>> {
+        find_fish(board, 3, true, Strategy::<S>::name(self))
+    }
+}
+
+/// for each line, a bit for each position along it that could be `val`
+///
+/// for rows bit `j` is column `j`, and for columns it is row `j`
+pub(crate) fn line_masks<L: ToSet, S: Shape>(board: &Board<S>, val: CellVal<S>) -> Vec<u64> {
+    let mut masks = vec![0; S::SIDE];
+    for (i, mask) in Index::indexes().zip(&mut masks) {
+        for (j, pos) in L::cells(i).enumerate() {
+            if board.cell(pos).is_possible(val) {
+                *mask |= 1 << j;
+            }
+        }
+    }
+    masks
+}
+
+fn find_fish<S: Shape>(
+    board: &Board<S>,
+    size: usize,
+    finned: bool,
+    strategy: &'static str,
+) -> Option<Deduction<S>> {
+    CellVal::cell_vals().find_map(|val| {
+        find_fish_in::<Row, _>(board, val, size, finned, strategy)
+            .or_else(|| find_fish_in::<Column, _>(board, val, size, finned, strategy))
+    })
+}
+
+/// finds `size` base lines of kind `L` where `val` only fits in `size` cover lines between them
+///
+/// with `finned` the base lines also have to have fins, which are cells outside of the cover
+/// lines that are all in one house. a base line can then have as few as one cover cell
+fn find_fish_in<L: ToSet, S: Shape>(
+    board: &Board<S>,
+    val: CellVal<S>,
+    size: usize,
+    finned: bool,
+    strategy: &'static str,
+) -> Option<Deduction<S>> {
+    let masks = line_masks::<L, _>(board, val);
+    // fins in one house can only add up to as many cells as the house is across to a line
+    let across = S::BOX_ROWS.max(S::BOX_COLUMNS);
+    let counts = if finned { 1..=size + across } else { 2..=size };
+    let bases: Vec<(Index<S>, u64)> = Index::indexes()
+        .zip(masks)
+        .filter(|(_, mask)| counts.contains(&(mask.count_ones() as usize)))
+        .collect();
+    combinations(&bases, size).into_iter().find_map(|base| {
+        let union = base.iter().fold(0, |union, (_, mask)| union | mask);
+        let covers: Vec<u64> = if finned {
+            finned_covers::<L, S>(&base, union, size)
+        } else if union.count_ones() as usize == size {
+            vec![union]
+        } else {
+            vec![]
+        };
+        covers.into_iter().find_map(|cover| {
+            if base.iter().any(|(_, mask)| mask & cover == 0) {
+                return None;
+            }
+            let in_cover = |j: usize| cover & (1 << j) != 0;
+            let base_cells = || {
+                base.iter()
+                    .flat_map(|&(i, _)| L::cells(i).enumerate())
+                    .filter(|&(_, pos)| board.cell(pos).is_possible(val))
+            };
+            let fins: Vec<_> = base_cells()
+                .filter(|&(j, _)| !in_cover(j))
+                .map(|(_, pos)| pos)
+                .collect();
+            if fins.is_empty() == finned
+                || fins
+                    .iter()
+                    .any(|&fin| House::containing(fin) != House::containing(fins[0]))
+            {
+                return None;
+            }
+            let cells = base_cells().map(|(_, pos)| pos).collect();
+            let eliminations = Index::indexes()
+                .filter(|i| !base.iter().any(|(b, _)| b == i))
+                .flat_map(|i| L::cells(i).enumerate())
+                .filter(|&(j, pos)| in_cover(j) && board.cell(pos).is_possible(val))
+                .filter(|&(_, pos)| fins.iter().all(|&fin| board.sees(pos, fin)))
+                .map(|(_, pos)| Elimination { pos, val })
+                .collect();
+            Deduction::new(strategy, cells, eliminations)
+        })
+    })
+}
+
+/// the sets of `size` cover lines out of `union` that leave the base cells they miss, the fins,
+/// in one house, in the order `combinations` would give them
+///
+/// every base cell outside the fins' house has to be covered, so rather than trying every set of
+/// lines, each house a base cell is in picks the lines those cells need and only chooses among
+/// the few lines through the house for the rest
+fn finned_covers<L: ToSet, S: Shape>(
+    base: &[(Index<S>, u64)],
+    union: u64,
+    size: usize,
+) -> Vec<u64> {
+    let cells: Vec<_> = base
+        .iter()
+        .flat_map(|&(i, mask)| {
+            L::cells(i)
+                .enumerate()
+                .filter(move |&(j, _)| mask & (1 << j) != 0)
+        })
+        .collect();
+    let mut covers: Vec<Vec<usize>> = vec![];
+    for &(_, fin) in &cells {
+        let house = House::containing(fin);
+        let needed = cells
+            .iter()
+            .filter(|&&(_, pos)| House::containing(pos) != house)
+            .fold(0, |needed, &(j, _)| needed | (1 << j));
+        let Some(left) = size.checked_sub(u64::count_ones(needed) as usize) else {
+            continue;
+        };
+        let lines = |mask: u64| (0..S::SIDE).filter(move |j| mask & (1 << j) != 0);
+        for extra in combinations(&lines(union & !needed).collect::<Vec<_>>(), left) {
+            let mut cover: Vec<_> = lines(needed).chain(extra).collect();
+            cover.sort();
+            covers.push(cover);
+        }
+    }
+    covers.sort();
+    covers.dedup();
+    covers
+        .into_iter()
+        .map(|lines| lines.iter().fold(0, |cover, j| cover | (1 << j)))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn line_masks_marks_possible_positions() {
+        let board = board!([[{ 1, 2 }, 3, { 1, 4 }, 2, 4, 5, 6, 7, 8]]);
+
+        assert_eq!(line_masks::<Row, _>(&board, cell_val!(1))[0], 0b101);
+        assert_eq!(
+            line_masks::<Column, _>(&board, cell_val!(1))[2],
+            0b1_1111_1111
+        );
+    }
+    #[test]
+    fn x_wing_removes_value_from_cover_columns() {
+        // 1 only fits in columns 1 and 4 of rows 0 and 3
+        let board = board!([
+            [2, { 1, 3 }, 4, 5, { 1, 6 }, 7, 8, 9, 3]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [2, { 1, 3 }, 4, 5, { 1, 6 }, 7, 8, 9, 3]
+        ]);
+        let deduction = XWing.find(&board).unwrap();
+
+        assert_eq!(
+            deduction.cells,
+            vec![pos!(0, 1), pos!(0, 4), pos!(3, 1), pos!(3, 4)]
+        );
+        assert_eq!(
+            deduction.eliminations,
+            [1, 2, 4, 5, 6, 7, 8]
+                .into_iter()
+                .flat_map(|row| [pos!(row, 1), pos!(row, 4)])
+                .map(|pos| Elimination {
+                    pos,
+                    val: cell_val!(1)
+                })
+                .collect::<Vec<_>>()
+        );
+    }
+    #[test]
+    fn swordfish_allows_lines_with_two_cells() {
+        // 1 only fits in columns 0, 4, and 8 of rows 0, 4, and 8
+        let board = board!([
+            [{ 1, 2 }, 3, 4, 5, { 1, 6 }, 7, 8, 9, 2]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [3, 2, 4, 5, { 1, 6 }, 7, 8, 9, { 1, 3 }]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [{ 1, 2 }, 3, 4, 5, 6, 7, 8, 9, { 1, 2 }]
+        ]);
+        let deduction = Swordfish.find(&board).unwrap();
+
+        assert_eq!(
+            deduction.cells,
+            vec![
+                pos!(0, 0),
+                pos!(0, 4),
+                pos!(4, 4),
+                pos!(4, 8),
+                pos!(8, 0),
+                pos!(8, 8)
+            ]
+        );
+        assert_eq!(deduction.eliminations.len(), 18);
+    }
+    #[test]
+    fn swordfish_needs_three_cover_lines() {
+        let board = board!([
+            [{ 1, 2 }, 3, 4, 5, { 1, 6 }, 7, 8, 9, 2]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [3, 2, 4, 5, { 1, 6 }, 7, 8, 9, { 1, 3 }]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [{ 1, 2 }, 3, { 1, 4 }, 5, 6, 7, 8, 9, { 1, 2 }]
+        ]);
+
+        assert_eq!(Swordfish.find(&board), None);
+    }
+    #[test]
+    fn finned_x_wing_removes_value_from_cover_cells_seeing_the_fin() {
+        // 1 only fits in columns 1 and 4 of rows 0 and 3, besides the fin at (3, 5)
+        let board = board!([
+            [2, { 1, 3 }, 4, 5, { 1, 6 }, 7, 8, 9, 3]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [2, { 1, 3 }, 4, 5, { 1, 6 }, { 1, 7 }, 8, 9, 3]
+        ]);
+        let deduction = FinnedXWing.find(&board).unwrap();
+
+        assert_eq!(
+            deduction.cells,
+            vec![pos!(0, 1), pos!(0, 4), pos!(3, 1), pos!(3, 4), pos!(3, 5)]
+        );
+        assert_eq!(
+            deduction.eliminations,
+            [pos!(4, 4), pos!(5, 4)]
+                .into_iter()
+                .map(|pos| Elimination {
+                    pos,
+                    val: cell_val!(1)
+                })
+                .collect::<Vec<_>>()
+        );
+    }
+    #[test]
+    fn finned_x_wing_allows_sashimi() {
+        // row 3 is missing (3, 4), so only the fin at (3, 5) stands in for it
+        let board = board!([
+            [2, { 1, 3 }, 4, 5, { 1, 6 }, 7, 8, 9, 3]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [2, { 1, 3 }, 4, 5, 6, { 1, 7 }, 8, 9, 3]
+        ]);
+
+        assert_eq!(
+            FinnedXWing.find(&board).unwrap().eliminations,
+            [pos!(4, 4), pos!(5, 4)]
+                .into_iter()
+                .map(|pos| Elimination {
+                    pos,
+                    val: cell_val!(1)
+                })
+                .collect::<Vec<_>>()
+        );
+    }
+    #[test]
+    fn finned_x_wing_needs_the_fins_in_one_house() {
+        let board = board!([
+            [2, { 1, 3 }, 4, 5, { 1, 6 }, 7, 8, 9, 3]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [2, { 1, 3 }, 4, 5, { 1, 6 }, { 1, 7 }, 8, 9, { 1, 3 }]
+        ]);
+
+        assert_eq!(FinnedXWing.find(&board), None);
+    }
+    #[test]
+    fn jellyfish_removes_value_from_cover_columns() {
+        // 1 only fits in columns 0, 3, 5, and 8 of rows 0, 2, 6, and 8
+        let board = board!([
+            [{ 1, 2 }, 3, 4, { 1, 5 }, 6, 7, 8, 9, 2]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [3, 2, 4, { 1, 5 }, 6, { 1, 7 }, 8, 9, 5]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [3, 2, 4, 5, 6, { 1, 7 }, 8, 9, { 1, 3 }]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [{ 1, 2 }, 3, 4, 5, 6, 7, 8, 9, { 1, 2 }]
+        ]);
+        let deduction = Jellyfish.find(&board).unwrap();
+
+        assert_eq!(deduction.cells.len(), 8);
+        assert_eq!(
+            deduction.eliminations,
+            [1, 3, 4, 5, 7]
+                .into_iter()
+                .flat_map(|row| [pos!(row, 0), pos!(row, 3), pos!(row, 5), pos!(row, 8)])
+                .map(|pos| Elimination {
+                    pos,
+                    val: cell_val!(1)
+                })
+                .collect::<Vec<_>>()
+        );
+    }
+}
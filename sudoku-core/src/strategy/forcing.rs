@@ -0,0 +1,164 @@
+use super::{wings::bivalue_cells, Deduction, Elimination, Strategy};
+use crate::board::{Cell, CellPos, CellVal, Column, House, Row, Shape, ToSet};
+use crate::Board;
+
+/// assumes a candidate is the cell's value and follows the singles that forces, removing the
+/// candidate if that ends in a contradiction
+///
+/// only the candidates of cells with two left are tried. those are the chains a person would
+/// start, and it keeps this cheap on open boards where nothing would be found anyway
+///
+/// `max_length` is how many values may be placed along the way before giving up on a candidate,
+/// which keeps this from turning into a full search. see `SolveOptions::max_chain_length`
+pub(crate) struct ForcingChains {
+    pub(crate) max_length: usize,
+}
+
+impl ForcingChains {
+    /// long enough to finish most chains a person would follow
+    pub(crate) const DEFAULT_LENGTH: usize = 8;
+}
+
+impl<S: Shape> Strategy<S> for ForcingChains {
+    fn name(&self) -> &'static str {
+        "forcing chain"
+    }
+    fn find(&self, board: &Board<S>) -> Option<Deduction<S>> {
+        bivalue_cells(board)
+            .into_iter()
+            .flat_map(|(pos, vals)| vals.map(|val| (pos, val)))
+            .find(|&(pos, val)| contradicts(board, pos, val, self.max_length))
+            .and_then(|(pos, val)| {
+                Deduction::new(
+                    Strategy::<S>::name(self),
+                    vec![pos],
+                    vec![Elimination { pos, val }],
+                )
+            })
+    }
+}
+
+/// true if placing `val` at `pos` forces a contradiction within `max_length` placements
+///
+/// each placement removes the value from its peers, and any cell left with one candidate or any
+/// value left with one place in a unit is placed next
+fn contradicts<S: Shape>(
+    board: &Board<S>,
+    pos: CellPos<S>,
+    val: CellVal<S>,
+    max_length: usize,
+) -> bool {
+    let mut board = board.clone();
+    let mut forced = vec![(pos, val)];
+    let mut placed = 0;
+    while let Some((pos, val)) = forced.pop() {
+        match board.cell(pos) {
+            Cell::Concrete(other) if *other == val => continue,
+            Cell::Concrete(_) => return true,
+            Cell::Possibilities(set) if !set.contains(val) => return true,
+            Cell::Possibilities(_) => {}
+        }
+        if placed == max_length {
+            return false;
+        }
+        placed += 1;
+        *board.mut_cell(pos) = Cell::Concrete(val);
+        let mut touched = vec![];
+        for peer in board.peers(pos).collect::<Vec<_>>() {
+            if !board.cell(peer).is_possible(val) {
+                continue;
+            }
+            let cell = board.cell(peer).remove_possibility(val);
+            match &cell {
+                Cell::Possibilities(set) if set.is_empty() => return true,
+                Cell::Possibilities(set) if set.len() == 1 => {
+                    forced.extend(set.iter().map(|single| (peer, single)))
+                }
+                _ => {}
+            }
+            *board.mut_cell(peer) = cell;
+            touched.push(peer);
+        }
+        // the cell's own units lost every other value it could have been, and the units of the
+        // peers lost `val`, so those are the only places a value can have run out of room
+        let lost = units_of(pos)
+            .into_iter()
+            .flat_map(|unit| CellVal::cell_vals().map(move |val| (unit.clone(), val)))
+            .chain(
+                touched
+                    .into_iter()
+                    .flat_map(|peer| units_of(peer).map(|unit| (unit, val))),
+            );
+        for (unit, val) in lost {
+            if unit
+                .iter()
+                .any(|&pos| board.cell(pos) == &Cell::Concrete(val))
+            {
+                continue;
+            }
+            match unit
+                .iter()
+                .filter(|&&pos| board.cell(pos).is_possible(val))
+                .collect::<Vec<_>>()[..]
+            {
+                [] => return true,
+                [&single] => forced.push((single, val)),
+                _ => {}
+            }
+        }
+    }
+    false
+}
+
+/// the row, column, and house containing `pos`
+fn units_of<S: Shape>(pos: CellPos<S>) -> [Vec<CellPos<S>>; 3] {
+    [
+        Row::cells(Row::containing(pos)).collect(),
+        Column::cells(Column::containing(pos)).collect(),
+        House::cells(House::containing(pos)).collect(),
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    fn board() -> Board {
+        // if (0, 0) is 1 then (0, 1) and (0, 2) both have to be 3
+        board!([[{ 1, 2 }, { 1, 3 }, { 1, 3 }, { 2, 4 }, 5, 6, 7, 8, 9]])
+    }
+
+    #[test]
+    fn forcing_chain_removes_a_candidate_that_leads_to_a_contradiction() {
+        let deduction = ForcingChains {
+            max_length: ForcingChains::DEFAULT_LENGTH,
+        }
+        .find(&board())
+        .unwrap();
+
+        assert_eq!(
+            deduction.eliminations,
+            vec![Elimination {
+                pos: pos!(0, 0),
+                val: cell_val!(1)
+            }]
+        );
+    }
+    #[test]
+    fn forcing_chain_gives_up_past_its_length() {
+        let deduction = ForcingChains { max_length: 1 }.find(&board());
+
+        assert_ne!(
+            deduction.map(|deduction| deduction.eliminations[0]),
+            Some(Elimination {
+                pos: pos!(0, 0),
+                val: cell_val!(1)
+            })
+        );
+    }
+    #[test]
+    fn contradicts_allows_consistent_placements() {
+        assert!(!contradicts(&board(), pos!(0, 0), cell_val!(2), 81));
+    }
+}
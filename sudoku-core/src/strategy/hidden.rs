@@ -0,0 +1,195 @@
+use super::{combinations, value_positions, Deduction, Elimination, Strategy};
+use crate::board::{units, Cell, CellPos, Shape};
+use crate::Board;
+
+/// two values in a unit that can only go in the same two cells
+///
+/// those cells have to hold those values, so every other candidate in them is removed
+pub(crate) struct HiddenPair;
+/// three values in a unit that can only go in three cells between them
+pub(crate) struct HiddenTriple;
+/// four values in a unit that can only go in four cells between them
+pub(crate) struct HiddenQuad;
+
+impl<S: Shape> Strategy<S> for HiddenPair {
+    fn name(&self) -> &'static str {
+        "hidden pair"
+    }
+    fn find(&self, board: &Board<S>) -> Option<Deduction<S>> {
+        find_hidden_subset(board, 2, Strategy::<S>::name(self))
+    }
+}
+impl<S: Shape> Strategy<S> for HiddenTriple {
+    fn name(&self) -> &'static str {
+        "hidden triple"
+    }
+    fn find(&self, board: &Board<S>) -> Option<Deduction<S>> {
+        find_hidden_subset(board, 3, Strategy::<S>::name(self))
+    }
+}
+impl<S: Shape> Strategy<S> for HiddenQuad {
+    fn name(&self) -> &'static str {
+        "hidden quad"
+    }
+    fn find(&self, board: &Board<S>) -> Option<Deduction<S>> {
+        find_hidden_subset(board, 4, Strategy::<S>::name(self))
+    }
+}
+
+/// finds `size` values in a unit that can only go in `size` cells between them
+///
+/// a value doesn't need to be possible in every one of the cells, just in no others
+fn find_hidden_subset<S: Shape>(
+    board: &Board<S>,
+    size: usize,
+    strategy: &'static str,
+) -> Option<Deduction<S>> {
+    units().find_map(|unit| {
+        let values: Vec<_> = value_positions(board, &unit)
+            .into_iter()
+            .filter(|(_, positions)| (2..=size).contains(&positions.len()))
+            .collect();
+        combinations(&values, size).into_iter().find_map(|subset| {
+            let mut cells: Vec<CellPos<S>> = subset
+                .iter()
+                .flat_map(|(_, positions)| positions.iter().copied())
+                .collect();
+            cells.sort();
+            cells.dedup();
+            if cells.len() != size {
+                return None;
+            }
+            let eliminations: Vec<_> = cells
+                .iter()
+                .flat_map(|&pos| match board.cell(pos) {
+                    Cell::Possibilities(set) => set
+                        .iter()
+                        .filter(|val| !subset.iter().any(|(kept, _)| kept == val))
+                        .map(|val| Elimination { pos, val })
+                        .collect(),
+                    Cell::Concrete(_) => vec![],
+                })
+                .collect();
+            Deduction::new(strategy, cells, eliminations)
+        })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn hidden_pair_strips_other_candidates() {
+        let board = board!([[
+            { 1, 2, 3 },
+            { 1, 2, 4 },
+            { 3, 4, 5 },
+            { 3, 5 },
+            { 4, 5 },
+            6,
+            7,
+            8,
+            9
+        ]]);
+
+        assert_eq!(
+            HiddenPair.find(&board).unwrap().eliminations,
+            vec![
+                Elimination {
+                    pos: pos!(0, 0),
+                    val: cell_val!(3)
+                },
+                Elimination {
+                    pos: pos!(0, 1),
+                    val: cell_val!(4)
+                },
+            ]
+        );
+    }
+    #[test]
+    fn hidden_pair_ignores_values_with_more_cells() {
+        let board = board!([[
+            { 1, 2, 3 },
+            { 1, 2, 4 },
+            { 2, 3, 4, 5 },
+            { 3, 5 },
+            { 4, 5 },
+            6,
+            7,
+            8,
+            9
+        ]]);
+
+        assert_eq!(HiddenPair.find(&board), None);
+    }
+    #[test]
+    fn hidden_triple_strips_other_candidates() {
+        let board = board!([[
+            { 1, 2, 4 },
+            { 2, 3, 5 },
+            { 1, 3, 6 },
+            { 4, 5, 6 },
+            { 4, 5 },
+            { 5, 6 },
+            7,
+            8,
+            9
+        ]]);
+
+        assert_eq!(
+            HiddenTriple.find(&board).unwrap().eliminations,
+            vec![
+                Elimination {
+                    pos: pos!(0, 0),
+                    val: cell_val!(4)
+                },
+                Elimination {
+                    pos: pos!(0, 1),
+                    val: cell_val!(5)
+                },
+                Elimination {
+                    pos: pos!(0, 2),
+                    val: cell_val!(6)
+                },
+            ]
+        );
+    }
+    #[test]
+    fn hidden_quad_strips_other_candidates() {
+        let board = board!([[
+            { 1, 2, 5 },
+            { 2, 3, 6 },
+            { 3, 4 },
+            { 1, 4, 5, 6 },
+            { 5, 6 },
+            { 5, 6 },
+            7,
+            8,
+            9
+        ]]);
+
+        assert_eq!(
+            HiddenQuad.find(&board).unwrap().eliminations,
+            vec![
+                Elimination {
+                    pos: pos!(0, 0),
+                    val: cell_val!(5)
+                },
+                Elimination {
+                    pos: pos!(0, 1),
+                    val: cell_val!(6)
+                },
+                Elimination {
+                    pos: pos!(0, 3),
+                    val: cell_val!(5)
+                },
+                Elimination {
+                    pos: pos!(0, 3),
+                    val: cell_val!(6)
+                },
+            ]
+        );
+    }
+}
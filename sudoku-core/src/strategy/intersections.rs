@@ -0,0 +1,174 @@
+use super::{value_positions, Deduction, Elimination, Strategy};
+use crate::board::{intersection, Column, House, Index, Row, Shape, ToSet};
+use crate::Board;
+
+/// every candidate for a value in a house is in the same row or column
+///
+/// the value has to be in that part of the line, so it is removed from the rest of it
+pub(crate) struct Pointing;
+
+impl<S: Shape> Strategy<S> for Pointing {
+    fn name(&self) -> &'static str {
+        "pointing"
+    }
+    fn find(&self, board: &Board<S>) -> Option<Deduction<S>> {
+        find_pointing::<Row, _>(board, Strategy::<S>::name(self))
+            .or_else(|| find_pointing::<Column, _>(board, Strategy::<S>::name(self)))
+    }
+}
+
+/// every candidate for a value in a row or column is in the same house
+///
+/// the value has to be in that part of the house, so it is removed from the rest of it
+pub(crate) struct Claiming;
+
+impl<S: Shape> Strategy<S> for Claiming {
+    fn name(&self) -> &'static str {
+        "claiming"
+    }
+    fn find(&self, board: &Board<S>) -> Option<Deduction<S>> {
+        find_claiming::<Row, _>(board, Strategy::<S>::name(self))
+            .or_else(|| find_claiming::<Column, _>(board, Strategy::<S>::name(self)))
+    }
+}
+
+fn find_pointing<L: ToSet, S: Shape>(
+    board: &Board<S>,
+    strategy: &'static str,
+) -> Option<Deduction<S>> {
+    Index::indexes().find_map(|house| {
+        let cells: Vec<_> = House::cells(house).collect();
+        value_positions(board, &cells)
+            .into_iter()
+            // a value with one spot left is a hidden single, which the unit checks already handle
+            .filter(|(_, positions)| positions.len() > 1)
+            .find_map(|(val, positions)| {
+                let line = L::containing(positions[0]);
+                if positions.iter().any(|&pos| L::containing(pos) != line) {
+                    return None;
+                }
+                let eliminations: Vec<_> = L::cells(line)
+                    .filter(|&pos| House::containing(pos) != house)
+                    .filter(|&pos| board.cell(pos).is_possible(val))
+                    .map(|pos| Elimination { pos, val })
+                    .collect();
+                Deduction::new(strategy, positions, eliminations)
+            })
+    })
+}
+
+fn find_claiming<L: ToSet, S: Shape>(
+    board: &Board<S>,
+    strategy: &'static str,
+) -> Option<Deduction<S>> {
+    Index::indexes().find_map(|line| {
+        let cells: Vec<_> = L::cells(line).collect();
+        value_positions(board, &cells)
+            .into_iter()
+            .filter(|(_, positions)| positions.len() > 1)
+            .find_map(|(val, positions)| {
+                let house = House::containing(positions[0]);
+                if positions.iter().any(|&pos| House::containing(pos) != house) {
+                    return None;
+                }
+                let shared = intersection::<House, L, _>(house, line);
+                let eliminations: Vec<_> = House::cells(house)
+                    .filter(|pos| !shared.contains(pos))
+                    .filter(|&pos| board.cell(pos).is_possible(val))
+                    .map(|pos| Elimination { pos, val })
+                    .collect();
+                Deduction::new(strategy, positions, eliminations)
+            })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn pointing_removes_value_from_rest_of_row() {
+        // 1 can only go in the top row of the first house
+        let board = board!([
+            [{ 1, 2 }, { 1, 3 }, 4, { 1, 5 }, { 1, 6 }, ?, ?, ?, ?]
+            [5, 6, 7, ?, ?, ?, ?, ?, ?]
+            [8, 9, 2, ?, ?, ?, ?, ?, ?]
+        ]);
+
+        assert_eq!(
+            Pointing.find(&board).unwrap().eliminations,
+            vec![
+                Elimination {
+                    pos: pos!(0, 3),
+                    val: cell_val!(1)
+                },
+                Elimination {
+                    pos: pos!(0, 4),
+                    val: cell_val!(1)
+                },
+                Elimination {
+                    pos: pos!(0, 5),
+                    val: cell_val!(1)
+                },
+                Elimination {
+                    pos: pos!(0, 6),
+                    val: cell_val!(1)
+                },
+                Elimination {
+                    pos: pos!(0, 7),
+                    val: cell_val!(1)
+                },
+                Elimination {
+                    pos: pos!(0, 8),
+                    val: cell_val!(1)
+                },
+            ]
+        );
+    }
+    #[test]
+    fn pointing_removes_value_from_rest_of_column() {
+        let board = board!([
+            [{ 1, 2 }, 3, 4, ?, ?, ?, ?, ?, ?]
+            [{ 1, 5 }, 6, 7, ?, ?, ?, ?, ?, ?]
+            [8, 9, 2, ?, ?, ?, ?, ?, ?]
+            [{ 1, 3 }, ?, ?, ?, ?, ?, ?, ?, ?]
+            [3, ?, ?, ?, ?, ?, ?, ?, ?]
+            [4, ?, ?, ?, ?, ?, ?, ?, ?]
+            [5, ?, ?, ?, ?, ?, ?, ?, ?]
+            [6, ?, ?, ?, ?, ?, ?, ?, ?]
+            [7, ?, ?, ?, ?, ?, ?, ?, ?]
+        ]);
+
+        assert_eq!(
+            Pointing.find(&board).unwrap().eliminations,
+            vec![Elimination {
+                pos: pos!(3, 0),
+                val: cell_val!(1)
+            }]
+        );
+    }
+    #[test]
+    fn claiming_removes_value_from_rest_of_house() {
+        // 1 can only go in the first house in the top row
+        let board = board!([
+            [{ 1, 2 }, { 1, 3 }, 4, 5, 6, 7, 8, 9, { 2, 3 }]
+            [{ 1, 2 }, ?, 5, ?, ?, ?, ?, ?, ?]
+            [6, 7, 8, ?, ?, ?, ?, ?, ?]
+        ]);
+
+        assert_eq!(
+            Claiming.find(&board).unwrap().eliminations,
+            vec![
+                Elimination {
+                    pos: pos!(1, 0),
+                    val: cell_val!(1)
+                },
+                Elimination {
+                    pos: pos!(1, 1),
+                    val: cell_val!(1)
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use super::value_positions;
+use crate::board::{units, CellPos, CellVal, Shape};
+use crate::Board;
+
+/// for each value, every pair of cells that are the only two places it can go in some unit
+///
+/// one of the two cells in a strong link has to be the value
+pub(crate) struct StrongLinks<S: Shape>(Vec<Vec<(CellPos<S>, CellPos<S>)>>);
+
+impl<S: Shape> StrongLinks<S> {
+    pub(crate) fn new(board: &Board<S>) -> Self {
+        let mut links = vec![vec![]; S::SIDE];
+        for unit in units() {
+            for (val, positions) in value_positions(board, &unit) {
+                if let [a, b] = positions[..] {
+                    links[val.into_inner() - 1].push((a, b));
+                }
+            }
+        }
+        // a pair in the same line and house shows up twice
+        for links in &mut links {
+            links.sort();
+            links.dedup();
+        }
+        Self(links)
+    }
+    /// the strong links on `val`, in order
+    pub(crate) fn of(&self, val: CellVal<S>) -> &[(CellPos<S>, CellPos<S>)] {
+        &self.0[val.into_inner() - 1]
+    }
+    /// the cells joined to `pos` by a strong link on `val`
+    pub(crate) fn neighbours(
+        &self,
+        val: CellVal<S>,
+        pos: CellPos<S>,
+    ) -> impl Iterator<Item = CellPos<S>> + '_ {
+        self.of(val).iter().filter_map(move |&(a, b)| match () {
+            _ if a == pos => Some(b),
+            _ if b == pos => Some(a),
+            _ => None,
+        })
+    }
+    /// splits the strong links on `val` into connected chains, giving every cell one of two
+    /// colors so that linked cells always differ
+    ///
+    /// either every `true` cell in a chain is `val` or every `false` one is. cells come out in the
+    /// order they were reached, starting from the first cell of the chain's first link
+    pub(crate) fn colorings(&self, val: CellVal<S>) -> Vec<Vec<(CellPos<S>, bool)>> {
+        let mut chains: Vec<Vec<(CellPos<S>, bool)>> = vec![];
+        for &(start, _) in self.of(val) {
+            if chains.iter().flatten().any(|&(pos, _)| pos == start) {
+                continue;
+            }
+            let mut chain = vec![(start, true)];
+            let mut i = 0;
+            while let Some(&(pos, color)) = chain.get(i) {
+                for next in self.neighbours(val, pos) {
+                    if !chain.iter().any(|&(other, _)| other == next) {
+                        chain.push((next, !color));
+                    }
+                }
+                i += 1;
+            }
+            chains.push(chain);
+        }
+        chains
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn strong_links_finds_values_with_two_cells_in_a_unit() {
+        // 1 only fits in (0, 0) and (0, 4) of row 0, which aren't in the same house
+        let board = board!([[{ 1, 2 }, 3, 4, 5, { 1, 6 }, 7, 8, 9, 2]]);
+        let links = StrongLinks::new(&board);
+
+        assert!(links.of(cell_val!(1)).contains(&(pos!(0, 0), pos!(0, 4))));
+    }
+    #[test]
+    fn strong_links_are_not_repeated() {
+        // 1 only fits in (0, 0) and (0, 1), which share a row and a house
+        let board = board!([
+            [{ 1, 2 }, { 1, 3 }, 4, 5, 6, 7, 8, 9, 2]
+            [4, 5, 6, ?, ?, ?, ?, ?, ?]
+            [7, 8, 9, ?, ?, ?, ?, ?, ?]
+        ]);
+        let links = StrongLinks::new(&board);
+        let count = links
+            .of(cell_val!(1))
+            .iter()
+            .filter(|&&link| link == (pos!(0, 0), pos!(0, 1)))
+            .count();
+
+        assert_eq!(count, 1);
+    }
+    #[test]
+    fn colorings_alternate_along_a_chain() {
+        // 1 only fits in (0, 0) and (0, 4) of row 0, and (0, 4) and (5, 4) of column 4
+        let board = board!([
+            [{ 1, 2 }, 3, 4, 5, { 1, 6 }, 7, 8, 9, 2]
+            [?, ?, ?, ?, 2, ?, ?, ?, ?]
+            [?, ?, ?, ?, 3, ?, ?, ?, ?]
+            [?, ?, ?, ?, 4, ?, ?, ?, ?]
+            [?, ?, ?, ?, 5, ?, ?, ?, ?]
+            [?, ?, ?, ?, { 1, 6 }, ?, ?, ?, ?]
+            [?, ?, ?, ?, 7, ?, ?, ?, ?]
+            [?, ?, ?, ?, 8, ?, ?, ?, ?]
+            [?, ?, ?, ?, 9, ?, ?, ?, ?]
+        ]);
+        let chains = StrongLinks::new(&board).colorings(cell_val!(1));
+
+        assert_eq!(
+            chains,
+            vec![vec![
+                (pos!(0, 0), true),
+                (pos!(0, 4), false),
+                (pos!(5, 4), true)
+            ]]
+        );
+    }
+}
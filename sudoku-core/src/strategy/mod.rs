@@ -0,0 +1,303 @@
+mod als;
+mod bug;
+mod coloring;
+mod fish;
+mod forcing;
+mod hidden;
+mod intersections;
+mod links;
+mod naked;
+mod single_digit;
+mod wings;
+
+use crate::{
+    board::{CellPos, CellVal, Shape},
+    Board, Classic, SolveOptions, UpdateError,
+};
+pub(crate) use als::AlsXz;
+pub(crate) use bug::BugPlusOne;
+pub(crate) use coloring::SimpleColoring;
+pub(crate) use fish::{FinnedSwordfish, FinnedXWing, Jellyfish, Swordfish, XWing};
+pub(crate) use forcing::ForcingChains;
+pub(crate) use hidden::{HiddenPair, HiddenQuad, HiddenTriple};
+pub(crate) use intersections::{Claiming, Pointing};
+pub(crate) use links::StrongLinks;
+pub(crate) use naked::{NakedPair, NakedQuad, NakedTriple};
+pub(crate) use single_digit::{Skyscraper, TurbotFish, TwoStringKite};
+use std::{collections::BTreeMap, time::Duration};
+pub(crate) use wings::{WWing, XYWing, XYZWing};
+
+/// a single candidate ruled out for a single cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct Elimination<S: Shape = Classic> {
+    pub(crate) pos: CellPos<S>,
+    pub(crate) val: CellVal<S>,
+}
+
+/// everything a strategy concluded from one instance of its pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Deduction<S: Shape = Classic> {
+    /// name of the strategy that found the pattern
+    pub(crate) strategy: &'static str,
+    /// the cells that make up the pattern, in order
+    pub(crate) cells: Vec<CellPos<S>>,
+    /// sorted so that the same board always reports the same eliminations
+    pub(crate) eliminations: Vec<Elimination<S>>,
+}
+impl<S: Shape> Deduction<S> {
+    /// sorts the cells and eliminations, or returns `None` if there is nothing to eliminate
+    pub(crate) fn new(
+        strategy: &'static str,
+        mut cells: Vec<CellPos<S>>,
+        mut eliminations: Vec<Elimination<S>>,
+    ) -> Option<Self> {
+        cells.sort();
+        cells.dedup();
+        eliminations.sort();
+        eliminations.dedup();
+        (!eliminations.is_empty()).then_some(Deduction {
+            strategy,
+            cells,
+            eliminations,
+        })
+    }
+}
+
+/// a logical technique that removes candidates without guessing
+pub(crate) trait Strategy<S: Shape = Classic> {
+    fn name(&self) -> &'static str;
+    /// finds the first instance of the pattern that removes at least one candidate
+    fn find(&self, board: &Board<S>) -> Option<Deduction<S>>;
+    /// whether the pattern only holds for a puzzle with one solution, so it can't be used while
+    /// searching a puzzle that might have more
+    fn needs_unique_solution(&self) -> bool {
+        false
+    }
+}
+
+/// the strategies the solver tries, in order, once the basic unit checks stop making progress
+///
+/// the ones named in `SolveOptions::disabled_strategies` are left out, and so is bug+1 unless
+/// `SolveOptions::unique_solution` is set
+pub(crate) fn strategies<S: Shape>(options: &SolveOptions<S>) -> Vec<Box<dyn Strategy<S>>> {
+    let all: Vec<Box<dyn Strategy<S>>> = vec![
+        Box::new(Pointing),
+        Box::new(Claiming),
+        Box::new(NakedPair),
+        Box::new(HiddenPair),
+        Box::new(NakedTriple),
+        Box::new(HiddenTriple),
+        Box::new(XWing),
+        Box::new(FinnedXWing),
+        Box::new(Skyscraper),
+        Box::new(TwoStringKite),
+        Box::new(TurbotFish),
+        Box::new(SimpleColoring),
+        Box::new(XYWing),
+        Box::new(WWing),
+        Box::new(NakedQuad),
+        Box::new(HiddenQuad),
+        Box::new(Swordfish),
+        Box::new(FinnedSwordfish),
+        Box::new(Jellyfish),
+        Box::new(XYZWing),
+        Box::new(AlsXz),
+        Box::new(BugPlusOne),
+        Box::new(ForcingChains {
+            max_length: options
+                .max_chain_length
+                .unwrap_or(ForcingChains::DEFAULT_LENGTH),
+        }),
+    ];
+    all.into_iter()
+        .filter(|strategy| !options.disabled_strategies.contains(strategy.name()))
+        .filter(|strategy| options.unique_solution || !strategy.needs_unique_solution())
+        .collect()
+}
+
+/// the name of every strategy the solver can use, in the order they are tried
+pub fn strategy_names() -> Vec<&'static str> {
+    let options = SolveOptions {
+        unique_solution: true,
+        ..Default::default()
+    };
+    strategies::<Classic>(&options)
+        .iter()
+        .map(|strategy| strategy.name())
+        .collect()
+}
+
+/// every way to pick `size` items out of `items`, keeping them in their original order
+pub(crate) fn combinations<T: Clone>(items: &[T], size: usize) -> Vec<Vec<T>> {
+    match (size, items) {
+        (0, _) => vec![vec![]],
+        (_, []) => vec![],
+        (_, [first, rest @ ..]) => {
+            let mut with_first = combinations(rest, size - 1);
+            for combination in &mut with_first {
+                combination.insert(0, first.clone());
+            }
+            with_first.extend(combinations(rest, size));
+            with_first
+        }
+    }
+}
+
+/// the cells each value could still go in within a unit
+///
+/// values come out in order, and values with nowhere left to go (usually because they are
+/// already placed) are left out
+pub(crate) fn value_positions<S: Shape>(
+    board: &Board<S>,
+    unit: &[CellPos<S>],
+) -> Vec<(CellVal<S>, Vec<CellPos<S>>)> {
+    CellVal::cell_vals()
+        .map(|val| {
+            let positions: Vec<_> = unit
+                .iter()
+                .copied()
+                .filter(|&pos| board.cell(pos).is_possible(val))
+                .collect();
+            (val, positions)
+        })
+        .filter(|(_, positions)| !positions.is_empty())
+        .collect()
+}
+
+impl<S: Shape> Board<S> {
+    /// removes every candidate in the deduction from the board
+    ///
+    /// errors if that leaves a cell with nothing left
+    pub(crate) fn apply_deduction(&mut self, deduction: &Deduction<S>) -> Result<(), UpdateError> {
+        for &Elimination { pos, val } in &deduction.eliminations {
+            let cell = self.cell(pos).remove_possibility(val);
+            if cell.possible_is_empty() {
+                Err(UpdateError::Impossible)?
+            }
+            *self.mut_cell(pos) = cell;
+        }
+        Ok(())
+    }
+    /// applies the first deduction found by any strategy
+    ///
+    /// returns `None` if no strategy could remove anything
+    ///
+    /// with `options.profile` on, the time each strategy took to look is added to `profile`
+    pub(crate) fn apply_strategies(
+        &mut self,
+        options: &SolveOptions<S>,
+        profile: &mut BTreeMap<&'static str, Duration>,
+    ) -> Result<Option<Deduction<S>>, UpdateError> {
+        let Some(deduction) = strategies(options)
+            .iter()
+            .find_map(|strategy| options.timed(profile, strategy.name(), || strategy.find(self)))
+        else {
+            return Ok(None);
+        };
+        self.apply_deduction(&deduction)?;
+        Ok(Some(deduction))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn combinations_keeps_order() {
+        assert_eq!(
+            combinations(&[1, 2, 3, 4], 3),
+            vec![vec![1, 2, 3], vec![1, 2, 4], vec![1, 3, 4], vec![2, 3, 4]]
+        );
+    }
+    #[test]
+    fn combinations_of_too_many_is_empty() {
+        assert!(combinations(&[1, 2], 3).is_empty());
+    }
+
+    #[test]
+    fn strategy_names_are_unique() {
+        let mut names = strategy_names();
+        names.sort();
+        names.dedup();
+
+        assert_eq!(names.len(), strategy_names().len());
+    }
+    #[test]
+    fn disabled_strategies_are_left_out() {
+        let options = SolveOptions::<Classic> {
+            disabled_strategies: ["x-wing".to_string()].into(),
+            unique_solution: true,
+            ..Default::default()
+        };
+        let names: Vec<_> = strategies(&options).iter().map(|s| s.name()).collect();
+
+        assert!(!names.contains(&"x-wing"));
+        assert_eq!(names.len(), strategy_names().len() - 1);
+    }
+    #[test]
+    fn bug_plus_one_needs_a_unique_solution() {
+        let names: Vec<_> = strategies::<Classic>(&SolveOptions::default())
+            .iter()
+            .map(|s| s.name())
+            .collect();
+
+        assert!(!names.contains(&"bug+1"));
+        assert!(strategy_names().contains(&"bug+1"));
+    }
+
+    #[test]
+    fn value_positions_skips_placed_values() {
+        let board = board!([[1, { 2, 3 }, { 3, 4 }]]);
+        let unit: Vec<_> = pos!(iter 0, { 0, 1, 2 }).collect();
+
+        assert_eq!(
+            value_positions(&board, &unit),
+            vec![
+                (cell_val!(2), vec![pos!(0, 1)]),
+                (cell_val!(3), vec![pos!(0, 1), pos!(0, 2)]),
+                (cell_val!(4), vec![pos!(0, 2)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn apply_deduction_removes_candidates() {
+        let mut board = board!([[{ 1, 2, 3 }, { 2, 3 }]]);
+        let deduction = Deduction {
+            strategy: "test",
+            cells: vec![],
+            eliminations: vec![
+                Elimination {
+                    pos: pos!(0, 0),
+                    val: cell_val!(2),
+                },
+                Elimination {
+                    pos: pos!(0, 1),
+                    val: cell_val!(3),
+                },
+            ],
+        };
+        board.apply_deduction(&deduction).unwrap();
+
+        assert_eq!(board, board!([[{ 1, 3 }, { 2 }]]));
+    }
+    #[test]
+    fn apply_deduction_errors_when_cell_emptied() {
+        let mut board = board!([[{ 4 }]]);
+        let deduction = Deduction {
+            strategy: "test",
+            cells: vec![],
+            eliminations: vec![Elimination {
+                pos: pos!(0, 0),
+                val: cell_val!(4),
+            }],
+        };
+
+        assert_eq!(
+            board.apply_deduction(&deduction),
+            Err(UpdateError::Impossible)
+        );
+    }
+}
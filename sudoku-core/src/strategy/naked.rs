@@ -0,0 +1,182 @@
+use super::{combinations, Deduction, Elimination, Strategy};
+use crate::board::{units, Candidates, Cell, CellPos, Shape};
+use crate::Board;
+
+/// two cells in a unit with the same two candidates
+///
+/// one of the cells has to be each value, so no other cell in the unit can be either
+pub(crate) struct NakedPair;
+/// three cells in a unit with only three candidates between them
+pub(crate) struct NakedTriple;
+/// four cells in a unit with only four candidates between them
+pub(crate) struct NakedQuad;
+
+impl<S: Shape> Strategy<S> for NakedPair {
+    fn name(&self) -> &'static str {
+        "naked pair"
+    }
+    fn find(&self, board: &Board<S>) -> Option<Deduction<S>> {
+        find_naked_subset(board, 2, Strategy::<S>::name(self))
+    }
+}
+impl<S: Shape> Strategy<S> for NakedTriple {
+    fn name(&self) -> &'static str {
+        "naked triple"
+    }
+    fn find(&self, board: &Board<S>) -> Option<Deduction<S>> {
+        find_naked_subset(board, 3, Strategy::<S>::name(self))
+    }
+}
+impl<S: Shape> Strategy<S> for NakedQuad {
+    fn name(&self) -> &'static str {
+        "naked quad"
+    }
+    fn find(&self, board: &Board<S>) -> Option<Deduction<S>> {
+        find_naked_subset(board, 4, Strategy::<S>::name(self))
+    }
+}
+
+/// finds `size` cells in a unit whose candidates together are only `size` values
+///
+/// those values have to go in those cells, so they are removed from the rest of the unit.
+/// a cell doesn't need every value (`{1, 2}, {2, 3}, {1, 3}` is still a triple)
+fn find_naked_subset<S: Shape>(
+    board: &Board<S>,
+    size: usize,
+    strategy: &'static str,
+) -> Option<Deduction<S>> {
+    units().find_map(|unit| {
+        let candidates: Vec<(CellPos<S>, &Candidates<S>)> = unit
+            .iter()
+            .filter_map(|&pos| match board.cell(pos) {
+                Cell::Possibilities(set) if (2..=size).contains(&set.len()) => Some((pos, set)),
+                _ => None,
+            })
+            .collect();
+        combinations(&candidates, size)
+            .into_iter()
+            .find_map(|subset| {
+                let values = Candidates::unions(subset.iter().map(|&(_, set)| set));
+                if values.len() != size {
+                    return None;
+                }
+                let eliminations: Vec<_> = unit
+                    .iter()
+                    .filter(|pos| !subset.iter().any(|(member, _)| member == *pos))
+                    .flat_map(|&pos| match board.cell(pos) {
+                        Cell::Possibilities(set) => set
+                            .intersection(&values)
+                            .into_iter()
+                            .map(|val| Elimination { pos, val })
+                            .collect(),
+                        Cell::Concrete(_) => vec![],
+                    })
+                    .collect();
+                let cells = subset.iter().map(|&(pos, _)| pos).collect();
+                Deduction::new(strategy, cells, eliminations)
+            })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn naked_pair_removes_pair_from_rest_of_unit() {
+        let board = board!([[{ 1, 2 }, { 1, 2 }, { 1, 2, 3 }, { 2, 4 }, 5, 6, 7, 8, 9]]);
+
+        assert_eq!(
+            NakedPair.find(&board).unwrap().eliminations,
+            vec![
+                Elimination {
+                    pos: pos!(0, 2),
+                    val: cell_val!(1)
+                },
+                Elimination {
+                    pos: pos!(0, 2),
+                    val: cell_val!(2)
+                },
+                Elimination {
+                    pos: pos!(0, 3),
+                    val: cell_val!(2)
+                },
+            ]
+        );
+    }
+    #[test]
+    fn naked_pair_ignores_pairs_with_nothing_to_remove() {
+        let board = board!([[{ 1, 2 }, 3, 4, { 1, 2 }, 5, 6, 7, 8, 9]]);
+
+        assert_eq!(NakedPair.find(&board), None);
+    }
+    #[test]
+    fn naked_pair_needs_matching_candidates() {
+        let board = board!([[{ 1, 2 }, { 1, 3 }, { 1, 2, 3 }, 4, 5, 6, 7, 8, 9]]);
+
+        assert_eq!(NakedPair.find(&board), None);
+    }
+    #[test]
+    fn naked_triple_allows_cells_without_every_value() {
+        let board = board!([[{ 1, 2 }, { 2, 3 }, { 1, 3 }, { 1, 4 }, { 3, 4, 5 }, 6, 7, 8, 9]]);
+
+        assert_eq!(
+            NakedTriple.find(&board).unwrap().eliminations,
+            vec![
+                Elimination {
+                    pos: pos!(0, 3),
+                    val: cell_val!(1)
+                },
+                Elimination {
+                    pos: pos!(0, 4),
+                    val: cell_val!(3)
+                },
+            ]
+        );
+    }
+    #[test]
+    fn naked_quad_removes_all_four_values() {
+        let board = board!([[
+            { 1, 2 },
+            { 2, 3 },
+            { 3, 4 },
+            { 1, 4 },
+            { 1, 5, 6 },
+            { 4, 5, 6 },
+            7,
+            8,
+            9
+        ]]);
+
+        assert_eq!(
+            NakedQuad.find(&board).unwrap().eliminations,
+            vec![
+                Elimination {
+                    pos: pos!(0, 4),
+                    val: cell_val!(1)
+                },
+                Elimination {
+                    pos: pos!(0, 5),
+                    val: cell_val!(4)
+                },
+            ]
+        );
+    }
+    #[test]
+    fn naked_quad_ignores_five_values() {
+        let board = board!([[
+            { 1, 2 },
+            { 2, 3 },
+            { 3, 4 },
+            { 1, 5 },
+            { 1, 4, 6 },
+            { 4, 5, 6 },
+            7,
+            8,
+            9
+        ]]);
+
+        assert_eq!(NakedQuad.find(&board), None);
+    }
+}
@@ -0,0 +1,183 @@
+use super::{Deduction, Elimination, Strategy, StrongLinks};
+use crate::board::{CellPos, CellVal, Column, House, Row, Shape, ToSet};
+use crate::Board;
+
+/// two strong links in parallel lines whose ends on one side see each other
+///
+/// at least one of the two far ends has to be the value, so it is removed from every cell that
+/// sees both of them
+pub(crate) struct Skyscraper;
+/// a strong link in a row and one in a column whose ends on one side share a house
+pub(crate) struct TwoStringKite;
+/// any two strong links on a value whose ends on one side see each other
+pub(crate) struct TurbotFish;
+
+impl<S: Shape> Strategy<S> for Skyscraper {
+    fn name(&self) -> &'static str {
+        "skyscraper"
+    }
+    fn find(&self, board: &Board<S>) -> Option<Deduction<S>> {
+        find_turbot(board, Strategy::<S>::name(self), |first, second, _, _| {
+            first == second && first != LinkKind::House
+        })
+    }
+}
+impl<S: Shape> Strategy<S> for TwoStringKite {
+    fn name(&self) -> &'static str {
+        "two-string kite"
+    }
+    fn find(&self, board: &Board<S>) -> Option<Deduction<S>> {
+        find_turbot(board, Strategy::<S>::name(self), |first, second, b, c| {
+            first == LinkKind::Row
+                && second == LinkKind::Column
+                && House::containing(b) == House::containing(c)
+        })
+    }
+}
+impl<S: Shape> Strategy<S> for TurbotFish {
+    fn name(&self) -> &'static str {
+        "turbot fish"
+    }
+    fn find(&self, board: &Board<S>) -> Option<Deduction<S>> {
+        find_turbot(board, Strategy::<S>::name(self), |_, _, _, _| true)
+    }
+}
+
+/// the kind of unit a strong link lives in, preferring lines over houses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LinkKind {
+    Row,
+    Column,
+    House,
+}
+
+impl LinkKind {
+    fn of<S: Shape>(a: CellPos<S>, b: CellPos<S>) -> Self {
+        if Row::containing(a) == Row::containing(b) {
+            LinkKind::Row
+        } else if Column::containing(a) == Column::containing(b) {
+            LinkKind::Column
+        } else {
+            LinkKind::House
+        }
+    }
+}
+
+/// finds strong links `a = b` and `c = d` on one value where `b` sees `c`, and removes the
+/// value from cells seeing both `a` and `d`
+///
+/// `accepts` gets the kinds of the two links and the two cells joining them
+fn find_turbot<S: Shape>(
+    board: &Board<S>,
+    strategy: &'static str,
+    accepts: impl Fn(LinkKind, LinkKind, CellPos<S>, CellPos<S>) -> bool,
+) -> Option<Deduction<S>> {
+    let links = StrongLinks::new(board);
+    CellVal::cell_vals().find_map(|val| {
+        // each link can be walked from either end
+        let ends: Vec<_> = links
+            .of(val)
+            .iter()
+            .flat_map(|&(a, b)| [(a, b), (b, a)])
+            .collect();
+        ends.iter().find_map(|&(a, b)| {
+            ends.iter().find_map(|&(c, d)| {
+                let distinct = [a, b].iter().all(|pos| ![c, d].contains(pos));
+                if !distinct
+                    || !board.sees(b, c)
+                    || !accepts(LinkKind::of(a, b), LinkKind::of(c, d), b, c)
+                {
+                    return None;
+                }
+                let eliminations = board
+                    .peers(a)
+                    .filter(|&pos| board.sees(pos, d))
+                    .filter(|&pos| board.cell(pos).is_possible(val))
+                    .map(|pos| Elimination { pos, val })
+                    .collect();
+                Deduction::new(strategy, vec![a, b, c, d], eliminations)
+            })
+        })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    fn eliminate_one(cells: Vec<CellPos>) -> Vec<Elimination> {
+        cells
+            .into_iter()
+            .map(|pos| Elimination {
+                pos,
+                val: cell_val!(1),
+            })
+            .collect()
+    }
+    fn kite() -> Board {
+        // 1 only fits in (0, 1) and (0, 6) of row 0 and (2, 0) and (7, 0) of column 0
+        board!([
+            [2, { 1, 2 }, 3, 4, 5, 6, { 1, 7 }, 7, 8]
+            [3, ?, ?, ?, ?, ?, ?, ?, ?]
+            [{ 1, 3 }, ?, ?, ?, ?, ?, ?, ?, ?]
+            [4, ?, ?, ?, ?, ?, ?, ?, ?]
+            [5, ?, ?, ?, ?, ?, ?, ?, ?]
+            [6, ?, ?, ?, ?, ?, ?, ?, ?]
+            [7, ?, ?, ?, ?, ?, ?, ?, ?]
+            [{ 1, 8 }, ?, ?, ?, ?, ?, ?, ?, ?]
+            [9, ?, ?, ?, ?, ?, ?, ?, ?]
+        ])
+    }
+
+    #[test]
+    fn link_kind_prefers_lines() {
+        assert_eq!(LinkKind::of(pos!(0, 0), pos!(0, 1)), LinkKind::Row);
+        assert_eq!(LinkKind::of(pos!(0, 0), pos!(2, 0)), LinkKind::Column);
+        assert_eq!(LinkKind::of(pos!(0, 0), pos!(2, 1)), LinkKind::House);
+    }
+    #[test]
+    fn skyscraper_removes_value_from_cells_seeing_both_tops() {
+        // 1 only fits in (0, 0) and (0, 4) of row 0 and (4, 0) and (4, 5) of row 4
+        let board = board!([
+            [{ 1, 2 }, 2, 3, 4, { 1, 5 }, 5, 6, 7, 8]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [?, ?, ?, ?, ?, ?, ?, ?, ?]
+            [{ 1, 2 }, 2, 3, 4, 5, { 1, 6 }, 6, 7, 8]
+        ]);
+        let deduction = Skyscraper.find(&board).unwrap();
+
+        assert_eq!(
+            deduction.cells,
+            vec![pos!(0, 0), pos!(0, 4), pos!(4, 0), pos!(4, 5)]
+        );
+        assert_eq!(
+            deduction.eliminations,
+            eliminate_one(vec![pos!(1, 5), pos!(2, 5), pos!(3, 4), pos!(5, 4)])
+        );
+    }
+    #[test]
+    fn two_string_kite_joins_a_row_and_a_column_in_a_house() {
+        let deduction = TwoStringKite.find(&kite()).unwrap();
+
+        assert_eq!(
+            deduction.cells,
+            vec![pos!(0, 1), pos!(0, 6), pos!(2, 0), pos!(7, 0)]
+        );
+        assert_eq!(deduction.eliminations, eliminate_one(vec![pos!(7, 6)]));
+    }
+    #[test]
+    fn skyscraper_needs_parallel_links() {
+        assert_eq!(Skyscraper.find(&kite()), None);
+    }
+    #[test]
+    fn turbot_fish_finds_any_chain_of_two_links() {
+        assert_eq!(
+            TurbotFish
+                .find(&kite())
+                .map(|deduction| deduction.eliminations),
+            Some(eliminate_one(vec![pos!(7, 6)]))
+        );
+    }
+}
@@ -0,0 +1,279 @@
+use super::{Deduction, Elimination, Strategy, StrongLinks};
+use crate::board::{Cell, CellPos, CellVal, Shape};
+use crate::Board;
+
+/// a pivot `{x, y}` that sees two pincers `{x, z}` and `{y, z}`
+///
+/// whichever value the pivot takes, one of the pincers has to be `z`, so `z` is removed from
+/// every cell that sees both pincers
+pub(crate) struct XYWing;
+/// a pivot `{x, y, z}` that sees two pincers `{x, z}` and `{y, z}`
+///
+/// one of the three cells has to be `z`, so `z` is removed from every cell that sees all three
+pub(crate) struct XYZWing;
+/// two cells with the same candidates `{x, y}` joined by a strong link on `x`
+///
+/// if neither cell were `y` they would both be `x`, which the link rules out, so `y` is removed
+/// from every cell that sees both of them
+pub(crate) struct WWing;
+
+impl<S: Shape> Strategy<S> for XYWing {
+    fn name(&self) -> &'static str {
+        "xy-wing"
+    }
+    fn find(&self, board: &Board<S>) -> Option<Deduction<S>> {
+        let cells = bivalue_cells(board);
+        cells.iter().find_map(|&(pivot, [x, y])| {
+            let pincers = |with: CellVal<S>| {
+                cells.iter().filter_map(move |&(pos, vals)| {
+                    let z = other_value(vals, with)?;
+                    (board.sees(pivot, pos) && z != x && z != y).then_some((pos, z))
+                })
+            };
+            pincers(x).find_map(|(first, z)| {
+                pincers(y)
+                    .filter(|&(_, other_z)| other_z == z)
+                    .find_map(|(second, _)| {
+                        let eliminations = board
+                            .peers(first)
+                            .filter(|&pos| board.sees(pos, second))
+                            .filter(|&pos| board.cell(pos).is_possible(z))
+                            .map(|pos| Elimination { pos, val: z })
+                            .collect();
+                        Deduction::new(
+                            Strategy::<S>::name(self),
+                            vec![pivot, first, second],
+                            eliminations,
+                        )
+                    })
+            })
+        })
+    }
+}
+
+impl<S: Shape> Strategy<S> for XYZWing {
+    fn name(&self) -> &'static str {
+        "xyz-wing"
+    }
+    fn find(&self, board: &Board<S>) -> Option<Deduction<S>> {
+        let pairs = bivalue_cells(board);
+        let mut pivots = CellPos::all().filter_map(|pos| match board.cell(pos) {
+            Cell::Possibilities(set) if set.len() == 3 => Some((pos, set)),
+            _ => None,
+        });
+        pivots.find_map(|(pivot, set)| {
+            let pincers: Vec<_> = pairs
+                .iter()
+                .filter(|&&(pos, [a, b])| {
+                    board.sees(pivot, pos) && set.contains(a) && set.contains(b)
+                })
+                .collect();
+            pincers
+                .iter()
+                .enumerate()
+                .find_map(|(i, &&(first, first_vals))| {
+                    pincers[i + 1..].iter().find_map(|&&(second, second_vals)| {
+                        // the pincers share exactly one value, which is z
+                        let z = match (first_vals, second_vals) {
+                            ([a, b], [c, d]) if (a, b) == (c, d) => return None,
+                            ([a, _], [c, d]) if a == c || a == d => a,
+                            ([_, b], _) => b,
+                        };
+                        let eliminations = board
+                            .peers(pivot)
+                            .filter(|&pos| board.sees(pos, first) && board.sees(pos, second))
+                            .filter(|&pos| board.cell(pos).is_possible(z))
+                            .map(|pos| Elimination { pos, val: z })
+                            .collect();
+                        Deduction::new(
+                            Strategy::<S>::name(self),
+                            vec![pivot, first, second],
+                            eliminations,
+                        )
+                    })
+                })
+        })
+    }
+}
+
+impl<S: Shape> Strategy<S> for WWing {
+    fn name(&self) -> &'static str {
+        "w-wing"
+    }
+    fn find(&self, board: &Board<S>) -> Option<Deduction<S>> {
+        let cells = bivalue_cells(board);
+        let links = StrongLinks::new(board);
+        cells.iter().enumerate().find_map(|(i, &(first, vals))| {
+            cells[i + 1..]
+                .iter()
+                .filter(|&&(second, other)| other == vals && !board.sees(first, second))
+                .find_map(|&(second, [x, y])| {
+                    [(x, y), (y, x)].into_iter().find_map(|(linked, removed)| {
+                        let (start, end) = links.of(linked).iter().find_map(|&(a, b)| {
+                            let joins = |a, b| {
+                                board.sees(first, a)
+                                    && board.sees(second, b)
+                                    && ![first, second].contains(&a)
+                                    && ![first, second].contains(&b)
+                            };
+                            match () {
+                                _ if joins(a, b) => Some((a, b)),
+                                _ if joins(b, a) => Some((b, a)),
+                                _ => None,
+                            }
+                        })?;
+                        let eliminations = board
+                            .peers(first)
+                            .filter(|&pos| board.sees(pos, second))
+                            .filter(|&pos| board.cell(pos).is_possible(removed))
+                            .map(|pos| Elimination { pos, val: removed })
+                            .collect();
+                        Deduction::new(
+                            Strategy::<S>::name(self),
+                            vec![first, second, start, end],
+                            eliminations,
+                        )
+                    })
+                })
+        })
+    }
+}
+
+/// every unsolved cell with exactly two candidates, with the candidates in order
+pub(crate) fn bivalue_cells<S: Shape>(board: &Board<S>) -> Vec<(CellPos<S>, [CellVal<S>; 2])> {
+    CellPos::all()
+        .filter_map(|pos| match board.cell(pos) {
+            Cell::Possibilities(set) if set.len() == 2 => {
+                let mut vals: Vec<_> = set.iter().collect();
+                vals.sort();
+                Some((pos, [vals[0], vals[1]]))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// if one of the pair is `val`, the other one
+fn other_value<S: Shape>([a, b]: [CellVal<S>; 2], val: CellVal<S>) -> Option<CellVal<S>> {
+    match val {
+        _ if val == a => Some(b),
+        _ if val == b => Some(a),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::macros::*;
+
+    #[test]
+    fn xy_wing_removes_z_from_cells_seeing_both_pincers() {
+        // pivot {1, 2} at (0, 0), pincers {1, 3} at (0, 5) and {2, 3} at (4, 0)
+        let board = board!([
+            [{ 1, 2 }, 4, 5, 6, 7, { 1, 3 }, 8, 9, { 1, 2, 3 }]
+            [6, 7, 8, ?, ?, ?, ?, ?, ?]
+            [9, ?, ?, ?, ?, ?, ?, ?, ?]
+            [4, ?, ?, ?, ?, ?, ?, ?, ?]
+            [{ 2, 3 }, ?, ?, ?, ?, { 3, 5 }, ?, ?, ?]
+        ]);
+        let deduction = XYWing.find(&board).unwrap();
+
+        assert_eq!(deduction.cells, vec![pos!(0, 0), pos!(0, 5), pos!(4, 0)]);
+        assert_eq!(
+            deduction.eliminations,
+            vec![Elimination {
+                pos: pos!(4, 5),
+                val: cell_val!(3)
+            }]
+        );
+    }
+    #[test]
+    fn xy_wing_needs_the_pincers_to_share_z() {
+        let board = board!([
+            [{ 1, 2 }, 4, 5, 6, 7, { 1, 3 }, 8, 9, { 1, 2, 3 }]
+            [6, 7, 8, ?, ?, ?, ?, ?, ?]
+            [9, ?, ?, ?, ?, ?, ?, ?, ?]
+            [3, ?, ?, ?, ?, ?, ?, ?, ?]
+            [{ 2, 5 }, ?, ?, ?, ?, { 3, 5 }, ?, ?, ?]
+        ]);
+
+        assert_eq!(XYWing.find(&board), None);
+    }
+    #[test]
+    fn xyz_wing_removes_z_from_cells_seeing_all_three() {
+        // pivot {1, 2, 3} at (0, 0), pincers {1, 3} at (0, 5) and {2, 3} at (1, 1)
+        let board = board!([
+            [{ 1, 2, 3 }, 4, { 3, 5 }, 6, 7, { 1, 3 }, 8, 9, { 2, 5 }]
+            [6, { 2, 3 }, 8, ?, ?, ?, ?, ?, ?]
+            [9, ?, ?, ?, ?, ?, ?, ?, ?]
+        ]);
+        let deduction = XYZWing.find(&board).unwrap();
+
+        assert_eq!(deduction.cells, vec![pos!(0, 0), pos!(0, 5), pos!(1, 1)]);
+        assert_eq!(
+            deduction.eliminations,
+            vec![Elimination {
+                pos: pos!(0, 2),
+                val: cell_val!(3)
+            }]
+        );
+    }
+    #[test]
+    fn xyz_wing_leaves_cells_that_miss_the_pivot() {
+        // (1, 5) sees both pincers but not the pivot
+        let board = board!([
+            [{ 1, 2, 3 }, 4, 5, 6, 7, { 1, 3 }, 8, 9, { 2, 5 }]
+            [6, { 2, 3 }, 8, 1, 2, { 3, 4 }, 5, 7, 9]
+            [9, ?, ?, ?, ?, ?, ?, ?, ?]
+        ]);
+
+        assert_eq!(XYZWing.find(&board), None);
+    }
+    #[test]
+    fn w_wing_removes_the_other_value_from_cells_seeing_both() {
+        // {1, 2} at (0, 0) and (4, 8), with 1 only fitting in (0, 5) and (4, 5) of column 5
+        let board = board!([
+            [{ 1, 2 }, ?, ?, ?, ?, { 1, 5 }, ?, ?, ?]
+            [?, ?, ?, ?, ?, 2, ?, ?, ?]
+            [?, ?, ?, ?, ?, 3, ?, ?, ?]
+            [?, ?, ?, ?, ?, 4, ?, ?, ?]
+            [?, ?, ?, ?, ?, { 1, 5 }, ?, ?, { 1, 2 }]
+            [?, ?, ?, ?, ?, 6, ?, ?, ?]
+            [?, ?, ?, ?, ?, 7, ?, ?, ?]
+            [?, ?, ?, ?, ?, 8, ?, ?, ?]
+            [?, ?, ?, ?, ?, 9, ?, ?, ?]
+        ]);
+        let deduction = WWing.find(&board).unwrap();
+
+        assert_eq!(
+            deduction.cells,
+            vec![pos!(0, 0), pos!(0, 5), pos!(4, 5), pos!(4, 8)]
+        );
+        assert_eq!(
+            deduction.eliminations,
+            [pos!(0, 8), pos!(4, 0)]
+                .into_iter()
+                .map(|pos| Elimination {
+                    pos,
+                    val: cell_val!(2)
+                })
+                .collect::<Vec<_>>()
+        );
+    }
+    #[test]
+    fn w_wing_needs_a_strong_link() {
+        let board = board!([
+            [{ 1, 2 }, ?, ?, ?, ?, { 1, 5 }, ?, ?, ?]
+            [?, ?, ?, ?, ?, 2, ?, ?, ?]
+            [?, ?, ?, ?, ?, 3, ?, ?, ?]
+            [?, ?, ?, ?, ?, 4, ?, ?, ?]
+            [?, ?, ?, ?, ?, { 1, 5 }, ?, ?, { 1, 2 }]
+            [?, ?, ?, ?, ?, 6, ?, ?, ?]
+            [?, ?, ?, ?, ?, 7, ?, ?, ?]
+            [?, ?, ?, ?, ?, 8, ?, ?, ?]
+        ]);
+
+        assert_eq!(WWing.find(&board), None);
+    }
+}
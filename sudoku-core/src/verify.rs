@@ -0,0 +1,122 @@
+//! end to end checks that every solver engine agrees with the grid a puzzle was dug from
+//!
+//! only built with the `verify` feature. `tests/round_trip.rs` runs `round_trip` over a range of
+//! seeds, and other crates can use it to check a build the same way
+
+use crate::{
+    board::geometry::house_cells, Board, Classic, SolveError, SolvedBoard, Solver, SolverBackend,
+};
+use rand::{seq::SliceRandom, Rng};
+
+/// a puzzle that made it through every engine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoundTrip {
+    /// the grid the puzzle was dug from
+    pub grid: SolvedBoard,
+    pub puzzle: Board,
+    /// whether `grid` is the puzzle's only solution
+    pub unique: bool,
+}
+
+/// an engine that didn't solve a puzzle the way `round_trip` expects
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub engine: SolverBackend,
+    pub grid: SolvedBoard,
+    pub puzzle: Board,
+    /// what the engine gave back
+    pub got: Result<Board, SolveError>,
+}
+
+/// a random solved grid
+pub fn random_grid<R: Rng + ?Sized>(rng: &mut R) -> SolvedBoard {
+    let empty = Board::build(vec![vec![None; 9]; 9]).expect("an empty board is valid");
+    let solution = empty
+        .sample_solution(rng)
+        .expect("an empty board has solutions");
+    SolvedBoard::try_from(solution).expect("a sampled solution is complete")
+}
+
+/// `grid` with all but `givens` random cells blanked out
+///
+/// nothing is done to keep the solution unique, so fewer givens make more puzzles with several
+pub fn dig<R: Rng + ?Sized>(grid: &SolvedBoard, givens: usize, rng: &mut R) -> Board {
+    let values: [[usize; 9]; 9] = grid.clone().into();
+    let mut kept = [false; 81];
+    kept[..givens.min(81)].fill(true);
+    kept.shuffle(rng);
+    let lines = values
+        .iter()
+        .zip(kept.chunks(9))
+        .map(|(row, kept)| {
+            row.iter()
+                .zip(kept)
+                .map(|(&val, &kept)| kept.then_some(val as u8))
+                .collect()
+        })
+        .collect();
+    Board::build(lines).expect("a dug grid only has values from the grid")
+}
+
+/// digs a puzzle with `givens` givens out of a random grid and solves it with every engine
+///
+/// if the grid is the only solution every engine has to find it. otherwise any grid that keeps
+/// the givens and follows the rules will do, and so will giving up
+pub fn round_trip<R: Rng + ?Sized>(givens: usize, rng: &mut R) -> Result<RoundTrip, Mismatch> {
+    let grid = random_grid(rng);
+    let puzzle = dig(&grid, givens, rng);
+    let unique = puzzle.count_solutions(2) == Ok(1);
+    let expected: [[usize; 9]; 9] = grid.clone().into();
+    for engine in SolverBackend::ALL {
+        let got = Solver::builder()
+            .backend(engine)
+            .build()
+            .solve(puzzle.clone())
+            .map(|(board, _)| board);
+        let solved = got
+            .clone()
+            .ok()
+            .and_then(|board| SolvedBoard::try_from(board).ok())
+            .map(<[[usize; 9]; 9]>::from);
+        let agrees = match solved {
+            Some(solved) if unique => solved == expected,
+            Some(solved) => follows_the_rules(&solved) && keeps_givens(&puzzle, &solved),
+            // the uniqueness strategies can rule out every solution of a puzzle with several, and
+            // annealing can run out of moves on any puzzle
+            None => (!unique || engine == SolverBackend::Anneal) && got.is_err(),
+        };
+        if !agrees {
+            return Err(Mismatch {
+                engine,
+                grid,
+                puzzle,
+                got,
+            });
+        }
+    }
+    Ok(RoundTrip {
+        grid,
+        puzzle,
+        unique,
+    })
+}
+
+/// every row, column, and house has each value once
+fn follows_the_rules(grid: &[[usize; 9]; 9]) -> bool {
+    let houses = house_cells::<Classic>();
+    (0..9).all(|i| {
+        let row = (0..9).map(|j| grid[i][j]);
+        let column = (0..9).map(|j| grid[j][i]);
+        let house = houses[i].iter().map(|&(row, column)| grid[row][column]);
+        let units: [Vec<usize>; 3] = [row.collect(), column.collect(), house.collect()];
+        units
+            .iter()
+            .all(|unit| (1..=9).all(|val| unit.contains(&val)))
+    })
+}
+
+fn keeps_givens(puzzle: &Board, grid: &[[usize; 9]; 9]) -> bool {
+    let givens = puzzle.givens();
+    let values: [[Option<usize>; 9]; 9] = puzzle.clone().into();
+    (0..81).all(|i| !givens[i / 9][i % 9] || values[i / 9][i % 9] == Some(grid[i / 9][i % 9]))
+}
@@ -0,0 +1,105 @@
+//! rendered output compared against the files in `tests/golden`
+//!
+//! after a deliberate change to a renderer, run `BLESS=1 cargo test -p sudoku-core --test golden`
+//! to rewrite the files from the current output, and check the diff before committing it
+
+use std::{env, fs, path::PathBuf};
+use sudoku_core::{io, Board, HeatMap};
+
+/// compares `actual` with the golden file `name`, or overwrites the file if `BLESS` is set
+fn assert_golden(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(name);
+    if env::var_os("BLESS").is_some() {
+        fs::write(&path, actual).unwrap_or_else(|err| panic!("writing {}: {err}", path.display()));
+        return;
+    }
+    let expected = fs::read_to_string(&path).unwrap_or_else(|err| {
+        panic!(
+            "reading {}: {err}\nrun with BLESS=1 to create it",
+            path.display()
+        )
+    });
+    if expected == actual {
+        return;
+    }
+    let (mut expected_lines, mut actual_lines) = (expected.lines(), actual.lines());
+    for line in 1.. {
+        let (want, got) = (expected_lines.next(), actual_lines.next());
+        if want != got {
+            panic!(
+                "{} differs at line {line}\n  expected: {want:?}\n  actual:   {got:?}\nrun with BLESS=1 to accept the new output",
+                path.display()
+            );
+        }
+        if want.is_none() {
+            break;
+        }
+    }
+    panic!(
+        "{} only differs in its line endings\nrun with BLESS=1 to accept the new output",
+        path.display()
+    );
+}
+
+/// the example from the pencilmarks module, with ragged columns that writing evens out
+const GRID: &str = "\
+.-------------------.--------------.---------------.
+| 5   3     1246    | 2689 7  2468 | 14689 1249 248 |
+| 6   247   247     | 1    9  5    | 3478  234  2478 |
+| 12  9     8       | 23   34 234  | 1345  6    247 |
+:-------------------+--------------+---------------:
+| 8   12457 123579  | 5679 6  1457 | 4579  2459 3   |
+| 4   257   25679   | 8    5  3    | 579   259  1   |
+| 7   145   1359    | 59   2  149  | 4589  459  6   |
+:-------------------+--------------+---------------:
+| 139 6     134579  | 357  35 37   | 2     8    47  |
+| 23  278   237     | 4    1  9    | 367   3    5   |
+| 123 1245  12345   | 2356 8  26   | 1346  7    9   |
+'-------------------'--------------'---------------'
+";
+
+/// a board with only the first row given, 1 to 9
+fn first_row() -> Board {
+    let mut lines = vec![vec![None; 9]; 9];
+    lines[0] = (1..=9).map(Some).collect();
+    Board::build(lines).unwrap()
+}
+
+#[test]
+fn pencilmarks() {
+    let board = io::read_pencilmarks(GRID).unwrap();
+
+    assert_golden("pencilmarks.txt", &io::write_pencilmarks(&board));
+}
+#[test]
+fn heat_map_svg() {
+    let mut map = HeatMap {
+        samples: 4,
+        counts: [[[0; 9]; 9]; 9],
+    };
+    for (r, row) in map.counts.iter_mut().enumerate() {
+        for (c, cell) in row.iter_mut().enumerate() {
+            cell[(r * 3 + r / 3 + c) % 9] = 4;
+        }
+    }
+    map.counts[0][0] = [2, 0, 0, 0, 1, 0, 0, 0, 1];
+    map.counts[4][4] = [0, 2, 0, 0, 0, 0, 2, 0, 0];
+
+    assert_golden("heat_map.svg", &io::heat_map_svg(&map));
+}
+#[test]
+fn constraint_graph_dot() {
+    assert_golden(
+        "constraint_graph.dot",
+        &first_row().constraint_graph().to_dot(),
+    );
+}
+#[test]
+fn constraint_graph_graphml() {
+    assert_golden(
+        "constraint_graph.graphml",
+        &first_row().constraint_graph().to_graphml(),
+    );
+}
@@ -0,0 +1,21 @@
+//! every engine against puzzles dug out of random grids, see `sudoku_core::verify`
+//!
+//! run with `cargo test -p sudoku-core --features verify --test round_trip`
+
+use rand::{rngs::StdRng, SeedableRng};
+use sudoku_core::verify::round_trip;
+
+#[test]
+fn every_engine_finds_the_grid_a_puzzle_was_dug_from() {
+    let mut unique = 0;
+    for seed in 0..20 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        // from a few givens, which almost never have one solution, up to plenty
+        let givens = 20 + seed as usize * 2;
+        match round_trip(givens, &mut rng) {
+            Ok(trip) => unique += usize::from(trip.unique),
+            Err(mismatch) => panic!("seed {seed}: {mismatch:#?}"),
+        }
+    }
+    assert!(unique > 0, "none of the puzzles had a unique solution");
+}
@@ -0,0 +1,95 @@
+//! every strategy against the positions that need it, see `sudoku_core::fixtures`
+//!
+//! run with `cargo test -p sudoku-core --features strategy-fixtures --test strategy_fixtures`
+
+use sudoku_core::{
+    fixtures::{check, first_to_fire, fixtures, Fixture, Misfire},
+    strategy_names, Board,
+};
+
+#[test]
+fn each_fixture_needs_its_strategy() {
+    for fixture in fixtures() {
+        if let Err(misfire) = check(&fixture) {
+            panic!("{}: {misfire:?}\n{:#?}", fixture.technique, fixture.board);
+        }
+    }
+}
+
+#[test]
+fn every_strategy_has_a_fixture() {
+    let techniques: Vec<_> = fixtures().iter().map(|fixture| fixture.technique).collect();
+
+    assert_eq!(techniques, strategy_names());
+}
+
+#[test]
+fn fixtures_pin_down_the_deduction() {
+    for fixture in fixtures() {
+        let (technique, eliminations) = first_to_fire(&fixture.board).unwrap();
+
+        assert_eq!(technique, fixture.technique);
+        assert_eq!(eliminations, fixture.eliminations, "{technique}");
+        assert!(!fixture.eliminations.is_empty());
+        // every elimination is a candidate the cell still had, and not its value in the solution
+        let solution = fixture.board.clone().solve().unwrap().values();
+        for &(row, column, value) in &fixture.eliminations {
+            assert!(
+                fixture.board.candidates()[row][column].contains(&value),
+                "{technique}"
+            );
+            assert_ne!(solution[row][column], Some(value), "{technique}");
+        }
+    }
+}
+
+#[test]
+fn check_catches_a_wrong_deduction() {
+    let mut fixture = fixtures().remove(0);
+    let found = fixture.eliminations.clone();
+    fixture.eliminations.push((0, 0, 9));
+
+    assert_eq!(check(&fixture), Err(Misfire::Eliminated(found)));
+    fixture.technique = "claiming";
+    assert_eq!(check(&fixture), Err(Misfire::FiredFirst(Some("pointing"))));
+}
+
+#[test]
+fn check_catches_strategies_firing_where_they_shouldnt() {
+    let mut fixture = fixtures().remove(0);
+    let fired = fixture.also_fired.clone();
+    fixture
+        .also_fired
+        .retain(|&technique| technique != "x-wing");
+
+    assert_eq!(check(&fixture), Err(Misfire::AlsoFired(fired)));
+}
+
+#[test]
+fn check_catches_positions_the_solver_never_reaches() {
+    let fixture = fixtures().remove(0);
+    let with_first_cell = |vals: &[u8]| {
+        let mut candidates: Vec<Vec<Vec<u8>>> = fixture
+            .board
+            .candidates()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|vals| vals.iter().map(|&val| val as u8).collect())
+                    .collect()
+            })
+            .collect();
+        candidates[0][0] = vals.to_vec();
+        Fixture {
+            board: Board::from_candidates(candidates).unwrap(),
+            ..fixture.clone()
+        }
+    };
+
+    // the first row already has a 4
+    assert_eq!(
+        check(&with_first_cell(&[4, 7, 8])),
+        Err(Misfire::Unpropagated)
+    );
+    assert_eq!(check(&with_first_cell(&[4])), Err(Misfire::Unsolvable));
+}